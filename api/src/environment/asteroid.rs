@@ -0,0 +1,124 @@
+use crate::item::{Item, ItemCount, ItemId};
+use crate::utils::math::Point;
+use crate::utils::utils::Float;
+use rand::{Rng, RngCore};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+pub type AsteroidId = Uuid;
+
+/// what a mining laser is able to do
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct MiningLaserSpec {
+    /// max distance to a mined asteroid, in meters
+    pub range: Float,
+    /// units of ore mined per second
+    pub rate: Float,
+}
+
+/// rock with ore which can be mined with mining lasers. Mined ore slowly regenerates
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Asteroid {
+    id: AsteroidId,
+    pos: Point<Float>,
+    ore: ItemId,
+    amount: ItemCount,
+    max_amount: ItemCount,
+    /// units of ore restored per second
+    regeneration: Float,
+    /// restored part of the next unit
+    regeneration_progress: Float,
+}
+
+impl Asteroid {
+    pub fn new(
+        pos: Point<Float>,
+        ore: ItemId,
+        amount: ItemCount,
+        regeneration: Float,
+        rng: &mut dyn RngCore,
+    ) -> Self {
+        Self {
+            id: uuid::Builder::from_random_bytes(rng.random()).into_uuid(),
+            pos,
+            ore,
+            amount,
+            max_amount: amount,
+            regeneration,
+            regeneration_progress: 0.,
+        }
+    }
+
+    pub fn id(&self) -> AsteroidId {
+        self.id
+    }
+
+    pub fn pos(&self) -> Point<Float> {
+        self.pos
+    }
+
+    pub fn ore(&self) -> &ItemId {
+        &self.ore
+    }
+
+    pub fn amount(&self) -> ItemCount {
+        self.amount
+    }
+
+    pub fn max_amount(&self) -> ItemCount {
+        self.max_amount
+    }
+
+    pub fn regeneration(&self) -> Float {
+        self.regeneration
+    }
+
+    /// takes up to `count` units of ore
+    pub(crate) fn mine(&mut self, count: ItemCount) -> Item {
+        let count = count.min(self.amount);
+        self.amount -= count;
+        Item::new(self.ore.clone(), count)
+    }
+
+    pub(crate) fn regenerate(&mut self, dt: Float) {
+        if self.amount >= self.max_amount {
+            self.regeneration_progress = 0.;
+            return;
+        }
+        self.regeneration_progress += self.regeneration * dt;
+        let restored = self.regeneration_progress.floor();
+        self.regeneration_progress -= restored;
+        self.amount = self
+            .amount
+            .saturating_add(restored as ItemCount)
+            .min(self.max_amount);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Asteroid;
+    use crate::item::Item;
+    use crate::utils::simulation_rng::SimulationRng;
+
+    #[test]
+    fn mine_and_regenerate() {
+        let mut asteroid = Asteroid::new(
+            (0., 0.).into(),
+            "ore".to_string(),
+            10,
+            0.5,
+            &mut SimulationRng::new(0),
+        );
+        assert_eq!(asteroid.mine(4), Item::new("ore".to_string(), 4));
+        assert_eq!(asteroid.mine(10), Item::new("ore".to_string(), 6));
+        assert_eq!(asteroid.amount(), 0);
+
+        asteroid.regenerate(1.);
+        assert_eq!(asteroid.amount(), 0);
+        asteroid.regenerate(1.);
+        assert_eq!(asteroid.amount(), 1);
+        asteroid.regenerate(100.);
+        assert_eq!(asteroid.amount(), 10);
+    }
+}
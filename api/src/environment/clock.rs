@@ -0,0 +1,48 @@
+use crate::utils::time_point::{StaticTimePoint, TimePoint};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Simulation time. Advances by a fixed tick duration each time the environment is proceeded
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SimulationClock {
+    now: StaticTimePoint,
+    tick_duration: Duration,
+}
+
+impl Default for SimulationClock {
+    fn default() -> Self {
+        Self::new(Duration::from_secs(1))
+    }
+}
+
+impl SimulationClock {
+    pub fn new(tick_duration: Duration) -> Self {
+        Self {
+            now: Default::default(),
+            tick_duration,
+        }
+    }
+
+    /// time point at the start of the current tick
+    pub fn now(&self) -> StaticTimePoint {
+        self.now
+    }
+
+    /// time passing during one tick
+    pub fn tick_duration(&self) -> Duration {
+        self.tick_duration
+    }
+
+    pub fn set_tick_duration(&mut self, tick_duration: Duration) {
+        self.tick_duration = tick_duration
+    }
+
+    /// time passed since the start of the simulation
+    pub fn elapsed(&self) -> Duration {
+        self.now.duration_since(&StaticTimePoint::default())
+    }
+
+    pub(crate) fn tick(&mut self) {
+        self.now += self.tick_duration
+    }
+}
@@ -1,13 +1,120 @@
-use crate::environment::Nebula;
-use crate::module::{Module, ProcessTokenContext};
-use crate::person::{Logger, ObjectiveDeciderVault};
-use crate::vessel::{Vessel, VesselId, VesselSeed};
+use crate::environment::{
+    Asteroid, AsteroidId, Nebula, SALVAGE_RANGE, SimulationClock, SpatialIndex, Wreck, WreckId,
+    WreckSeed,
+};
+use crate::finance::{Bank, Wallet};
+use crate::item::{
+    ContractId, Item, ItemCount, ItemId, ItemRegistry, ItemStorage, OfferId, OrderId, Settlement,
+    UnknownItemError,
+};
+use crate::module::{
+    ContractBoardConsole, Module, ModuleCapability, ModuleId, ProcessTokenContext, TradingConsole,
+};
+use crate::utils::utils::Float;
+use crate::person::{
+    DynObjective, Logger, ObjectiveDeciderVault, Person, PersonId, PersonSeed, Severity,
+};
+use crate::research::{RESEARCH_DATA_ITEM_ID, Research, TechTree};
+use crate::utils::math::{Point, Rect};
+use crate::utils::simulation_rng::SimulationRng;
+use crate::vessel::{
+    AsteroidContact, Contact, NavTarget, PersonDestination, RadarSpec, TerminalContact,
+    TerminalRef, Vessel, VesselConsole, VesselId, VesselRequestError, VesselSeed, WreckContact,
+};
 use dyn_serde::{DynDeserializeSeedVault, VecSeed};
 use dyn_serde_macro::DeserializeSeedXXX;
+use rand::Rng;
 use serde::de::{DeserializeSeed, MapAccess, SeqAccess, Visitor};
 use serde::{Deserialize, Deserializer, Serialize, Serializer, de};
+use std::cell::{Ref, RefCell};
+use std::collections::BTreeMap;
 use std::fmt;
 use std::fmt::Formatter;
+use std::time::Duration;
+use uuid::Uuid;
+
+/// max distance between vessels at which they can dock to each other or settle trades
+pub static DOCKING_RANGE: Float = 10.;
+/// units of research data per second each powered research lab collects inside a nebula
+static NEBULA_RESEARCH_DATA_RATE: Float = 1.;
+/// time a trading terminal keeps an order for the customer to arrive
+pub static ORDER_LIFETIME: Duration = Duration::from_secs(60 * 60);
+
+/// requests from vessels which affect other vessels. Processed after all vessels proceeded
+#[derive(Debug)]
+pub(crate) enum EnvironmentRequest {
+    /// an undocked vessel which must be added to the environment
+    Release { vessel: Vessel },
+    Dock {
+        vessel_id: VesselId,
+        dst_vessel_id: VesselId,
+        dst_module_id: ModuleId,
+    },
+    /// dock to any free clamp of the destination vessel
+    DockTo {
+        vessel_id: VesselId,
+        dst_vessel_id: VesselId,
+    },
+    UndockFromCarrier {
+        vessel_id: VesselId,
+        carrier_id: VesselId,
+    },
+    /// a person which left module `module_id` of vessel `vessel_id` for a docked vessel
+    TransferPerson {
+        person: Person,
+        vessel_id: VesselId,
+        module_id: ModuleId,
+        destination: PersonDestination,
+    },
+    HarvestGas {
+        vessel_id: VesselId,
+        module_id: ModuleId,
+        count: ItemCount,
+    },
+    Mine {
+        vessel_id: VesselId,
+        asteroid_id: AsteroidId,
+    },
+    Salvage {
+        vessel_id: VesselId,
+        wreck_id: WreckId,
+    },
+    Abandon {
+        vessel_id: VesselId,
+    },
+    Research {
+        owner: PersonId,
+        data: ItemCount,
+    },
+    GatherResearchData {
+        vessel_id: VesselId,
+        wreck_id: WreckId,
+    },
+    PlaceBuyOrder {
+        vessel_id: VesselId,
+        owner: PersonId,
+        terminal: TerminalRef,
+        offer_id: OfferId,
+        count: ItemCount,
+    },
+    PlaceSellOrder {
+        vessel_id: VesselId,
+        owner: PersonId,
+        terminal: TerminalRef,
+        offer_id: OfferId,
+        count: ItemCount,
+    },
+    CancelOrder {
+        terminal: TerminalRef,
+        order_id: OrderId,
+    },
+    AcceptContract {
+        vessel_id: VesselId,
+        owner: PersonId,
+        terminal: TerminalRef,
+        contract_id: ContractId,
+    },
+}
 
 #[derive(Debug, Serialize, DeserializeSeedXXX)]
 #[deserialize_seed_xxx(seed = crate::environment::EnvironmentSeed::<'v>)]
@@ -15,41 +122,1235 @@ pub struct Environment {
     #[deserialize_seed_xxx(seed = self.seed.vessel_seed)]
     vessels: Vec<Vessel>,
     nebulae: Vec<Nebula>,
+    #[serde(default)]
+    asteroids: Vec<Asteroid>,
+    #[serde(default)]
+    #[deserialize_seed_xxx(seed = self.seed.wreck_seed)]
+    wrecks: Vec<Wreck>,
+    /// research progress of each owner
+    #[serde(default)]
+    research: BTreeMap<PersonId, Research>,
+    /// money of persons and vessel owners
+    #[serde(default)]
+    bank: Bank,
+    /// saves older than the rng continue with the sequence of seed 0
+    #[serde(default)]
+    rng: SimulationRng,
+    #[serde(default)]
+    clock: SimulationClock,
+    /// persons which could neither reach their destination nor find a free seat nearby.
+    /// They try to get back on board every tick
+    #[serde(default)]
+    #[deserialize_seed_xxx(seed = self.seed.stranded_seed)]
+    stranded: Vec<StrandedPerson>,
+    /// built on first query after vessels moved or were added or removed
+    #[serde(skip)]
+    index: RefCell<Option<SpatialIndex>>,
 }
 
 pub struct EnvironmentSeed<'v> {
     vessel_seed: VecSeed<VesselSeed<'v>>,
+    wreck_seed: VecSeed<WreckSeed<'v>>,
+    stranded_seed: VecSeed<StrandedPersonSeed<'v>>,
 }
 
 impl<'v> EnvironmentSeed<'v> {
-    pub fn new(vault: &'v DynDeserializeSeedVault<dyn Module>) -> Self {
+    pub fn new(
+        vault: &'v DynDeserializeSeedVault<dyn Module>,
+        objective_vault: &'v DynDeserializeSeedVault<dyn DynObjective>,
+    ) -> Self {
         Self {
             vessel_seed: VecSeed::new(VesselSeed::new(vault)),
+            wreck_seed: VecSeed::new(WreckSeed::new(vault)),
+            stranded_seed: VecSeed::new(StrandedPersonSeed::new(objective_vault)),
+        }
+    }
+}
+
+/// a person which left module `module_id` of vessel `vessel_id` and found no free seat
+#[derive(Debug, Serialize, DeserializeSeedXXX)]
+#[deserialize_seed_xxx(seed = crate::environment::StrandedPersonSeed::<'v>)]
+pub struct StrandedPerson {
+    #[deserialize_seed_xxx(seed = self.seed.person_seed)]
+    person: Person,
+    vessel_id: VesselId,
+    module_id: ModuleId,
+    error: VesselRequestError,
+}
+
+#[derive(Clone)]
+pub struct StrandedPersonSeed<'v> {
+    person_seed: PersonSeed<'v>,
+}
+
+impl<'v> StrandedPersonSeed<'v> {
+    pub fn new(objective_vault: &'v DynDeserializeSeedVault<dyn DynObjective>) -> Self {
+        Self {
+            person_seed: PersonSeed::new(objective_vault),
         }
     }
 }
 
+impl StrandedPerson {
+    pub fn person(&self) -> &Person {
+        &self.person
+    }
+}
+
 impl Environment {
-    pub fn new(vessels: Vec<Vessel>, nebulae: Vec<Nebula>) -> Self {
-        Self { vessels, nebulae }
+    pub fn new(rng: SimulationRng, vessels: Vec<Vessel>, nebulae: Vec<Nebula>) -> Self {
+        Self {
+            vessels,
+            nebulae,
+            asteroids: Vec::new(),
+            wrecks: Vec::new(),
+            research: BTreeMap::new(),
+            bank: Bank::new(),
+            rng,
+            clock: Default::default(),
+            stranded: Vec::new(),
+            index: Default::default(),
+        }
+    }
+
+    pub fn clock(&self) -> &SimulationClock {
+        &self.clock
+    }
+
+    pub fn set_tick_duration(&mut self, tick_duration: Duration) {
+        self.clock.set_tick_duration(tick_duration)
+    }
+
+    pub fn vessels(&self) -> &[Vessel] {
+        &self.vessels
+    }
+
+    pub fn nebulae(&self) -> &[Nebula] {
+        &self.nebulae
+    }
+
+    pub fn with_asteroids(mut self, asteroids: Vec<Asteroid>) -> Self {
+        self.asteroids.extend(asteroids);
+        self
+    }
+
+    pub fn asteroids(&self) -> &[Asteroid] {
+        &self.asteroids
+    }
+
+    pub fn wrecks(&self) -> &[Wreck] {
+        &self.wrecks
+    }
+
+    pub fn research(&self, owner: PersonId) -> Option<&Research> {
+        self.research.get(&owner)
+    }
+
+    pub fn stranded_persons(&self) -> &[StrandedPerson] {
+        &self.stranded
+    }
+
+    pub fn bank(&self) -> &Bank {
+        &self.bank
+    }
+
+    pub fn bank_mut(&mut self) -> &mut Bank {
+        &mut self.bank
+    }
+
+    /// turns the vessel into a wreck keeping a random part of its modules and items.
+    /// Persons aboard move to vessels docked to it, which are released.
+    /// Returns false if there is no such vessel or someone aboard found no seat
+    pub fn wreck_vessel(&mut self, vessel_id: VesselId, item_registry: &ItemRegistry) -> bool {
+        let Some(index) = self.index().index_of(vessel_id) else {
+            return false;
+        };
+        if !self.vessels[index].evacuate() {
+            return false;
+        }
+        let vessel = self.vessels.remove(index);
+        let (wreck, released) = Wreck::from_vessel(vessel, &mut self.rng, item_registry);
+        self.wrecks.push(wreck);
+        self.vessels.extend(released);
+        self.invalidate_index();
+        true
+    }
+
+    /// id drawn from the simulation rng so that runs with the same seed match
+    fn random_id(&mut self) -> Uuid {
+        uuid::Builder::from_random_bytes(self.rng.random()).into_uuid()
+    }
+
+    fn index(&self) -> Ref<SpatialIndex> {
+        if self.index.borrow().is_none() {
+            self.index.replace(Some(SpatialIndex::new(&self.vessels)));
+        }
+        Ref::map(self.index.borrow(), |index| index.as_ref().unwrap())
+    }
+
+    fn invalidate_index(&self) {
+        self.index.replace(None);
+    }
+
+    /// first nebula which contains the point
+    pub fn nebula_at(&self, point: Point<Float>) -> Option<&Nebula> {
+        self.nebulae.iter().find(|nebula| nebula.contains(point))
+    }
+
+    fn radar_factor_at(&self, point: Point<Float>) -> Float {
+        self.nebulae
+            .iter()
+            .filter(|nebula| nebula.contains(point))
+            .map(|nebula| nebula.radar_factor())
+            .fold(1., Float::min)
+    }
+
+    fn speed_factor_at(&self, point: Point<Float>) -> Float {
+        self.nebulae
+            .iter()
+            .filter(|nebula| nebula.contains(point))
+            .map(|nebula| nebula.speed_factor())
+            .fold(1., Float::min)
     }
 
     pub(crate) fn vessel_by_id(&self, id: VesselId) -> Option<&Vessel> {
-        self.vessels.iter().find(|v| v.id() == id)
+        let i = self.index().index_of(id)?;
+        Some(&self.vessels[i])
     }
 
     pub fn vessel_by_id_mut(&mut self, id: VesselId) -> Option<&mut Vessel> {
-        self.vessels.iter_mut().find(|v| v.id() == id)
+        let i = self.index().index_of(id)?;
+        Some(&mut self.vessels[i])
+    }
+
+    /// vessels not farther than `radius` from `center` ordered by distance
+    pub fn vessels_in_radius(&self, center: Point<Float>, radius: Float) -> Vec<&Vessel> {
+        let rect = Rect::from_center(center, (radius * 2., radius * 2.).into());
+        let mut result: Vec<_> = self
+            .index()
+            .candidates(rect)
+            .into_iter()
+            .map(|i| &self.vessels[i])
+            .filter(|vessel| (vessel.pos() - center).len() <= radius)
+            .collect();
+        result.sort_by(|a, b| {
+            (a.pos() - center)
+                .len()
+                .total_cmp(&(b.pos() - center).len())
+        });
+        result
+    }
+
+    /// nearest vessel which has a module with the capability not farther than `radius` from `center`
+    pub fn nearest_with_capability(
+        &self,
+        center: Point<Float>,
+        capability: ModuleCapability,
+        radius: Float,
+    ) -> Option<&Vessel> {
+        self.vessels_in_radius(center, radius)
+            .into_iter()
+            .find(|vessel| {
+                vessel
+                    .modules()
+                    .any(|module| module.capabilities().contains(&capability))
+            })
+    }
+
+    /// checks that every recipe and trading offer in the environment refers to a registered item.
+    /// Docked vessels, stored modules and modules of wrecks are checked as well
+    pub fn validate_items(&self, item_registry: &ItemRegistry) -> Result<(), UnknownItemError> {
+        for vessel in &self.vessels {
+            Self::validate_vessel_items(vessel, item_registry)?;
+        }
+        for wreck in &self.wrecks {
+            for module in wreck.modules() {
+                Self::validate_module_items(module, item_registry)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn validate_vessel_items(
+        vessel: &Vessel,
+        item_registry: &ItemRegistry,
+    ) -> Result<(), UnknownItemError> {
+        for module in vessel.modules() {
+            Self::validate_module_items(module.as_ref(), item_registry)?;
+        }
+        Ok(())
+    }
+
+    fn validate_module_items(
+        module: &dyn Module,
+        item_registry: &ItemRegistry,
+    ) -> Result<(), UnknownItemError> {
+        for recipe in module.recipes() {
+            item_registry.validate_recipe(recipe)?;
+        }
+        for recipe in module.assembly_recipes() {
+            item_registry.validate_input_recipe(recipe.input())?;
+        }
+        if let Some(console) = module.trading_console() {
+            for offer in console.buy_offers() {
+                item_registry.validate_buy_offer(offer)?;
+            }
+            for offer in console.sell_offers() {
+                item_registry.validate_sell_offer(offer)?;
+            }
+        }
+        if module.capabilities().contains(&ModuleCapability::ModuleStorage) {
+            for storage in module.module_storages() {
+                for stored in storage.iter() {
+                    Self::validate_module_items(stored, item_registry)?;
+                }
+            }
+        }
+        if module.capabilities().contains(&ModuleCapability::DockingClamp) {
+            for clamp in module.docking_clamps() {
+                if let Some(docked) = clamp.vessel_docked() {
+                    Self::validate_vessel_items(docked, item_registry)?;
+                }
+            }
+        }
+        Ok(())
     }
 
     pub fn proceed(
         &mut self,
+        item_registry: &ItemRegistry,
+        tech_tree: &TechTree,
         process_token_context: &ProcessTokenContext,
         decider_vault: &ObjectiveDeciderVault,
         logger: &mut dyn Logger,
     ) {
+        let research = &self.research;
+        let locked_modules = |owner: PersonId| {
+            tech_tree.locked_modules(
+                research
+                    .get(&owner)
+                    .map(|research| research.researched())
+                    .unwrap_or(&Default::default()),
+            )
+        };
         for v in &mut self.vessels {
-            v.proceed(process_token_context, decider_vault, logger)
+            v.set_locked_modules(&locked_modules);
+        }
+        self.board_stranded(logger);
+
+        let mut requests = Vec::new();
+        for v in &mut self.vessels {
+            requests.extend(v.proceed(
+                &mut self.rng,
+                &self.clock,
+                item_registry,
+                process_token_context,
+                decider_vault,
+                logger,
+            ))
+        }
+        for request in requests {
+            match request {
+                EnvironmentRequest::Release { vessel } => {
+                    self.vessels.push(vessel);
+                    self.invalidate_index();
+                }
+                EnvironmentRequest::Dock {
+                    vessel_id,
+                    dst_vessel_id,
+                    dst_module_id,
+                } => self.dock(vessel_id, dst_vessel_id, dst_module_id),
+                EnvironmentRequest::DockTo {
+                    vessel_id,
+                    dst_vessel_id,
+                } => self.dock_to(vessel_id, dst_vessel_id),
+                EnvironmentRequest::UndockFromCarrier {
+                    vessel_id,
+                    carrier_id,
+                } => {
+                    if let Some(vessel) = self
+                        .vessel_by_id(carrier_id)
+                        .and_then(|carrier| carrier.undock_vessel(vessel_id))
+                    {
+                        self.vessels.push(vessel);
+                        self.invalidate_index();
+                    }
+                }
+                EnvironmentRequest::TransferPerson {
+                    person,
+                    vessel_id,
+                    module_id,
+                    destination,
+                } => self.transfer_person(person, vessel_id, module_id, destination, logger),
+                EnvironmentRequest::HarvestGas {
+                    vessel_id,
+                    module_id,
+                    count,
+                } => self.harvest_gas(vessel_id, module_id, count, item_registry),
+                EnvironmentRequest::Mine {
+                    vessel_id,
+                    asteroid_id,
+                } => self.mine(vessel_id, asteroid_id, item_registry),
+                EnvironmentRequest::Salvage {
+                    vessel_id,
+                    wreck_id,
+                } => self.salvage(vessel_id, wreck_id, item_registry),
+                EnvironmentRequest::Abandon { vessel_id } => {
+                    let Some(owner) = self.vessel_by_id(vessel_id).map(|vessel| vessel.owner())
+                    else {
+                        continue;
+                    };
+                    if !self.wreck_vessel(vessel_id, item_registry) {
+                        logger.log(
+                            &owner,
+                            Severity::Warning,
+                            format!("Can not abandon vessel {}: no seats for its crew", vessel_id),
+                        );
+                    }
+                }
+                EnvironmentRequest::Research { owner, data } => {
+                    let research = self.research.entry(owner).or_default();
+                    for tech in research.add_data(data, tech_tree) {
+                        logger.log(&owner, Severity::Info, format!("Researched {}", tech.name()));
+                    }
+                }
+                EnvironmentRequest::GatherResearchData {
+                    vessel_id,
+                    wreck_id,
+                } => self.gather_research_data(vessel_id, wreck_id, item_registry),
+                EnvironmentRequest::PlaceBuyOrder {
+                    vessel_id,
+                    owner,
+                    terminal,
+                    offer_id,
+                    count,
+                } => {
+                    let order_id = self.random_id();
+                    let deadline = self.clock.now() + ORDER_LIFETIME;
+                    let placed = self.with_terminal(terminal, |console| {
+                        let offer = console
+                            .buy_offers()
+                            .iter()
+                            .find(|offer| offer.id == offer_id)?
+                            .clone();
+                        console.place_buy_order(order_id, &offer, count, vessel_id, deadline)
+                    });
+                    if placed.is_none() {
+                        logger.log(
+                            &owner,
+                            Severity::Warning,
+                            format!("Buy order at terminal {} rejected", terminal.module_id),
+                        );
+                    }
+                }
+                EnvironmentRequest::PlaceSellOrder {
+                    vessel_id,
+                    owner,
+                    terminal,
+                    offer_id,
+                    count,
+                } => {
+                    let order_id = self.random_id();
+                    let deadline = self.clock.now() + ORDER_LIFETIME;
+                    let mut bank = std::mem::take(&mut self.bank);
+                    let placed = self.terminal_owner(terminal).and_then(|terminal_owner| {
+                        let price = self.with_terminal(terminal, |console| {
+                            let offer = console
+                                .sell_offers()
+                                .iter()
+                                .find(|offer| offer.id == offer_id)?
+                                .clone();
+                            console
+                                .place_sell_order(
+                                    order_id,
+                                    &offer,
+                                    count,
+                                    vessel_id,
+                                    deadline,
+                                    bank.wallet_mut(terminal_owner),
+                                )?
+                                .price(console.orders())
+                        })?;
+                        Some((terminal_owner, price))
+                    });
+                    match placed {
+                        Some((terminal_owner, price)) => bank.record(
+                            self.clock.now(),
+                            Some(terminal_owner),
+                            None,
+                            price,
+                            format!("Reserved for order {} at {}", order_id, terminal.vessel_id),
+                        ),
+                        None => logger.log(
+                            &owner,
+                            Severity::Warning,
+                            format!("Sell order at terminal {} rejected", terminal.module_id),
+                        ),
+                    }
+                    self.bank = bank;
+                }
+                EnvironmentRequest::CancelOrder { terminal, order_id } => {
+                    let mut bank = std::mem::take(&mut self.bank);
+                    let refund = self.terminal_owner(terminal).and_then(|terminal_owner| {
+                        let price = self.with_terminal(terminal, |console| {
+                            // only sell orders reserve money
+                            let price = console.orders().sell_order(order_id).map(|o| o.price());
+                            console
+                                .cancel_order(order_id, bank.wallet_mut(terminal_owner))
+                                .then_some(price)
+                        })??;
+                        Some((terminal_owner, price))
+                    });
+                    if let Some((terminal_owner, price)) = refund {
+                        bank.record(
+                            self.clock.now(),
+                            None,
+                            Some(terminal_owner),
+                            price,
+                            format!("Order {} at {} cancelled", order_id, terminal.vessel_id),
+                        );
+                    }
+                    self.bank = bank;
+                }
+                EnvironmentRequest::AcceptContract {
+                    vessel_id,
+                    owner,
+                    terminal,
+                    contract_id,
+                } => {
+                    let order_id = self.random_id();
+                    let mut bank = std::mem::take(&mut self.bank);
+                    let collateral = self.with_contract_board(terminal, |board| {
+                        let penalty = board
+                            .contracts()
+                            .iter()
+                            .find(|contract| contract.id() == contract_id)?
+                            .penalty();
+                        board
+                            .accept_contract(
+                                contract_id,
+                                order_id,
+                                vessel_id,
+                                bank.wallet_mut(owner),
+                            )
+                            .map(|_| penalty)
+                    });
+                    match collateral {
+                        Some(collateral) => bank.record(
+                            self.clock.now(),
+                            Some(owner),
+                            None,
+                            collateral,
+                            format!(
+                                "Collateral for contract {} at {}",
+                                contract_id, terminal.vessel_id
+                            ),
+                        ),
+                        None => logger.log(
+                            &owner,
+                            Severity::Warning,
+                            format!("Contract {} rejected", contract_id),
+                        ),
+                    }
+                    self.bank = bank;
+                }
+            }
         }
+        self.navigate(item_registry);
+        self.invalidate_index();
+        self.collect_nebula_research_data(item_registry);
+        self.expire_trades(logger);
+        self.settle_trades(item_registry, logger);
+        let dt = self.clock.tick_duration().as_secs_f64();
+        for asteroid in &mut self.asteroids {
+            asteroid.regenerate(dt);
+        }
+        self.scan(item_registry);
+        self.clock.tick();
+    }
+
+    fn navigate(&mut self, item_registry: &ItemRegistry) {
+        let dt = self.clock.tick_duration().as_secs_f64();
+        for i in 0..self.vessels.len() {
+            let nav_target = self.vessels[i].nav_target();
+            let target = match nav_target {
+                None => None,
+                Some(NavTarget::Point(point)) => Some(point),
+                Some(NavTarget::Vessel(id)) => match self.vessels[i].contact(id) {
+                    Some(contact) => Some(contact.pos()),
+                    None => {
+                        // followed vessel is out of radar range, docked somewhere or does not exist anymore
+                        self.vessels[i].set_nav_target(None);
+                        None
+                    }
+                },
+            };
+            let speed_factor = self.speed_factor_at(self.vessels[i].pos());
+            let arrived = self.vessels[i].navigate(target, dt, item_registry, speed_factor);
+            if arrived && matches!(nav_target, Some(NavTarget::Point(_))) {
+                self.vessels[i].set_nav_target(None);
+            }
+        }
+    }
+
+    /// replaces contacts of every powered vessel with vessels, asteroids and wrecks its radars detect
+    fn scan(&mut self, item_registry: &ItemRegistry) {
+        #[allow(clippy::type_complexity)]
+        let contacts: Vec<(
+            Vec<Contact>,
+            Vec<AsteroidContact>,
+            Vec<WreckContact>,
+            Vec<TerminalContact>,
+        )> = self
+            .vessels
+            .iter()
+            .map(|vessel| {
+                if !vessel.powered() {
+                    return Default::default();
+                }
+                let radars: Vec<RadarSpec> =
+                    vessel.modules().filter_map(|module| module.radar()).collect();
+                let Some(range) = radars.iter().map(|radar| radar.range).reduce(Float::max)
+                else {
+                    return Default::default();
+                };
+                let radar_factor = self.radar_factor_at(vessel.pos());
+                // radar with the best resolution among ones which reach the point
+                let radar_for = |pos: Point<Float>| {
+                    let distance = (pos - vessel.pos()).len();
+                    // objects inside a nebula are hidden from outside as well
+                    let radar_factor = radar_factor.min(self.radar_factor_at(pos));
+                    radars
+                        .iter()
+                        .filter(|radar| radar.range * radar_factor >= distance)
+                        .min_by(|a, b| a.resolution.total_cmp(&b.resolution))
+                };
+                let detected: Vec<(&Vessel, &RadarSpec)> = self
+                    .vessels_in_radius(vessel.pos(), range * radar_factor)
+                    .into_iter()
+                    .filter(|other| other.id() != vessel.id())
+                    .filter_map(|other| radar_for(other.pos()).map(|radar| (other, radar)))
+                    .collect();
+                let vessels = detected
+                    .iter()
+                    .map(|(other, radar)| radar.detect(other))
+                    .collect();
+                let terminals = detected
+                    .iter()
+                    .flat_map(|(other, radar)| radar.detect_terminals(other, vessel.id()))
+                    .collect();
+                let asteroids = self
+                    .asteroids
+                    .iter()
+                    .filter_map(|asteroid| {
+                        radar_for(asteroid.pos()).map(|radar| radar.detect_asteroid(asteroid))
+                    })
+                    .collect();
+                let wrecks = self
+                    .wrecks
+                    .iter()
+                    .filter_map(|wreck| radar_for(wreck.pos()).map(|radar| radar.detect_wreck(wreck)))
+                    .collect();
+                (vessels, asteroids, wrecks, terminals)
+            })
+            .collect();
+
+        for (vessel, (contacts, asteroid_contacts, wreck_contacts, terminal_contacts)) in
+            self.vessels.iter_mut().zip(contacts)
+        {
+            let cargo_space = terminal_contacts
+                .iter()
+                .flat_map(|terminal| {
+                    terminal
+                        .buy_offers()
+                        .iter()
+                        .map(|offer| offer.item.clone())
+                        .chain(terminal.sell_offers().iter().map(|offer| offer.item.clone()))
+                })
+                .map(|item| {
+                    let space = Self::cargo_space(vessel, &item, item_registry);
+                    (item, space)
+                })
+                .collect();
+            vessel.set_contacts(contacts, asteroid_contacts, wreck_contacts, terminal_contacts);
+            vessel.set_market_knowledge(self.bank.balance(vessel.owner()), cargo_space);
+        }
+    }
+
+    /// settles orders of trading terminals of powered vessels with customer vessels
+    /// which are docked to the terminal vessel or are in docking range of it
+    fn settle_trades(&mut self, item_registry: &ItemRegistry, logger: &mut dyn Logger) {
+        let mut bank = std::mem::take(&mut self.bank);
+        let now = self.clock.now();
+        for terminal_vessel in &self.vessels {
+            if !terminal_vessel.powered() {
+                continue;
+            }
+            let mut terminals = terminal_vessel.modules_with_cap(ModuleCapability::TradingTerminal);
+            terminals.retain(|module| module.trading_console().is_some());
+            if terminals.is_empty() {
+                continue;
+            }
+            let terminal_owner = terminal_vessel.owner();
+            let mut settle = |customer: &Vessel| {
+                let mut modules = customer.modules_with_cap(ModuleCapability::ItemStorage);
+                let mut storages: Vec<&mut ItemStorage> = modules
+                    .iter_mut()
+                    .flat_map(|module| module.storages_mut().iter_mut())
+                    .collect();
+                let owner = customer.owner();
+                for terminal in &mut terminals {
+                    let console = terminal.trading_console_mut().unwrap();
+                    // settlement only pays to the till, so the till is taken out of the bank to be
+                    // borrowed alongside the customer wallet and its balance is paid back after
+                    let mut till = if owner == terminal_owner {
+                        Wallet::default()
+                    } else {
+                        std::mem::take(bank.wallet_mut(terminal_owner))
+                    };
+                    let settlements = console.settle(
+                        customer.id(),
+                        &mut storages,
+                        bank.wallet_mut(owner),
+                        &mut till,
+                        item_registry,
+                    );
+                    bank.wallet_mut(terminal_owner)
+                        .deposit(till.balance())
+                        .unwrap();
+                    for settlement in settlements {
+                        let (message, from, to, price) = match settlement {
+                            Settlement::Bought { items, price, .. } => (
+                                format!("Bought {} for {}", Self::items_to_string(&items), price),
+                                Some(owner),
+                                Some(terminal_owner),
+                                price,
+                            ),
+                            Settlement::Sold { items, price, .. } => (
+                                format!("Sold {} for {}", Self::items_to_string(&items), price),
+                                None,
+                                Some(owner),
+                                price,
+                            ),
+                            Settlement::Delivered {
+                                contract_id,
+                                items,
+                                reward,
+                                collateral,
+                                ..
+                            } => (
+                                format!(
+                                    "Delivered {} for {} under contract {}",
+                                    Self::items_to_string(&items),
+                                    reward,
+                                    contract_id
+                                ),
+                                None,
+                                Some(owner),
+                                reward.saturating_add(collateral),
+                            ),
+                        };
+                        bank.record(
+                            now,
+                            from,
+                            to,
+                            price,
+                            format!("{} at {}", message, terminal_vessel.id()),
+                        );
+                        logger.log(&owner, Severity::Info, message);
+                    }
+                }
+            };
+            for customer in self.vessels_in_radius(terminal_vessel.pos(), DOCKING_RANGE) {
+                if customer.id() != terminal_vessel.id() {
+                    settle(customer);
+                }
+            }
+            for module in terminal_vessel.modules_with_cap(ModuleCapability::DockingClamp) {
+                for clamp in module.docking_clamps() {
+                    if let Some(customer) = clamp.vessel_docked() {
+                        settle(customer);
+                    }
+                }
+            }
+        }
+        self.bank = bank;
+    }
+
+    /// removes orders and contracts past their deadline from trading terminals and notifies owners of customers
+    /// and contractors. Refunds which do not fit into the till of the terminal owner are reported to the owner
+    fn expire_trades(&mut self, logger: &mut dyn Logger) {
+        let now = self.clock.now();
+        let mut bank = std::mem::take(&mut self.bank);
+        let mut expired_orders = Vec::new();
+        let mut expired = Vec::new();
+        for vessel in &self.vessels {
+            let owner = vessel.owner();
+            for mut module in vessel.modules_with_cap(ModuleCapability::TradingTerminal) {
+                let Some(console) = module.trading_console_mut() else {
+                    continue;
+                };
+                let balance = bank.balance(owner);
+                let mut orders = Vec::new();
+                for order in console.expire_orders(now, bank.wallet_mut(owner)) {
+                    match order {
+                        Ok(order) => orders.push(order),
+                        Err(err) => logger.log(&owner, Severity::Error, err.to_string()),
+                    }
+                }
+                let refund = bank.balance(owner) - balance;
+                if refund > 0 {
+                    let ids: Vec<String> = orders.iter().map(|o| o.id().to_string()).collect();
+                    bank.record(
+                        now,
+                        None,
+                        Some(owner),
+                        refund,
+                        format!("Orders {} expired at {}", ids.join(", "), vessel.id()),
+                    );
+                }
+                expired_orders.extend(orders);
+            }
+            for mut module in vessel.modules_with_cap(ModuleCapability::ContractBoard) {
+                let Some(board) = module.contract_board_console_mut() else {
+                    continue;
+                };
+                let balance = bank.balance(owner);
+                let mut contracts = Vec::new();
+                for contract in board.expire_contracts(now, bank.wallet_mut(owner)) {
+                    match contract {
+                        Ok(contract) => contracts.push(contract),
+                        Err(err) => logger.log(&owner, Severity::Error, err.to_string()),
+                    }
+                }
+                let refund = bank.balance(owner) - balance;
+                if refund > 0 {
+                    let ids: Vec<String> = contracts.iter().map(|c| c.id().to_string()).collect();
+                    bank.record(
+                        now,
+                        None,
+                        Some(owner),
+                        refund,
+                        format!("Contracts {} expired at {}", ids.join(", "), vessel.id()),
+                    );
+                }
+                expired.extend(contracts);
+            }
+        }
+        self.bank = bank;
+        for order in expired_orders {
+            let Some(customer) = self.vessel_by_id(order.customer()) else {
+                continue;
+            };
+            logger.log(
+                &customer.owner(),
+                Severity::Warning,
+                format!("Order {} expired", order.id()),
+            );
+        }
+        for contract in expired {
+            let Some(contractor) = contract
+                .contractor()
+                .and_then(|contractor| self.vessel_by_id(contractor))
+            else {
+                continue;
+            };
+            logger.log(
+                &contractor.owner(),
+                Severity::Warning,
+                format!(
+                    "Contract {} expired, collateral of {} forfeited",
+                    contract.id(),
+                    contract.penalty()
+                ),
+            );
+        }
+    }
+
+    /// runs `f` with the contract board of the terminal if the terminal vessel is in the environment
+    fn with_contract_board<T>(
+        &self,
+        terminal: TerminalRef,
+        f: impl FnOnce(&mut dyn ContractBoardConsole) -> Option<T>,
+    ) -> Option<T> {
+        let vessel = self.vessel_by_id(terminal.vessel_id)?;
+        let mut module = vessel
+            .modules_mut()
+            .find(|module| module.id() == terminal.module_id)?;
+        f(module.contract_board_console_mut()?)
+    }
+
+    /// owner of the terminal vessel whose bank wallet is the till of the terminal
+    fn terminal_owner(&self, terminal: TerminalRef) -> Option<PersonId> {
+        self.vessel_by_id(terminal.vessel_id)
+            .map(|vessel| vessel.owner())
+    }
+
+    /// runs `f` with the trading console of the terminal if the terminal vessel is in the environment
+    fn with_terminal<T>(
+        &self,
+        terminal: TerminalRef,
+        f: impl FnOnce(&mut dyn TradingConsole) -> Option<T>,
+    ) -> Option<T> {
+        let vessel = self.vessel_by_id(terminal.vessel_id)?;
+        let mut module = vessel
+            .modules_mut()
+            .find(|module| module.id() == terminal.module_id)?;
+        f(module.trading_console_mut()?)
+    }
+
+    fn items_to_string(items: &[Item]) -> String {
+        items
+            .iter()
+            .map(|item| item.to_string())
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// how many units of the item fit into item storages of the vessel
+    fn cargo_space(vessel: &Vessel, item: &ItemId, item_registry: &ItemRegistry) -> ItemCount {
+        vessel
+            .modules()
+            .filter(|module| module.capabilities().contains(&ModuleCapability::ItemStorage))
+            .flat_map(|module| {
+                module
+                    .storages()
+                    .iter()
+                    .map(|storage| storage.free_space_for(item, item_registry))
+                    .collect::<Vec<_>>()
+            })
+            .fold(0, ItemCount::saturating_add)
+    }
+
+    /// how many units of research data fit into storages of research labs of the vessel
+    fn research_lab_space(vessel: &Vessel, item_registry: &ItemRegistry) -> ItemCount {
+        let research_data = RESEARCH_DATA_ITEM_ID.to_string();
+        vessel
+            .modules()
+            .filter(|module| module.capabilities().contains(&ModuleCapability::ResearchLab))
+            .map(|module| {
+                module
+                    .storages()
+                    .iter()
+                    .map(|storage| storage.free_space_for(&research_data, item_registry))
+                    .fold(0, ItemCount::saturating_add)
+            })
+            .fold(0, ItemCount::saturating_add)
+    }
+
+    /// the count must fit into research labs of the vessel
+    fn store_research_data(vessel: &Vessel, count: ItemCount, item_registry: &ItemRegistry) {
+        let mut data = Item::new(RESEARCH_DATA_ITEM_ID.to_string(), count);
+        for mut module in vessel.modules_mut() {
+            if !module.capabilities().contains(&ModuleCapability::ResearchLab) {
+                continue;
+            }
+            for storage in module.storages_mut() {
+                data = storage.add_item(data, item_registry);
+            }
+        }
+        assert_eq!(data.count(), 0);
+    }
+
+    /// powered research labs inside nebulae collect research data from them
+    fn collect_nebula_research_data(&mut self, item_registry: &ItemRegistry) {
+        let dt = self.clock.tick_duration().as_secs_f64();
+        for vessel in &self.vessels {
+            if !vessel.powered() {
+                continue;
+            }
+            let labs = vessel
+                .modules()
+                .filter(|module| module.capabilities().contains(&ModuleCapability::ResearchLab))
+                .count();
+            if labs == 0 {
+                continue;
+            }
+            let Some(nebula) = self
+                .nebulae
+                .iter_mut()
+                .find(|nebula| nebula.research_data() > 0 && nebula.contains(vessel.pos()))
+            else {
+                continue;
+            };
+            let count = ((NEBULA_RESEARCH_DATA_RATE * labs as Float * dt) as ItemCount)
+                .min(Self::research_lab_space(vessel, item_registry));
+            let count = nebula.take_research_data(count);
+            Self::store_research_data(vessel, count, item_registry);
+        }
+    }
+
+    /// moves research data of the wreck to research labs of the vessel as long as there is space.
+    /// Wrecks with nothing left are removed
+    fn gather_research_data(
+        &mut self,
+        vessel_id: VesselId,
+        wreck_id: WreckId,
+        item_registry: &ItemRegistry,
+    ) {
+        let Some(vessel_index) = self.index().index_of(vessel_id) else {
+            return;
+        };
+        let vessel = &self.vessels[vessel_index];
+        let Some(wreck_index) = self.wrecks.iter().position(|w| w.id() == wreck_id) else {
+            return;
+        };
+        let wreck = &mut self.wrecks[wreck_index];
+        if (wreck.pos() - vessel.pos()).len() > SALVAGE_RANGE {
+            return;
+        }
+        let count = wreck.take_research_data(Self::research_lab_space(vessel, item_registry));
+        Self::store_research_data(vessel, count, item_registry);
+        if wreck.is_empty() {
+            self.wrecks.remove(wreck_index);
+        }
+    }
+
+    /// moves modules of the wreck to module storages of the vessel and its items to item storages
+    /// as long as there is space. Wrecks with nothing left are removed
+    fn salvage(&mut self, vessel_id: VesselId, wreck_id: WreckId, item_registry: &ItemRegistry) {
+        let Some(vessel_index) = self.index().index_of(vessel_id) else {
+            return;
+        };
+        let vessel = &self.vessels[vessel_index];
+        let Some(wreck_index) = self.wrecks.iter().position(|w| w.id() == wreck_id) else {
+            return;
+        };
+        let wreck = &mut self.wrecks[wreck_index];
+        if (wreck.pos() - vessel.pos()).len() > SALVAGE_RANGE {
+            return;
+        }
+        for mut module in vessel.modules_mut() {
+            if module.capabilities().contains(&ModuleCapability::ModuleStorage) {
+                for storage in module.module_storages_mut() {
+                    while storage.has_space() {
+                        let Some(salvaged) = wreck.take_module() else {
+                            break;
+                        };
+                        assert!(storage.add(salvaged));
+                    }
+                }
+            }
+            if !module.capabilities().contains(&ModuleCapability::ItemStorage) {
+                continue;
+            }
+            for storage in module.storages_mut() {
+                for item in wreck.items().iter().collect::<Vec<_>>() {
+                    let rest = storage.add_item(item.clone(), item_registry);
+                    wreck
+                        .items_mut()
+                        .remove_item(item.id().clone(), item.count() - rest.count());
+                }
+            }
+        }
+        if wreck.is_empty() {
+            self.wrecks.remove(wreck_index);
+        }
+    }
+
+    /// mining lasers of the vessel which reach the asteroid mine it for one tick.
+    /// Ore is put to item storages of the vessel as long as there is space
+    fn mine(&mut self, vessel_id: VesselId, asteroid_id: AsteroidId, item_registry: &ItemRegistry) {
+        let dt = self.clock.tick_duration().as_secs_f64();
+        let Some(vessel_index) = self.index().index_of(vessel_id) else {
+            return;
+        };
+        let vessel = &self.vessels[vessel_index];
+        let Some(asteroid) = self.asteroids.iter_mut().find(|a| a.id() == asteroid_id) else {
+            return;
+        };
+        if !vessel.powered() {
+            return;
+        }
+        let distance = (asteroid.pos() - vessel.pos()).len();
+        let rate: Float = vessel
+            .modules()
+            .filter_map(|module| module.mining_laser())
+            .filter(|laser| laser.range >= distance)
+            .map(|laser| laser.rate)
+            .sum();
+        let free_space = vessel
+            .modules()
+            .filter(|module| module.capabilities().contains(&ModuleCapability::ItemStorage))
+            .map(|module| {
+                module
+                    .storages()
+                    .iter()
+                    .map(|storage| storage.free_space_for(asteroid.ore(), item_registry))
+                    .fold(0, ItemCount::saturating_add)
+            })
+            .fold(0, ItemCount::saturating_add);
+        let mut ore = asteroid.mine(((rate * dt) as ItemCount).min(free_space));
+        for mut module in vessel.modules_mut() {
+            if !module.capabilities().contains(&ModuleCapability::ItemStorage) {
+                continue;
+            }
+            for storage in module.storages_mut() {
+                ore = storage.add_item(ore, item_registry);
+            }
+        }
+        assert_eq!(ore.count(), 0);
+    }
+
+    /// moves gas from the nebula the vessel is in to storages of the module as long as there is space
+    fn harvest_gas(
+        &mut self,
+        vessel_id: VesselId,
+        module_id: ModuleId,
+        count: ItemCount,
+        item_registry: &ItemRegistry,
+    ) {
+        let Some(vessel_index) = self.index().index_of(vessel_id) else {
+            return;
+        };
+        let vessel = &self.vessels[vessel_index];
+        let Some(nebula) = self
+            .nebulae
+            .iter_mut()
+            .find(|nebula| nebula.gas().is_some() && nebula.contains(vessel.pos()))
+        else {
+            return;
+        };
+        let Some(mut module) = vessel.modules_mut().find(|module| module.id() == module_id) else {
+            return;
+        };
+        let gas_id = nebula.gas().unwrap().id().clone();
+        let free_space: ItemCount = module
+            .storages()
+            .iter()
+            .map(|storage| storage.free_space_for(&gas_id, item_registry))
+            .fold(0, ItemCount::saturating_add);
+        let Some(mut gas) = nebula.harvest(count.min(free_space)) else {
+            return;
+        };
+        for storage in module.storages_mut() {
+            gas = storage.add_item(gas, item_registry);
+        }
+        assert_eq!(gas.count(), 0);
+    }
+
+    fn transfer_person(
+        &mut self,
+        person: Person,
+        vessel_id: VesselId,
+        module_id: ModuleId,
+        destination: PersonDestination,
+        logger: &mut dyn Logger,
+    ) {
+        let person_id = person.id();
+        let mut person = Some(person);
+        let mut found = false;
+        for vessel in &self.vessels {
+            found |= vessel.transfer_person(vessel_id, destination, &mut person);
+        }
+        if person.is_none() {
+            return;
+        }
+        let error = if found {
+            VesselRequestError::DestinationFull { destination }
+        } else {
+            VesselRequestError::DestinationNotFound { destination }
+        };
+        logger.log(&person_id, Severity::Warning, error.to_string());
+        for vessel in &self.vessels {
+            vessel.return_person(vessel_id, module_id, &mut person, &error);
+        }
+        if let Some(person) = person {
+            logger.log(
+                &person_id,
+                Severity::Error,
+                format!("No free seat near module {}, waiting for one", module_id),
+            );
+            self.stranded.push(StrandedPerson {
+                person,
+                vessel_id,
+                module_id,
+                error,
+            });
+        }
+    }
+
+    /// puts stranded persons back on board if a seat became free
+    fn board_stranded(&mut self, logger: &mut dyn Logger) {
+        for stranded in std::mem::take(&mut self.stranded) {
+            let person_id = stranded.person.id();
+            let mut person = Some(stranded.person);
+            for vessel in &self.vessels {
+                vessel.return_person(
+                    stranded.vessel_id,
+                    stranded.module_id,
+                    &mut person,
+                    &stranded.error,
+                );
+            }
+            match person {
+                Some(person) => self.stranded.push(StrandedPerson { person, ..stranded }),
+                None => logger.log(&person_id, Severity::Info, "Found a free seat".to_string()),
+            }
+        }
+    }
+
+    fn dock_to(&mut self, vessel_id: VesselId, dst_vessel_id: VesselId) {
+        let Some(dst_module_id) = self.vessel_by_id(dst_vessel_id).and_then(|dst| {
+            dst.modules()
+                .find(|module| {
+                    module
+                        .docking_clamps()
+                        .iter()
+                        .any(|clamp| !clamp.is_docked())
+                })
+                .map(|module| module.id())
+        }) else {
+            return;
+        };
+        self.dock(vessel_id, dst_vessel_id, dst_module_id)
+    }
+
+    fn dock(&mut self, vessel_id: VesselId, dst_vessel_id: VesselId, dst_module_id: ModuleId) {
+        if vessel_id == dst_vessel_id {
+            return;
+        }
+        let (Some(vessel), Some(dst)) = (
+            self.vessel_by_id(vessel_id),
+            self.vessel_by_id(dst_vessel_id),
+        ) else {
+            return;
+        };
+        if (vessel.pos() - dst.pos()).len() > DOCKING_RANGE {
+            return;
+        }
+
+        let index = self.index().index_of(vessel_id).unwrap();
+        let vessel = self.vessels.remove(index);
+        self.invalidate_index();
+        if let Err(vessel) = self
+            .vessel_by_id(dst_vessel_id)
+            .unwrap()
+            .dock(dst_module_id, vessel)
+        {
+            self.vessels.insert(index, vessel);
+            self.invalidate_index();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::EnvironmentSeed;
+    use dyn_serde::DynDeserializeSeedVault;
+    use serde::de::DeserializeSeed;
+
+    #[test]
+    fn loads_saves_without_later_fields() {
+        let module_vault = DynDeserializeSeedVault::new();
+        let objective_vault = DynDeserializeSeedVault::new();
+        let json = r#"{"vessels":[],"nebulae":[{"bound":[]}]}"#;
+        let environment = EnvironmentSeed::new(&module_vault, &objective_vault)
+            .deserialize(&mut serde_json::Deserializer::from_str(json))
+            .unwrap();
+        assert!(environment.asteroids().is_empty());
+        assert!(environment.wrecks().is_empty());
+        assert_eq!(environment.rng.seed(), 0);
+        assert_eq!(environment.nebulae[0].radar_factor(), 1.);
     }
 }
@@ -1,5 +1,17 @@
 mod environment;
 pub use environment::*;
 
+mod asteroid;
+pub use asteroid::*;
+
+mod wreck;
+pub use wreck::*;
+
 mod nebula;
 pub use nebula::*;
+
+mod spatial_index;
+pub(crate) use spatial_index::*;
+
+mod clock;
+pub use clock::*;
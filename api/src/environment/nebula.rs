@@ -1,10 +1,133 @@
+use crate::item::{Item, ItemCount};
 use crate::utils::math::Point;
 use crate::utils::utils::Float;
 use serde::{Deserialize, Serialize};
 
+/// cloud of gas and dust which slows vessels down and blinds their radars
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Nebula {
     bound: Vec<Point<Float>>,
+    /// radar range of vessels inside and of radars looking at vessels inside is multiplied by this factor
+    #[serde(default = "no_effect")]
+    radar_factor: Float,
+    /// part of speed a vessel inside keeps after one second. Engine acceleration is multiplied by it too
+    #[serde(default = "no_effect")]
+    speed_factor: Float,
+    /// gas left to harvest. `None` if there is nothing to harvest
+    #[serde(default)]
+    gas: Option<Item>,
+    /// units of research data powered research labs inside can still collect
+    #[serde(default)]
+    research_data: ItemCount,
 }
 
-impl Nebula {}
+/// factor of nebulae from saves which predate it
+fn no_effect() -> Float {
+    1.
+}
+
+impl Nebula {
+    pub fn new(bound: Vec<Point<Float>>, radar_factor: Float, speed_factor: Float) -> Self {
+        Self {
+            bound,
+            radar_factor,
+            speed_factor,
+            gas: None,
+            research_data: 0,
+        }
+    }
+
+    pub fn with_gas(mut self, gas: Item) -> Self {
+        self.gas = Some(gas);
+        self
+    }
+
+    pub fn with_research_data(mut self, research_data: ItemCount) -> Self {
+        self.research_data = research_data;
+        self
+    }
+
+    pub fn bound(&self) -> &[Point<Float>] {
+        &self.bound
+    }
+
+    pub fn radar_factor(&self) -> Float {
+        self.radar_factor
+    }
+
+    pub fn speed_factor(&self) -> Float {
+        self.speed_factor
+    }
+
+    pub fn gas(&self) -> Option<&Item> {
+        self.gas.as_ref()
+    }
+
+    pub fn research_data(&self) -> ItemCount {
+        self.research_data
+    }
+
+    /// even-odd rule. Points exactly on the bound can be counted either way
+    pub fn contains(&self, point: Point<Float>) -> bool {
+        let (x, y) = (*point.x(), *point.y());
+        let mut inside = false;
+        for (i, a) in self.bound.iter().enumerate() {
+            let b = &self.bound[(i + self.bound.len() - 1) % self.bound.len()];
+            let (ax, ay, bx, by) = (*a.x(), *a.y(), *b.x(), *b.y());
+            if (ay > y) != (by > y) && x < ax + (y - ay) * (bx - ax) / (by - ay) {
+                inside = !inside;
+            }
+        }
+        inside
+    }
+
+    /// takes up to `count` units of gas
+    pub(crate) fn harvest(&mut self, count: ItemCount) -> Option<Item> {
+        let gas = self.gas.as_mut()?;
+        let count = count.min(gas.count);
+        gas.count -= count;
+        Some(Item::new(gas.id.clone(), count))
+    }
+
+    /// takes up to `count` units of research data
+    pub(crate) fn take_research_data(&mut self, count: ItemCount) -> ItemCount {
+        let count = count.min(self.research_data);
+        self.research_data -= count;
+        count
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Nebula;
+    use crate::item::Item;
+
+    #[test]
+    fn contains() {
+        let nebula = Nebula::new(
+            vec![
+                (0., 0.).into(),
+                (10., 0.).into(),
+                (10., 10.).into(),
+                (5., 2.).into(),
+                (0., 10.).into(),
+            ],
+            1.,
+            1.,
+        );
+        assert!(nebula.contains((1., 1.).into()));
+        assert!(nebula.contains((9., 8.).into()));
+        assert!(!nebula.contains((5., 8.).into()));
+        assert!(!nebula.contains((-1., 1.).into()));
+        assert!(!nebula.contains((11., 5.).into()));
+    }
+
+    #[test]
+    fn harvest() {
+        let mut nebula = Nebula::new(vec![], 1., 1.).with_gas(Item::new("gas".to_string(), 5));
+        assert_eq!(nebula.harvest(3), Some(Item::new("gas".to_string(), 3)));
+        assert_eq!(nebula.harvest(3), Some(Item::new("gas".to_string(), 2)));
+        assert_eq!(nebula.gas().unwrap().count(), 0);
+        assert!(Nebula::new(vec![], 1., 1.).harvest(1).is_none());
+    }
+}
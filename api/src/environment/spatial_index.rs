@@ -0,0 +1,114 @@
+use crate::utils::math::{Point, Rect};
+use crate::utils::utils::Float;
+use crate::vessel::{Vessel, VesselId};
+use std::collections::BTreeMap;
+
+/// side of one grid cell in meters
+static CELL_SIZE: Float = 1000.;
+
+type Cell = (i64, i64);
+
+fn cell_of(point: Point<Float>) -> Cell {
+    (
+        (point.x() / CELL_SIZE).floor() as i64,
+        (point.y() / CELL_SIZE).floor() as i64,
+    )
+}
+
+/// uniform grid over vessel positions. Stores indices into the vessel list it was built from
+#[derive(Debug, Default)]
+pub(crate) struct SpatialIndex {
+    cells: BTreeMap<Cell, Vec<usize>>,
+    ids: BTreeMap<VesselId, usize>,
+}
+
+impl SpatialIndex {
+    pub(crate) fn new(vessels: &[Vessel]) -> Self {
+        let mut result = Self::default();
+        for (i, vessel) in vessels.iter().enumerate() {
+            result
+                .cells
+                .entry(cell_of(vessel.pos()))
+                .or_default()
+                .push(i);
+            result.ids.insert(vessel.id(), i);
+        }
+        result
+    }
+
+    pub(crate) fn index_of(&self, id: VesselId) -> Option<usize> {
+        self.ids.get(&id).cloned()
+    }
+
+    /// indices of all vessels which can be inside the rect. Some of them can be outside
+    pub(crate) fn candidates(&self, rect: Rect<Float>) -> Vec<usize> {
+        let (left, top) = cell_of(rect.left_top());
+        let (right, bottom) = cell_of(rect.right_bottom());
+        let width = right.saturating_sub(left).saturating_add(1);
+        let height = bottom.saturating_sub(top).saturating_add(1);
+        let cell_count = width.saturating_mul(height);
+        if cell_count as usize > self.cells.len() {
+            // query covers more cells than there are occupied ones
+            return self
+                .cells
+                .iter()
+                .filter(|((x, y), _)| (left..=right).contains(x) && (top..=bottom).contains(y))
+                .flat_map(|(_, indices)| indices.iter().cloned())
+                .collect();
+        }
+
+        let mut result = Vec::new();
+        for x in left..=right {
+            for y in top..=bottom {
+                if let Some(indices) = self.cells.get(&(x, y)) {
+                    result.extend(indices.iter().cloned());
+                }
+            }
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SpatialIndex;
+    use crate::person::PersonId;
+    use crate::utils::math::Rect;
+    use crate::utils::simulation_rng::SimulationRng;
+    use crate::utils::utils::Float;
+    use crate::vessel::Vessel;
+
+    #[test]
+    fn candidates() {
+        let mut rng = SimulationRng::new(0);
+        let vessels = vec![
+            Vessel::new(PersonId::nil(), (10., 10.).into(), vec![], &mut rng),
+            Vessel::new(PersonId::nil(), (-10., 10.).into(), vec![], &mut rng),
+            Vessel::new(PersonId::nil(), (5000., 5000.).into(), vec![], &mut rng),
+        ];
+        let index = SpatialIndex::new(&vessels);
+        assert_eq!(index.index_of(vessels[2].id()), Some(2));
+
+        let mut candidates = index.candidates(Rect::from_lrtb_unchecked(0., 100., 0., 100.));
+        candidates.sort();
+        assert_eq!(candidates, vec![0]);
+
+        let mut candidates = index.candidates(Rect::from_lrtb_unchecked(-100., 100., 0., 100.));
+        candidates.sort();
+        assert_eq!(candidates, vec![0, 1]);
+
+        let mut candidates = index.candidates(Rect::from_lrtb_unchecked(-1e9, 1e9, -1e9, 1e9));
+        candidates.sort();
+        assert_eq!(candidates, vec![0, 1, 2]);
+
+        // cells of the rect exceed the i64 range
+        let mut candidates = index.candidates(Rect::from_lrtb_unchecked(
+            Float::MIN,
+            Float::MAX,
+            Float::MIN,
+            Float::MAX,
+        ));
+        candidates.sort();
+        assert_eq!(candidates, vec![0, 1, 2]);
+    }
+}
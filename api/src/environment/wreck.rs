@@ -0,0 +1,405 @@
+use crate::item::{Item, ItemCount, ItemRegistry, ItemStorage};
+use crate::module::{Module, ModuleSeed};
+use crate::utils::math::Point;
+use crate::utils::utils::Float;
+use crate::vessel::Vessel;
+use dyn_serde::{DynDeserializeSeedVault, VecSeed};
+use dyn_serde_macro::DeserializeSeedXXX;
+use rand::{Rng, RngCore};
+use serde::Serialize;
+use std::ops::Deref;
+use uuid::Uuid;
+
+pub type WreckId = Uuid;
+
+/// max distance from a vessel to a wreck it can salvage
+pub static SALVAGE_RANGE: Float = 10.;
+/// chance of a module to stay intact when a vessel is wrecked
+static MODULE_SURVIVAL_CHANCE: f64 = 0.5;
+/// units of research data which can be collected from a wreck per each module it has left
+static RESEARCH_DATA_PER_MODULE: ItemCount = 10;
+
+/// what is left of a destroyed or abandoned vessel.
+/// Surviving modules are empty, items which survived are kept in the wreck itself, one stack per item
+#[derive(Debug, Serialize, DeserializeSeedXXX)]
+#[deserialize_seed_xxx(seed = crate::environment::WreckSeed::<'v>)]
+pub struct Wreck {
+    id: WreckId,
+    pos: Point<Float>,
+    #[deserialize_seed_xxx(seed = self.seed.modules_seed)]
+    modules: Vec<Box<dyn Module>>,
+    items: ItemStorage,
+    /// units of research data left to collect
+    research_data: ItemCount,
+}
+
+#[derive(Clone)]
+pub struct WreckSeed<'v> {
+    modules_seed: VecSeed<ModuleSeed<'v>>,
+}
+
+impl<'v> WreckSeed<'v> {
+    pub fn new(vault: &'v DynDeserializeSeedVault<dyn Module>) -> Self {
+        Self {
+            modules_seed: VecSeed::new(ModuleSeed::new(vault)),
+        }
+    }
+}
+
+impl Wreck {
+    /// keeps a random subset of vessel modules and a random part of each item stack.
+    /// Returns the wreck and vessels which were docked to the wrecked one
+    pub(crate) fn from_vessel(
+        vessel: Vessel,
+        rng: &mut dyn RngCore,
+        item_registry: &ItemRegistry,
+    ) -> (Self, Vec<Vessel>) {
+        let mut result = Self {
+            id: uuid::Builder::from_random_bytes(rng.random()).into_uuid(),
+            pos: vessel.pos(),
+            modules: Vec::new(),
+            items: ItemStorage::new(),
+            research_data: 0,
+        };
+        let (modules, released) = vessel.into_modules();
+        for mut module in modules {
+            for storage in module.storages_mut() {
+                for item in storage.iter().collect::<Vec<_>>() {
+                    let item = storage.remove_item(item.id().clone(), item.count());
+                    let survived = rng.random_range(0..=item.count());
+                    // what does not fit into one stack is lost with the rest
+                    result
+                        .items
+                        .add_item(Item::new(item.id().clone(), survived), item_registry);
+                }
+            }
+            if rng.random_bool(MODULE_SURVIVAL_CHANCE) {
+                result.modules.push(module);
+            }
+        }
+        result.research_data = RESEARCH_DATA_PER_MODULE * result.modules.len() as ItemCount;
+        (result, released)
+    }
+
+    pub fn id(&self) -> WreckId {
+        self.id
+    }
+
+    pub fn pos(&self) -> Point<Float> {
+        self.pos
+    }
+
+    pub fn modules(&self) -> impl Iterator<Item = &dyn Module> {
+        self.modules.iter().map(|module| module.deref())
+    }
+
+    pub fn items(&self) -> &ItemStorage {
+        &self.items
+    }
+
+    pub fn research_data(&self) -> ItemCount {
+        self.research_data
+    }
+
+    /// true if there is nothing left to salvage or study
+    pub fn is_empty(&self) -> bool {
+        self.modules.is_empty() && self.items.is_empty() && self.research_data == 0
+    }
+
+    pub(crate) fn take_module(&mut self) -> Option<Box<dyn Module>> {
+        self.modules.pop()
+    }
+
+    pub(crate) fn items_mut(&mut self) -> &mut ItemStorage {
+        &mut self.items
+    }
+
+    /// takes up to `count` units of research data
+    pub(crate) fn take_research_data(&mut self, count: ItemCount) -> ItemCount {
+        let count = count.min(self.research_data);
+        self.research_data -= count;
+        count
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{RESEARCH_DATA_PER_MODULE, Wreck};
+    use crate::environment::{Environment, MiningLaserSpec, SimulationClock};
+    use crate::item::{Item, ItemCount, ItemRegistry, ItemStorage};
+    use crate::module::{
+        ContractBoardConsole, Module, ModuleCapability, ModuleId, ModuleStorage, PackageId,
+        ProcessTokenContext, TradingConsole,
+    };
+    use crate::person::{Logger, ObjectiveDeciderVault, Person, PersonId};
+    use crate::recipe::{AssemblyRecipe, Recipe};
+    use crate::utils::simulation_rng::SimulationRng;
+    use crate::utils::utils::Float;
+    use crate::vessel::{
+        DockedVesselPolicy, DockingClamp, RadarSpec, Vessel, VesselModuleInterface,
+    };
+    use dyn_serde::{DynSerialize, TypeId};
+    use rand::RngCore;
+    use serde::Serialize;
+    use serde_intermediate::{Intermediate, to_intermediate};
+    use std::error::Error;
+
+    /// cargo hold with seats and a docking clamp
+    #[derive(Debug, Serialize)]
+    struct Hold {
+        id: ModuleId,
+        cargo: ItemStorage,
+        crew: Vec<Person>,
+        seats: usize,
+        #[serde(skip)]
+        clamps: Vec<DockingClamp>,
+    }
+
+    impl Hold {
+        fn new(ore: ItemCount, seats: usize) -> Box<Self> {
+            let mut cargo = ItemStorage::new();
+            assert!(cargo.try_add_item(Item::new("ore".to_string(), ore), &ItemRegistry::new()));
+            Box::new(Self {
+                id: ModuleId::new_v4(),
+                cargo,
+                crew: Vec::new(),
+                seats,
+                clamps: vec![DockingClamp::new(DockedVesselPolicy::Proceed)],
+            })
+        }
+    }
+
+    impl DynSerialize for Hold {
+        fn type_id(&self) -> TypeId {
+            "Hold".to_string()
+        }
+
+        fn serialize(&self) -> Result<Intermediate, Box<dyn Error>> {
+            to_intermediate(self).map_err(|e| e.into())
+        }
+    }
+
+    impl Module for Hold {
+        fn id(&self) -> ModuleId {
+            self.id
+        }
+
+        fn package_id(&self) -> PackageId {
+            "test".to_string()
+        }
+
+        fn capabilities(&self) -> &[ModuleCapability] {
+            &[]
+        }
+
+        fn primary_capabilities(&self) -> &[ModuleCapability] {
+            &[]
+        }
+
+        fn proceed(
+            &mut self,
+            _this_vessel: &dyn VesselModuleInterface,
+            _rng: &mut dyn RngCore,
+            _clock: &SimulationClock,
+            _item_registry: &ItemRegistry,
+            _process_token_context: &ProcessTokenContext,
+            _decider_vault: &ObjectiveDeciderVault,
+            _logger: &mut dyn Logger,
+        ) {
+        }
+
+        fn thrust(&self) -> Float {
+            0.
+        }
+
+        fn engine_energy_per_meter(&self) -> Float {
+            0.
+        }
+
+        fn power_draw(&self) -> Float {
+            0.
+        }
+
+        fn power_output(&self) -> Float {
+            0.
+        }
+
+        fn radar(&self) -> Option<RadarSpec> {
+            None
+        }
+
+        fn mining_laser(&self) -> Option<MiningLaserSpec> {
+            None
+        }
+
+        fn recipes(&self) -> &[Recipe] {
+            &[]
+        }
+
+        fn assembly_recipes(&self) -> &[AssemblyRecipe] {
+            &[]
+        }
+
+        fn extract_person(&mut self, id: PersonId) -> Option<Person> {
+            let index = self.crew.iter().position(|person| person.id() == id)?;
+            Some(self.crew.remove(index))
+        }
+
+        fn insert_person(&mut self, person: Person) -> Result<(), Person> {
+            if !self.can_insert_person() {
+                return Err(person);
+            }
+            self.crew.push(person);
+            Ok(())
+        }
+
+        fn can_insert_person(&self) -> bool {
+            self.crew.len() < self.seats
+        }
+
+        fn contains_person(&self, id: PersonId) -> bool {
+            self.crew.iter().any(|person| person.id() == id)
+        }
+
+        fn person_ids(&self) -> Vec<PersonId> {
+            self.crew.iter().map(|person| person.id()).collect()
+        }
+
+        fn storages(&self) -> &[ItemStorage] {
+            std::slice::from_ref(&self.cargo)
+        }
+
+        fn storages_mut(&mut self) -> &mut [ItemStorage] {
+            std::slice::from_mut(&mut self.cargo)
+        }
+
+        fn module_storages(&self) -> &[ModuleStorage] {
+            &[]
+        }
+
+        fn module_storages_mut(&mut self) -> &mut [ModuleStorage] {
+            &mut []
+        }
+
+        fn docking_clamps(&self) -> &[DockingClamp] {
+            &self.clamps
+        }
+
+        fn docking_clamps_mut(&mut self) -> &mut [DockingClamp] {
+            &mut self.clamps
+        }
+
+        fn trading_console(&self) -> Option<&dyn TradingConsole> {
+            None
+        }
+
+        fn trading_console_mut(&mut self) -> Option<&mut dyn TradingConsole> {
+            None
+        }
+
+        fn contract_board_console(&self) -> Option<&dyn ContractBoardConsole> {
+            None
+        }
+
+        fn contract_board_console_mut(&mut self) -> Option<&mut dyn ContractBoardConsole> {
+            None
+        }
+    }
+
+    /// vessel with one crew member in its only hold and a docked boat with `boat_seats` seats
+    fn crewed_vessel_with_boat(boat_seats: usize) -> (Vessel, PersonId) {
+        let mut rng = SimulationRng::new(0);
+        let person = Person::random(&mut rng);
+        let person_id = person.id();
+        let mut hold = Hold::new(0, 1);
+        hold.insert_person(person).unwrap();
+        let boat = Vessel::new(
+            PersonId::nil(),
+            (0., 0.).into(),
+            vec![Hold::new(0, boat_seats)],
+            &mut rng,
+        );
+        assert!(hold.docking_clamps_mut()[0].dock(boat));
+        (
+            Vessel::new(PersonId::nil(), (0., 0.).into(), vec![hold], &mut rng),
+            person_id,
+        )
+    }
+
+    #[test]
+    fn id_is_drawn_from_rng() {
+        let wreck_id = || {
+            let vessel = Vessel::new(
+                PersonId::nil(),
+                (0., 0.).into(),
+                vec![],
+                &mut SimulationRng::new(0),
+            );
+            Wreck::from_vessel(vessel, &mut SimulationRng::new(7), &ItemRegistry::new())
+                .0
+                .id()
+        };
+        assert_eq!(wreck_id(), wreck_id());
+    }
+
+    #[test]
+    fn keeps_part_of_items_and_empty_modules() {
+        let mut surviving_modules = 0;
+        for seed in 0..20 {
+            let modules: Vec<Box<dyn Module>> = (0..4).map(|_| Hold::new(100, 0) as _).collect();
+            let vessel = Vessel::new(
+                PersonId::nil(),
+                (0., 0.).into(),
+                modules,
+                &mut SimulationRng::new(seed),
+            );
+            let (wreck, released) =
+                Wreck::from_vessel(vessel, &mut SimulationRng::new(seed), &ItemRegistry::new());
+            assert!(released.is_empty());
+            assert!(wreck.items().count("ore".to_string()) <= 400);
+            assert!(
+                wreck
+                    .modules()
+                    .all(|module| module.storages()[0].is_empty())
+            );
+            let module_count = wreck.modules().count();
+            assert_eq!(
+                wreck.research_data(),
+                RESEARCH_DATA_PER_MODULE * module_count as ItemCount
+            );
+            surviving_modules += module_count;
+        }
+        // some modules survive, some are lost
+        assert!(surviving_modules > 0 && surviving_modules < 80);
+    }
+
+    #[test]
+    fn abandoning_moves_crew_to_docked_vessels() {
+        let (vessel, person_id) = crewed_vessel_with_boat(1);
+        let vessel_id = vessel.id();
+        let mut environment = Environment::new(SimulationRng::new(0), vec![vessel], vec![]);
+        assert!(environment.wreck_vessel(vessel_id, &ItemRegistry::new()));
+        assert_eq!(environment.wrecks().len(), 1);
+        let boat = &environment.vessels()[0];
+        assert!(
+            boat.modules()
+                .any(|module| module.contains_person(person_id))
+        );
+    }
+
+    #[test]
+    fn refuses_to_abandon_without_seats_for_crew() {
+        let (vessel, person_id) = crewed_vessel_with_boat(0);
+        let vessel_id = vessel.id();
+        let mut environment = Environment::new(SimulationRng::new(0), vec![vessel], vec![]);
+        assert!(!environment.wreck_vessel(vessel_id, &ItemRegistry::new()));
+        assert!(environment.wrecks().is_empty());
+        let vessel = &environment.vessels()[0];
+        assert_eq!(vessel.id(), vessel_id);
+        assert!(
+            vessel
+                .modules()
+                .any(|module| module.contains_person(person_id))
+        );
+    }
+}
@@ -0,0 +1,188 @@
+use crate::finance::{Ledger, TransferError, Wallet};
+use crate::item::Money;
+use crate::person::PersonId;
+use crate::utils::time_point::StaticTimePoint;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// wallets of persons and vessel owners and the ledger of money moved between them
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct Bank {
+    wallets: BTreeMap<PersonId, Wallet>,
+    ledger: Ledger,
+}
+
+impl Bank {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn balance(&self, account: PersonId) -> Money {
+        self.wallets
+            .get(&account)
+            .map(|wallet| wallet.balance())
+            .unwrap_or(0)
+    }
+
+    pub fn wallets(&self) -> impl Iterator<Item = (&PersonId, &Wallet)> {
+        self.wallets.iter()
+    }
+
+    pub fn ledger(&self) -> &Ledger {
+        &self.ledger
+    }
+
+    /// wallet of the account. Created empty if the account has none yet
+    pub(crate) fn wallet_mut(&mut self, account: PersonId) -> &mut Wallet {
+        self.wallets.entry(account).or_default()
+    }
+
+    /// money coming from outside of the bank
+    pub fn deposit(
+        &mut self,
+        to: PersonId,
+        amount: Money,
+        time: StaticTimePoint,
+        memo: String,
+    ) -> Result<(), TransferError> {
+        self.wallet_mut(to).deposit(amount)?;
+        self.ledger.record(time, None, Some(to), amount, memo);
+        Ok(())
+    }
+
+    /// money leaving the bank
+    pub fn withdraw(
+        &mut self,
+        from: PersonId,
+        amount: Money,
+        time: StaticTimePoint,
+        memo: String,
+    ) -> Result<(), TransferError> {
+        self.wallet_mut(from).withdraw(amount)?;
+        self.ledger.record(time, Some(from), None, amount, memo);
+        Ok(())
+    }
+
+    /// moves money between accounts. Nothing is changed on error
+    pub fn transfer(
+        &mut self,
+        from: PersonId,
+        to: PersonId,
+        amount: Money,
+        time: StaticTimePoint,
+        memo: String,
+    ) -> Result<(), TransferError> {
+        self.wallet_mut(from).can_withdraw(amount)?;
+        if from != to {
+            self.wallet_mut(to).can_deposit(amount)?;
+            self.wallet_mut(from).withdraw(amount)?;
+            self.wallet_mut(to).deposit(amount)?;
+        }
+        self.ledger.record(time, Some(from), Some(to), amount, memo);
+        Ok(())
+    }
+
+    /// records money moved in or out of a wallet obtained by `wallet_mut`
+    pub(crate) fn record(
+        &mut self,
+        time: StaticTimePoint,
+        from: Option<PersonId>,
+        to: Option<PersonId>,
+        amount: Money,
+        memo: String,
+    ) {
+        self.ledger.record(time, from, to, amount, memo);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Bank;
+    use crate::finance::{LEDGER_CAPACITY, TransferError};
+    use crate::item::Money;
+    use crate::person::PersonId;
+    use crate::utils::time_point::StaticTimePoint;
+
+    #[test]
+    fn transfer() {
+        let time = StaticTimePoint::default();
+        let alice = PersonId::new_v4();
+        let bob = PersonId::new_v4();
+        let mut bank = Bank::new();
+        bank.deposit(alice, 100, time, "salary".to_string())
+            .unwrap();
+        bank.deposit(bob, Money::MAX - 10, time, "inheritance".to_string())
+            .unwrap();
+
+        assert_eq!(
+            bank.transfer(alice, bob, 101, time, "rent".to_string()),
+            Err(TransferError::InsufficientFunds {
+                balance: 100,
+                amount: 101
+            })
+        );
+        assert_eq!(
+            bank.transfer(alice, bob, 11, time, "rent".to_string()),
+            Err(TransferError::Overflow {
+                balance: Money::MAX - 10,
+                amount: 11
+            })
+        );
+        assert_eq!(bank.balance(alice), 100);
+        assert_eq!(bank.balance(bob), Money::MAX - 10);
+
+        bank.transfer(alice, bob, 10, time, "rent".to_string())
+            .unwrap();
+        assert_eq!(bank.balance(alice), 90);
+        assert_eq!(bank.balance(bob), Money::MAX);
+        assert_eq!(bank.ledger().iter().count(), 3);
+        assert_eq!(bank.ledger().of(alice).count(), 2);
+        let rent = bank.ledger().iter().last().unwrap();
+        assert_eq!(
+            (rent.from(), rent.to(), rent.amount(), rent.memo()),
+            (Some(alice), Some(bob), 10, "rent")
+        );
+
+        let bank: Bank = serde_json::from_str(&serde_json::to_string(&bank).unwrap()).unwrap();
+        assert_eq!(bank.balance(bob), Money::MAX);
+        assert_eq!(bank.ledger().iter().count(), 3);
+    }
+
+    #[test]
+    fn ledger_keeps_most_recent_transactions() {
+        let time = StaticTimePoint::default();
+        let alice = PersonId::new_v4();
+        let mut bank = Bank::new();
+        for i in 0..LEDGER_CAPACITY + 5 {
+            bank.deposit(alice, 1, time, i.to_string()).unwrap();
+        }
+        assert_eq!(bank.balance(alice), (LEDGER_CAPACITY + 5) as Money);
+        assert_eq!(bank.ledger().iter().count(), LEDGER_CAPACITY);
+        assert_eq!(bank.ledger().iter().next().unwrap().memo(), "5");
+
+        bank.withdraw(alice, 10, time, "groceries".to_string())
+            .unwrap();
+        assert_eq!(bank.ledger().iter().count(), LEDGER_CAPACITY);
+        assert_eq!(bank.ledger().iter().next().unwrap().memo(), "6");
+        let groceries = bank.ledger().iter().last().unwrap();
+        assert_eq!((groceries.from(), groceries.to()), (Some(alice), None));
+    }
+
+    #[test]
+    fn transfer_to_self() {
+        let time = StaticTimePoint::default();
+        let alice = PersonId::new_v4();
+        let mut bank = Bank::new();
+        bank.deposit(alice, Money::MAX, time, "lottery".to_string())
+            .unwrap();
+        // no overflow as the money stays in the wallet
+        bank.transfer(alice, alice, 10, time, "piggy bank".to_string())
+            .unwrap();
+        assert_eq!(bank.balance(alice), Money::MAX);
+        assert_eq!(
+            bank.transfer(alice, alice, Money::MAX, time, "piggy bank".to_string()),
+            Ok(())
+        );
+        assert_eq!(bank.ledger().of(alice).count(), 3);
+    }
+}
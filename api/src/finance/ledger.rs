@@ -0,0 +1,83 @@
+use crate::item::Money;
+use crate::person::PersonId;
+use crate::utils::time_point::StaticTimePoint;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+/// number of most recent transactions a ledger keeps
+pub static LEDGER_CAPACITY: usize = 1000;
+
+/// money moved between accounts. `None` account is outside of the bank, e.g. a trading terminal till
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Transaction {
+    time: StaticTimePoint,
+    from: Option<PersonId>,
+    to: Option<PersonId>,
+    amount: Money,
+    memo: String,
+}
+
+impl Transaction {
+    pub fn time(&self) -> StaticTimePoint {
+        self.time
+    }
+
+    pub fn from(&self) -> Option<PersonId> {
+        self.from
+    }
+
+    pub fn to(&self) -> Option<PersonId> {
+        self.to
+    }
+
+    pub fn amount(&self) -> Money {
+        self.amount
+    }
+
+    pub fn memo(&self) -> &str {
+        &self.memo
+    }
+}
+
+/// most recent transactions in the order they were made
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct Ledger {
+    transactions: VecDeque<Transaction>,
+}
+
+impl Ledger {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub(crate) fn record(
+        &mut self,
+        time: StaticTimePoint,
+        from: Option<PersonId>,
+        to: Option<PersonId>,
+        amount: Money,
+        memo: String,
+    ) {
+        if self.transactions.len() >= LEDGER_CAPACITY {
+            self.transactions.pop_front();
+        }
+        self.transactions.push_back(Transaction {
+            time,
+            from,
+            to,
+            amount,
+            memo,
+        });
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Transaction> {
+        self.transactions.iter()
+    }
+
+    /// transactions the account took part in
+    pub fn of(&self, account: PersonId) -> impl Iterator<Item = &Transaction> {
+        self.transactions
+            .iter()
+            .filter(move |t| t.from == Some(account) || t.to == Some(account))
+    }
+}
@@ -0,0 +1,8 @@
+mod wallet;
+pub use wallet::*;
+
+mod ledger;
+pub use ledger::*;
+
+mod bank;
+pub use bank::*;
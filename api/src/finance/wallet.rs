@@ -0,0 +1,84 @@
+use crate::item::Money;
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TransferError {
+    /// the receiving balance would exceed `Money::MAX`
+    Overflow {
+        balance: Money,
+        amount: Money,
+    },
+    InsufficientFunds {
+        balance: Money,
+        amount: Money,
+    },
+}
+
+impl Display for TransferError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TransferError::Overflow { balance, amount } => {
+                write!(f, "Can not add {} to balance {}: overflow", amount, balance)
+            }
+            TransferError::InsufficientFunds { balance, amount } => {
+                write!(
+                    f,
+                    "Can not take {} from balance {}: insufficient funds",
+                    amount, balance
+                )
+            }
+        }
+    }
+}
+
+impl Error for TransferError {}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Wallet {
+    balance: Money,
+}
+
+impl Wallet {
+    pub fn new(balance: Money) -> Self {
+        Self { balance }
+    }
+
+    pub fn balance(&self) -> Money {
+        self.balance
+    }
+
+    pub fn can_deposit(&self, amount: Money) -> Result<(), TransferError> {
+        self.balance
+            .checked_add(amount)
+            .map(|_| ())
+            .ok_or(TransferError::Overflow {
+                balance: self.balance,
+                amount,
+            })
+    }
+
+    pub fn can_withdraw(&self, amount: Money) -> Result<(), TransferError> {
+        if self.balance < amount {
+            Err(TransferError::InsufficientFunds {
+                balance: self.balance,
+                amount,
+            })
+        } else {
+            Ok(())
+        }
+    }
+
+    pub fn deposit(&mut self, amount: Money) -> Result<(), TransferError> {
+        self.can_deposit(amount)?;
+        self.balance += amount;
+        Ok(())
+    }
+
+    pub fn withdraw(&mut self, amount: Money) -> Result<(), TransferError> {
+        self.can_withdraw(amount)?;
+        self.balance -= amount;
+        Ok(())
+    }
+}
@@ -4,7 +4,7 @@ use std::fmt::{Debug, Display, Formatter};
 pub type ItemId = String;
 pub type ItemCount = u32;
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Item {
     pub(crate) id: ItemId,
     pub(crate) count: ItemCount,
@@ -23,12 +23,18 @@ impl Item {
     }
 }
 
+impl Display for Item {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", self.count, self.id)
+    }
+}
+
 #[derive(Debug)]
 pub struct DuplicateItemError;
 
 impl Display for DuplicateItemError {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        todo!()
+        write!(f, "Item list contains the same item more than once")
     }
 }
 
@@ -0,0 +1,325 @@
+use crate::item::{BuyOffer, ItemCount, ItemId, Money, SellOffer};
+use crate::recipe::{InputRecipe, OutputRecipe, Recipe};
+use crate::utils::utils::Float;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+
+/// volume of one item unit in liters
+pub type ItemVolume = u64;
+/// mass of one item unit in kilograms
+pub type ItemMass = u64;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum ItemCategory {
+    RawMaterial,
+    RefinedMaterial,
+    Component,
+    Fuel,
+    Data,
+    Consumable,
+}
+
+/// properties shared by all items with the same id
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ItemInfo {
+    id: ItemId,
+    name: String,
+    category: ItemCategory,
+    volume: ItemVolume,
+    mass: ItemMass,
+    base_price: Money,
+    /// max count of units that can be handled as one piece of cargo. 1 if the item is not stackable
+    max_stack: ItemCount,
+    /// energy released when one unit is burned in a reactor, in kilojoules. 0 if the item is not a fuel
+    #[serde(default)]
+    fuel_energy: Float,
+}
+
+impl ItemInfo {
+    pub fn new(
+        id: ItemId,
+        name: String,
+        category: ItemCategory,
+        volume: ItemVolume,
+        mass: ItemMass,
+        base_price: Money,
+        max_stack: ItemCount,
+    ) -> Self {
+        Self {
+            id,
+            name,
+            category,
+            volume,
+            mass,
+            base_price,
+            max_stack,
+            fuel_energy: 0.,
+        }
+    }
+
+    pub fn with_fuel_energy(mut self, fuel_energy: Float) -> Self {
+        self.fuel_energy = fuel_energy;
+        self
+    }
+
+    pub fn id(&self) -> &ItemId {
+        &self.id
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn category(&self) -> ItemCategory {
+        self.category
+    }
+
+    pub fn volume(&self) -> ItemVolume {
+        self.volume
+    }
+
+    pub fn mass(&self) -> ItemMass {
+        self.mass
+    }
+
+    pub fn base_price(&self) -> Money {
+        self.base_price
+    }
+
+    pub fn max_stack(&self) -> ItemCount {
+        self.max_stack
+    }
+
+    pub fn is_stackable(&self) -> bool {
+        self.max_stack > 1
+    }
+
+    pub fn fuel_energy(&self) -> Float {
+        self.fuel_energy
+    }
+
+    pub fn is_fuel(&self) -> bool {
+        self.fuel_energy > 0.
+    }
+}
+
+#[derive(Debug)]
+pub enum ItemRegistryError {
+    Io(PathBuf, std::io::Error),
+    UnsupportedFormat(PathBuf),
+    Json(serde_json::Error),
+    Ron(ron::error::SpannedError),
+    DuplicateItem(ItemId),
+    ZeroStackSize(ItemId),
+}
+
+impl Display for ItemRegistryError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ItemRegistryError::Io(path, err) => {
+                write!(f, "Can not read item data `{}`: {}", path.display(), err)
+            }
+            ItemRegistryError::UnsupportedFormat(path) => write!(
+                f,
+                "Item data `{}` is neither `.json` nor `.ron`",
+                path.display()
+            ),
+            ItemRegistryError::Json(err) => write!(f, "Invalid item data: {}", err),
+            ItemRegistryError::Ron(err) => write!(f, "Invalid item data: {}", err),
+            ItemRegistryError::DuplicateItem(id) => write!(f, "Item `{}` is defined twice", id),
+            ItemRegistryError::ZeroStackSize(id) => {
+                write!(f, "Item `{}` has zero max stack size", id)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ItemRegistryError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownItemError {
+    pub id: ItemId,
+}
+
+impl Display for UnknownItemError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Unknown item `{}`", self.id)
+    }
+}
+
+impl std::error::Error for UnknownItemError {}
+
+/// all item types known to the simulation.
+/// Item data files contain a list of `ItemInfo` in JSON or RON
+#[derive(Debug, Default)]
+pub struct ItemRegistry {
+    items: BTreeMap<ItemId, ItemInfo>,
+}
+
+impl ItemRegistry {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// panics if an item with the same id is already registered
+    pub fn with(mut self, info: ItemInfo) -> Self {
+        if let Err(err) = self.insert(info) {
+            panic!("{}", err)
+        }
+        self
+    }
+
+    pub fn insert(&mut self, info: ItemInfo) -> Result<(), ItemRegistryError> {
+        if info.max_stack == 0 {
+            return Err(ItemRegistryError::ZeroStackSize(info.id));
+        }
+        let id = info.id.clone();
+        self.items
+            .try_insert(id.clone(), info)
+            .map(|_| ())
+            .map_err(|_| ItemRegistryError::DuplicateItem(id))
+    }
+
+    pub fn extend(
+        &mut self,
+        items: impl IntoIterator<Item = ItemInfo>,
+    ) -> Result<(), ItemRegistryError> {
+        for info in items {
+            self.insert(info)?;
+        }
+        Ok(())
+    }
+
+    pub fn extend_from_json(&mut self, bytes: &[u8]) -> Result<(), ItemRegistryError> {
+        let items: Vec<ItemInfo> =
+            serde_json::from_slice(bytes).map_err(ItemRegistryError::Json)?;
+        self.extend(items)
+    }
+
+    pub fn extend_from_ron(&mut self, str: &str) -> Result<(), ItemRegistryError> {
+        let items: Vec<ItemInfo> = ron::from_str(str).map_err(ItemRegistryError::Ron)?;
+        self.extend(items)
+    }
+
+    /// format is chosen by file extension
+    pub fn extend_from_file(&mut self, path: &Path) -> Result<(), ItemRegistryError> {
+        let read = || std::fs::read(path).map_err(|e| ItemRegistryError::Io(path.into(), e));
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("json") => self.extend_from_json(&read()?),
+            Some("ron") => self.extend_from_ron(&String::from_utf8_lossy(&read()?)),
+            _ => Err(ItemRegistryError::UnsupportedFormat(path.into())),
+        }
+    }
+
+    pub fn get(&self, id: &ItemId) -> Option<&ItemInfo> {
+        self.items.get(id)
+    }
+
+    pub fn contains(&self, id: &ItemId) -> bool {
+        self.items.contains_key(id)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &ItemInfo> {
+        self.items.values()
+    }
+
+    pub fn check(&self, id: &ItemId) -> Result<&ItemInfo, UnknownItemError> {
+        self.get(id)
+            .ok_or_else(|| UnknownItemError { id: id.clone() })
+    }
+
+    pub fn validate_input_recipe(&self, recipe: &InputRecipe) -> Result<(), UnknownItemError> {
+        for item in recipe.iter() {
+            self.check(item.id())?;
+        }
+        Ok(())
+    }
+
+    pub fn validate_output_recipe(&self, recipe: &OutputRecipe) -> Result<(), UnknownItemError> {
+        for item in recipe.iter() {
+            self.check(item.id())?;
+        }
+        Ok(())
+    }
+
+    pub fn validate_recipe(&self, recipe: &Recipe) -> Result<(), UnknownItemError> {
+        self.validate_input_recipe(recipe.input())?;
+        self.validate_output_recipe(recipe.output())
+    }
+
+    pub fn validate_buy_offer(&self, offer: &BuyOffer) -> Result<(), UnknownItemError> {
+        self.check(&offer.item).map(|_| ())
+    }
+
+    pub fn validate_sell_offer(&self, offer: &SellOffer) -> Result<(), UnknownItemError> {
+        self.check(&offer.item).map(|_| ())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ItemRegistry, ItemRegistryError};
+    use crate::item::Item;
+    use crate::recipe::{InputRecipe, OutputRecipe, Recipe};
+    use std::time::Duration;
+
+    static ITEMS_RON: &str = r#"[
+        (
+            id: "ore",
+            name: "Ore",
+            category: RawMaterial,
+            volume: 1,
+            mass: 3,
+            base_price: 2,
+            max_stack: 1000,
+        ),
+        (
+            id: "steel",
+            name: "Steel",
+            category: RefinedMaterial,
+            volume: 1,
+            mass: 8,
+            base_price: 10,
+            max_stack: 1000,
+        ),
+    ]"#;
+
+    #[test]
+    fn ron_and_json() {
+        let mut registry = ItemRegistry::new();
+        registry.extend_from_ron(ITEMS_RON).unwrap();
+        assert_eq!(registry.check(&"steel".to_string()).unwrap().mass(), 8);
+
+        let json = serde_json::to_vec(&registry.iter().collect::<Vec<_>>()).unwrap();
+        let mut from_json = ItemRegistry::new();
+        from_json.extend_from_json(&json).unwrap();
+        assert_eq!(from_json.iter().count(), 2);
+
+        assert!(matches!(
+            registry.extend_from_json(&json),
+            Err(ItemRegistryError::DuplicateItem(_))
+        ));
+    }
+
+    #[test]
+    fn validate_recipe() {
+        let mut registry = ItemRegistry::new();
+        registry.extend_from_ron(ITEMS_RON).unwrap();
+
+        let recipe = |output: &str| {
+            Recipe::new(
+                InputRecipe::try_from(vec![Item::new("ore".to_string(), 2)]).unwrap(),
+                OutputRecipe::try_from(vec![Item::new(output.to_string(), 1)]).unwrap(),
+                Duration::from_secs(1),
+            )
+        };
+
+        assert!(registry.validate_recipe(&recipe("steel")).is_ok());
+        assert_eq!(
+            registry.validate_recipe(&recipe("stel")).unwrap_err().id,
+            "stel"
+        );
+    }
+}
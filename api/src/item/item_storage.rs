@@ -1,54 +1,187 @@
-use crate::item::{DuplicateItemError, Item, ItemCount, ItemId};
-use crate::recipe::InputRecipe;
+use crate::item::{
+    DuplicateItemError, Item, ItemCount, ItemId, ItemMass, ItemRegistry, ItemVolume,
+};
+use crate::recipe::{InputRecipe, OutputRecipe};
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
 
 #[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct ItemStorage {
     content: BTreeMap<ItemId, ItemCount>,
+    /// max total volume of stored items. `None` if unlimited
+    #[serde(with = "crate::utils::untagged_option")]
+    volume_capacity: Option<ItemVolume>,
+    /// max total mass of stored items. `None` if unlimited
+    #[serde(with = "crate::utils::untagged_option")]
+    mass_capacity: Option<ItemMass>,
 }
 
+/// creates a storage with unlimited capacity. Counts of items with the same id are summed up
 impl FromIterator<Item> for ItemStorage {
     fn from_iter<T: IntoIterator<Item = Item>>(iter: T) -> Self {
-        todo!()
+        let mut result = Self::new();
+        for item in iter {
+            *result.content.entry(item.id).or_default() += item.count;
+        }
+        result.content.retain(|_, count| *count > 0);
+        result
     }
 }
 
+/// creates a storage with unlimited capacity
 impl TryFrom<Vec<Item>> for ItemStorage {
     type Error = DuplicateItemError;
 
     fn try_from(value: Vec<Item>) -> Result<Self, Self::Error> {
-        todo!()
+        let mut result = Self::new();
+        for item in value {
+            result
+                .content
+                .try_insert(item.id, item.count)
+                .map_err(|_| DuplicateItemError)?;
+        }
+        result.content.retain(|_, count| *count > 0);
+        Ok(result)
     }
 }
 
 impl ItemStorage {
+    /// creates an empty storage with unlimited capacity
     pub fn new() -> Self {
         Default::default()
     }
 
+    pub fn with_capacity(volume_capacity: ItemVolume, mass_capacity: ItemMass) -> Self {
+        Self {
+            content: Default::default(),
+            volume_capacity: Some(volume_capacity),
+            mass_capacity: Some(mass_capacity),
+        }
+    }
+
+    pub fn volume_capacity(&self) -> Option<ItemVolume> {
+        self.volume_capacity
+    }
+
+    pub fn mass_capacity(&self) -> Option<ItemMass> {
+        self.mass_capacity
+    }
+
+    /// total volume of stored items. Items missing in the registry are not counted
+    pub fn volume(&self, registry: &ItemRegistry) -> ItemVolume {
+        self.content
+            .iter()
+            .filter_map(|(id, count)| {
+                registry
+                    .get(id)
+                    .map(|info| info.volume() * *count as ItemVolume)
+            })
+            .sum()
+    }
+
+    /// total mass of stored items. Items missing in the registry are not counted
+    pub fn mass(&self, registry: &ItemRegistry) -> ItemMass {
+        self.content
+            .iter()
+            .filter_map(|(id, count)| {
+                registry
+                    .get(id)
+                    .map(|info| info.mass() * *count as ItemMass)
+            })
+            .sum()
+    }
+
+    /// `None` if unlimited
+    pub fn free_volume(&self, registry: &ItemRegistry) -> Option<ItemVolume> {
+        self.volume_capacity
+            .map(|capacity| capacity.saturating_sub(self.volume(registry)))
+    }
+
+    /// `None` if unlimited
+    pub fn free_mass(&self, registry: &ItemRegistry) -> Option<ItemMass> {
+        self.mass_capacity
+            .map(|capacity| capacity.saturating_sub(self.mass(registry)))
+    }
+
+    /// how many units of an item can be added before the storage is full.
+    /// Each item is kept in one stack which does not grow beyond `max_stack` of the item.
+    /// Items missing in the registry fit into unlimited storages only
+    pub fn free_space_for(&self, item_id: &ItemId, registry: &ItemRegistry) -> ItemCount {
+        let Some(info) = registry.get(item_id) else {
+            if self.volume_capacity.is_none() && self.mass_capacity.is_none() {
+                return ItemCount::MAX;
+            }
+            return 0;
+        };
+
+        let fit = |free: Option<u64>, unit: u64| match (free, unit) {
+            (None, _) | (_, 0) => ItemCount::MAX,
+            (Some(free), unit) => (free / unit).min(ItemCount::MAX as u64) as ItemCount,
+        };
+
+        let stack = info.max_stack().saturating_sub(self.count(item_id.clone()));
+        fit(self.free_volume(registry), info.volume())
+            .min(fit(self.free_mass(registry), info.mass()))
+            .min(stack)
+    }
+
     /// returns the rest that did not fit inside storage space
-    pub fn add_item(&mut self, item: Item) -> Item {
-        todo!()
+    pub fn add_item(&mut self, item: Item, registry: &ItemRegistry) -> Item {
+        let count = item.count.min(self.free_space_for(&item.id, registry));
+        if count > 0 {
+            *self.content.entry(item.id.clone()).or_default() += count;
+        }
+        Item::new(item.id, item.count - count)
     }
 
     /// returns true if an item was added, false if not due to full storage
-    pub fn try_add_item(&mut self, item: Item) -> bool {
-        todo!()
+    pub fn try_add_item(&mut self, item: Item, registry: &ItemRegistry) -> bool {
+        if self.free_space_for(&item.id, registry) < item.count {
+            return false;
+        }
+        if item.count > 0 {
+            *self.content.entry(item.id).or_default() += item.count;
+        }
+        true
     }
 
     /// remove as many items as possible
     pub fn remove_item(&mut self, item_id: ItemId, count: ItemCount) -> Item {
-        todo!()
+        let removed = match self.content.get_mut(&item_id) {
+            None => 0,
+            Some(c) => {
+                let removed = count.min(*c);
+                *c -= removed;
+                if *c == 0 {
+                    self.content.remove(&item_id);
+                }
+                removed
+            }
+        };
+        Item::new(item_id, removed)
     }
 
     /// returns true if an item  was removed, false if not due to not enough item count in storage
     pub fn try_remove_item(&mut self, item: Item) -> bool {
-        todo!()
+        if !self.contains(&item) {
+            return false;
+        }
+        self.remove_item(item.id, item.count);
+        true
     }
 
     pub fn count(&self, item_id: ItemId) -> ItemCount {
-        todo!()
+        self.content.get(&item_id).cloned().unwrap_or(0)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.content.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = Item> {
+        self.content
+            .iter()
+            .map(|(id, count)| Item::new(id.clone(), *count))
     }
 
     pub fn contains(&self, item: &Item) -> bool {
@@ -78,4 +211,134 @@ impl ItemStorage {
 
         true
     }
+
+    /// returns true if the whole output was added, false if nothing was added due to full storage
+    pub fn try_produce(&mut self, output: OutputRecipe, registry: &ItemRegistry) -> bool {
+        self.try_add_items(output, registry)
+    }
+
+    /// adds either all items or none of them. Returns false if they do not fit together
+    pub fn try_add_items(
+        &mut self,
+        items: impl IntoIterator<Item = Item>,
+        registry: &ItemRegistry,
+    ) -> bool {
+        let mut result = self.clone();
+        for item in items {
+            if !result.try_add_item(item, registry) {
+                return false;
+            }
+        }
+
+        *self = result;
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ItemStorage;
+    use crate::item::{Item, ItemCategory, ItemInfo, ItemRegistry};
+    use crate::recipe::OutputRecipe;
+
+    fn registry() -> ItemRegistry {
+        let info = |id: &str, volume, mass| {
+            ItemInfo::new(
+                id.to_string(),
+                id.to_string(),
+                ItemCategory::RawMaterial,
+                volume,
+                mass,
+                1,
+                100,
+            )
+        };
+        ItemRegistry::new()
+            .with(info("ore", 2, 5))
+            .with(info("gas", 10, 0))
+    }
+
+    #[test]
+    fn add_item_returns_rest() {
+        let registry = registry();
+        let mut storage = ItemStorage::with_capacity(100, 40);
+
+        let rest = storage.add_item(Item::new("ore".to_string(), 10), &registry);
+        assert_eq!(rest, Item::new("ore".to_string(), 2));
+        assert_eq!(storage.count("ore".to_string()), 8);
+        assert_eq!(storage.mass(&registry), 40);
+        assert_eq!(storage.free_mass(&registry), Some(0));
+
+        let rest = storage.add_item(Item::new("gas".to_string(), 10), &registry);
+        assert_eq!(rest, Item::new("gas".to_string(), 2));
+        assert_eq!(storage.free_volume(&registry), Some(4));
+    }
+
+    #[test]
+    fn unknown_items_do_not_fit() {
+        let registry = registry();
+        let mut storage = ItemStorage::with_capacity(100, 100);
+        assert!(!storage.try_add_item(Item::new("unobtainium".to_string(), 1), &registry));
+        assert!(storage.is_empty());
+
+        let mut unlimited = ItemStorage::new();
+        assert!(unlimited.try_add_item(Item::new("unobtainium".to_string(), 1), &registry));
+    }
+
+    #[test]
+    fn stack_does_not_exceed_max_stack() {
+        let registry = registry();
+        let mut unlimited = ItemStorage::new();
+        let rest = unlimited.add_item(Item::new("ore".to_string(), 150), &registry);
+        assert_eq!(rest, Item::new("ore".to_string(), 50));
+        assert!(!unlimited.try_add_item(Item::new("ore".to_string(), 1), &registry));
+        assert_eq!(unlimited.free_space_for(&"gas".to_string(), &registry), 100);
+
+        let mut storage = ItemStorage::with_capacity(1000, 1000);
+        assert!(!storage.try_add_item(Item::new("ore".to_string(), 101), &registry));
+        assert!(storage.try_add_item(Item::new("ore".to_string(), 100), &registry));
+        assert_eq!(storage.free_space_for(&"ore".to_string(), &registry), 0);
+    }
+
+    #[test]
+    fn remove_item() {
+        let mut storage: ItemStorage = vec![Item::new("ore".to_string(), 3)].try_into().unwrap();
+        assert!(!storage.try_remove_item(Item::new("ore".to_string(), 4)));
+        assert_eq!(
+            storage.remove_item("ore".to_string(), 4),
+            Item::new("ore".to_string(), 3)
+        );
+        assert!(storage.is_empty());
+    }
+
+    #[test]
+    fn produce_is_all_or_nothing() {
+        let registry = registry();
+        let mut storage = ItemStorage::with_capacity(20, 100);
+        let output: OutputRecipe = vec![
+            Item::new("ore".to_string(), 5),
+            Item::new("gas".to_string(), 2),
+        ]
+        .try_into()
+        .unwrap();
+
+        assert!(!storage.try_produce(output.clone(), &registry));
+        assert!(storage.is_empty());
+
+        storage = ItemStorage::with_capacity(30, 100);
+        assert!(storage.try_produce(output, &registry));
+        assert_eq!(storage.volume(&registry), 30);
+    }
+
+    #[test]
+    fn duplicates() {
+        let items = || {
+            vec![
+                Item::new("ore".to_string(), 1),
+                Item::new("ore".to_string(), 2),
+            ]
+        };
+        assert!(ItemStorage::try_from(items()).is_err());
+        assert_eq!(ItemStorage::from_iter(items()).count("ore".to_string()), 3);
+    }
 }
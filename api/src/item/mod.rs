@@ -9,3 +9,9 @@ pub use order::*;
 
 mod offer;
 pub use offer::*;
+
+mod item_registry;
+pub use item_registry::*;
+
+mod pricing;
+pub use pricing::*;
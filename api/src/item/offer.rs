@@ -2,9 +2,10 @@ use crate::item::{ItemCount, ItemId, Money};
 use crate::utils::range::Range;
 use serde::{Deserialize, Serialize};
 
-type OfferId = u64;
+pub type OfferId = u64;
 
-#[derive(Debug, Serialize, Deserialize)]
+/// goods a trading terminal sells. Customers place buy orders for them
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BuyOffer {
     pub id: OfferId,
     pub item: ItemId,
@@ -12,7 +13,8 @@ pub struct BuyOffer {
     pub price_per_unit: Money,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+/// goods a trading terminal buys. Customers place sell orders for them
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SellOffer {
     pub id: OfferId,
     pub item: ItemId,
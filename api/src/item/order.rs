@@ -1,108 +1,404 @@
+use crate::finance::TransferError;
 use crate::item::Item;
+use crate::utils::time_point::StaticTimePoint;
 use crate::vessel::VesselId;
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
-use std::rc::Rc;
-use std::sync::Weak;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
 use uuid::Uuid;
 
-pub(crate) type Money = usize;
+pub type Money = usize;
+pub type OrderId = Uuid;
+pub type ContractId = Uuid;
 
 #[derive(Debug, Serialize, Deserialize)]
 struct BuyOrderImpl {
     vessel_to_buy_from: VesselId,
+    buyer: VesselId,
     items: Vec<Item>,
     price: Money,
+    /// orders of saves older than deadlines expire on the first tick
+    #[serde(default)]
+    deadline: StaticTimePoint,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 struct SellOrderImpl {
     vessel_to_sell_to: VesselId,
+    seller: VesselId,
     items: Vec<Item>,
     price: Money,
+    /// orders of saves older than deadlines expire on the first tick
+    #[serde(default)]
+    deadline: StaticTimePoint,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+/// handle of a buy order kept by the buyer. The order is looked up by id among pending orders
+/// of the terminal, so the handle stays valid after loading. Getters return `None` once the order is settled or cancelled
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WeakBuyOrder {
-    id: Uuid,
-    data: Option<Weak<BuyOrderImpl>>,
+    id: OrderId,
 }
 
 impl WeakBuyOrder {
-    pub fn vessel_to_buy_from(&self) -> Option<VesselId> {
-        todo!()
+    pub fn id(&self) -> OrderId {
+        self.id
+    }
+    pub fn is_pending(&self, orders: &OrderHolder) -> bool {
+        self.upgrade(orders).is_some()
+    }
+    pub fn vessel_to_buy_from(&self, orders: &OrderHolder) -> Option<VesselId> {
+        self.upgrade(orders).map(|order| order.vessel_to_buy_from())
+    }
+    pub fn buyer(&self, orders: &OrderHolder) -> Option<VesselId> {
+        self.upgrade(orders).map(|order| order.buyer())
+    }
+    pub fn items(&self, orders: &OrderHolder) -> Option<Vec<Item>> {
+        self.upgrade(orders).map(|order| order.items())
     }
-    pub fn items(&self) -> Option<Vec<Item>> {
-        todo!()
+    pub fn price(&self, orders: &OrderHolder) -> Option<Money> {
+        self.upgrade(orders).map(|order| order.price())
     }
-    pub fn price(&self) -> Option<Money> {
-        todo!()
+    fn upgrade<'a>(&self, orders: &'a OrderHolder) -> Option<&'a BuyOrder> {
+        orders.buy_order(self.id)
     }
 }
 
+/// buy order owned by a trading terminal. `items` are reserved for the buyer until the order is settled
 #[derive(Debug, Serialize, Deserialize)]
 pub struct BuyOrder {
-    id: Uuid,
-    data: Rc<BuyOrderImpl>,
+    id: OrderId,
+    data: BuyOrderImpl,
 }
 
 impl BuyOrder {
-    pub fn new() -> (Self, WeakBuyOrder) {
-        todo!()
+    pub fn new(
+        id: OrderId,
+        vessel_to_buy_from: VesselId,
+        buyer: VesselId,
+        items: Vec<Item>,
+        price: Money,
+        deadline: StaticTimePoint,
+    ) -> (Self, WeakBuyOrder) {
+        let data = BuyOrderImpl {
+            vessel_to_buy_from,
+            buyer,
+            items,
+            price,
+            deadline,
+        };
+        (Self { id, data }, WeakBuyOrder { id })
     }
 
+    pub fn id(&self) -> OrderId {
+        self.id
+    }
     pub fn vessel_to_buy_from(&self) -> VesselId {
-        todo!()
+        self.data.vessel_to_buy_from
+    }
+    pub fn buyer(&self) -> VesselId {
+        self.data.buyer
     }
     pub fn items(&self) -> Vec<Item> {
-        todo!()
+        self.data.items.clone()
     }
     pub fn price(&self) -> Money {
-        todo!()
+        self.data.price
+    }
+    pub fn deadline(&self) -> StaticTimePoint {
+        self.data.deadline
+    }
+    pub fn is_expired(&self, now: StaticTimePoint) -> bool {
+        now >= self.data.deadline
+    }
+    pub fn downgrade(&self) -> WeakBuyOrder {
+        WeakBuyOrder { id: self.id }
     }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+/// handle of a sell order kept by the seller. The order is looked up by id among pending orders
+/// of the terminal, so the handle stays valid after loading. Getters return `None` once the order is settled or cancelled
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WeakSellOrder {
-    id: Uuid,
-    data: Option<Weak<SellOrderImpl>>,
+    id: OrderId,
 }
 
 impl WeakSellOrder {
-    pub fn vessel_to_sell_to(&self) -> Option<VesselId> {
-        todo!()
+    pub fn id(&self) -> OrderId {
+        self.id
+    }
+    pub fn is_pending(&self, orders: &OrderHolder) -> bool {
+        self.upgrade(orders).is_some()
+    }
+    pub fn vessel_to_sell_to(&self, orders: &OrderHolder) -> Option<VesselId> {
+        self.upgrade(orders).map(|order| order.vessel_to_sell_to())
+    }
+    pub fn seller(&self, orders: &OrderHolder) -> Option<VesselId> {
+        self.upgrade(orders).map(|order| order.seller())
+    }
+    pub fn items(&self, orders: &OrderHolder) -> Option<Vec<Item>> {
+        self.upgrade(orders).map(|order| order.items())
     }
-    pub fn items(&self) -> Option<Vec<Item>> {
-        todo!()
+    pub fn price(&self, orders: &OrderHolder) -> Option<Money> {
+        self.upgrade(orders).map(|order| order.price())
     }
-    pub fn price(&self) -> Option<Money> {
-        todo!()
+    fn upgrade<'a>(&self, orders: &'a OrderHolder) -> Option<&'a SellOrder> {
+        orders.sell_order(self.id)
     }
 }
 
+/// sell order owned by a trading terminal. `price` is reserved for the seller until the order is settled
 #[derive(Debug, Serialize, Deserialize)]
 pub struct SellOrder {
-    id: Uuid,
-    data: Rc<SellOrderImpl>,
+    id: OrderId,
+    data: SellOrderImpl,
 }
 
 impl SellOrder {
-    pub fn new() -> (Self, WeakSellOrder) {
-        todo!()
+    pub fn new(
+        id: OrderId,
+        vessel_to_sell_to: VesselId,
+        seller: VesselId,
+        items: Vec<Item>,
+        price: Money,
+        deadline: StaticTimePoint,
+    ) -> (Self, WeakSellOrder) {
+        let data = SellOrderImpl {
+            vessel_to_sell_to,
+            seller,
+            items,
+            price,
+            deadline,
+        };
+        (Self { id, data }, WeakSellOrder { id })
     }
 
+    pub fn id(&self) -> OrderId {
+        self.id
+    }
     pub fn vessel_to_sell_to(&self) -> VesselId {
-        todo!()
+        self.data.vessel_to_sell_to
+    }
+    pub fn seller(&self) -> VesselId {
+        self.data.seller
     }
     pub fn items(&self) -> Vec<Item> {
-        todo!()
+        self.data.items.clone()
     }
     pub fn price(&self) -> Money {
-        todo!()
+        self.data.price
+    }
+    pub fn deadline(&self) -> StaticTimePoint {
+        self.data.deadline
+    }
+    pub fn is_expired(&self, now: StaticTimePoint) -> bool {
+        now >= self.data.deadline
+    }
+    pub fn downgrade(&self) -> WeakSellOrder {
+        WeakSellOrder { id: self.id }
     }
 }
 
-struct OrderHolder {
-    buy_orders: BTreeMap<Uuid, Weak<BuyOrderImpl>>,
-    sell_orders: BTreeMap<Uuid, Weak<WeakSellOrder>>,
+/// pending orders of a trading terminal
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct OrderHolder {
+    buy_orders: BTreeMap<OrderId, BuyOrder>,
+    sell_orders: BTreeMap<OrderId, SellOrder>,
 }
+
+impl OrderHolder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert_buy_order(&mut self, order: BuyOrder) {
+        self.buy_orders.insert(order.id, order);
+    }
+
+    pub fn insert_sell_order(&mut self, order: SellOrder) {
+        self.sell_orders.insert(order.id, order);
+    }
+
+    /// handles held by the customer become dangling after removal
+    pub fn remove_buy_order(&mut self, id: OrderId) -> Option<BuyOrder> {
+        self.buy_orders.remove(&id)
+    }
+
+    /// handles held by the customer become dangling after removal
+    pub fn remove_sell_order(&mut self, id: OrderId) -> Option<SellOrder> {
+        self.sell_orders.remove(&id)
+    }
+
+    pub fn buy_orders(&self) -> impl Iterator<Item = &BuyOrder> {
+        self.buy_orders.values()
+    }
+
+    pub fn sell_orders(&self) -> impl Iterator<Item = &SellOrder> {
+        self.sell_orders.values()
+    }
+
+    pub fn buy_order(&self, id: OrderId) -> Option<&BuyOrder> {
+        self.buy_orders.get(&id)
+    }
+
+    pub fn sell_order(&self, id: OrderId) -> Option<&SellOrder> {
+        self.sell_orders.get(&id)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.buy_orders.is_empty() && self.sell_orders.is_empty()
+    }
+}
+
+/// delivery of `items` to the `destination` vessel before `deadline` posted on a contract board of a trading terminal.
+/// The reward is held in escrow by the terminal and, once the contract is accepted, by a sell order of the contractor.
+/// The contractor pays `penalty` as collateral on acceptance and gets it back on delivery
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Contract {
+    id: ContractId,
+    destination: VesselId,
+    items: Vec<Item>,
+    reward: Money,
+    penalty: Money,
+    deadline: StaticTimePoint,
+    #[serde(with = "crate::utils::untagged_option")]
+    contractor: Option<VesselId>,
+    /// sell order escrowing the reward after acceptance
+    #[serde(with = "crate::utils::untagged_option")]
+    order_id: Option<OrderId>,
+}
+
+impl Contract {
+    pub fn new(
+        id: ContractId,
+        destination: VesselId,
+        items: Vec<Item>,
+        reward: Money,
+        penalty: Money,
+        deadline: StaticTimePoint,
+    ) -> Self {
+        Self {
+            id,
+            destination,
+            items,
+            reward,
+            penalty,
+            deadline,
+            contractor: None,
+            order_id: None,
+        }
+    }
+
+    pub fn id(&self) -> ContractId {
+        self.id
+    }
+    pub fn destination(&self) -> VesselId {
+        self.destination
+    }
+    pub fn items(&self) -> &[Item] {
+        &self.items
+    }
+    pub fn reward(&self) -> Money {
+        self.reward
+    }
+    pub fn penalty(&self) -> Money {
+        self.penalty
+    }
+    pub fn deadline(&self) -> StaticTimePoint {
+        self.deadline
+    }
+    pub fn contractor(&self) -> Option<VesselId> {
+        self.contractor
+    }
+    pub fn order_id(&self) -> Option<OrderId> {
+        self.order_id
+    }
+    pub fn is_expired(&self, now: StaticTimePoint) -> bool {
+        now >= self.deadline
+    }
+
+    /// assigns the contractor and creates the sell order `order_id` which pays the reward on delivery.
+    /// `None` if the contract is already accepted
+    pub fn accept(&mut self, contractor: VesselId, order_id: OrderId) -> Option<SellOrder> {
+        if self.contractor.is_some() {
+            return None;
+        }
+        let (order, _) = SellOrder::new(
+            order_id,
+            self.destination,
+            contractor,
+            self.items.clone(),
+            self.reward,
+            self.deadline,
+        );
+        self.contractor = Some(contractor);
+        self.order_id = Some(order.id());
+        Some(order)
+    }
+}
+
+/// order settled between a trading terminal and a customer vessel
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Settlement {
+    /// the customer received `items` and paid `price`
+    Bought {
+        order_id: OrderId,
+        items: Vec<Item>,
+        price: Money,
+    },
+    /// the customer handed over `items` and received `price`
+    Sold {
+        order_id: OrderId,
+        items: Vec<Item>,
+        price: Money,
+    },
+    /// the contractor handed over `items` of the contract and received the reward and its collateral back
+    Delivered {
+        contract_id: ContractId,
+        order_id: OrderId,
+        items: Vec<Item>,
+        reward: Money,
+        collateral: Money,
+    },
+}
+
+/// pending order removed from a trading terminal after its deadline
+#[derive(Debug)]
+pub enum ExpiredOrder {
+    /// reserved goods are for sale again
+    Buy(BuyOrder),
+    /// reserved money went back to the till
+    Sell(SellOrder),
+}
+
+impl ExpiredOrder {
+    pub fn id(&self) -> OrderId {
+        match self {
+            ExpiredOrder::Buy(order) => order.id(),
+            ExpiredOrder::Sell(order) => order.id(),
+        }
+    }
+    pub fn customer(&self) -> VesselId {
+        match self {
+            ExpiredOrder::Buy(order) => order.buyer(),
+            ExpiredOrder::Sell(order) => order.seller(),
+        }
+    }
+}
+
+/// money held by an expired contract or order does not fit into the till.
+/// The contract or order stays at the terminal until it does
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RefundError {
+    pub id: Uuid,
+    pub error: TransferError,
+}
+
+impl Display for RefundError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Can not refund {}: {}", self.id, self.error)
+    }
+}
+
+impl Error for RefundError {}
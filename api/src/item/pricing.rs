@@ -0,0 +1,127 @@
+use crate::item::{ItemCount, Money};
+use crate::utils::utils::Float;
+use serde::{Deserialize, Serialize};
+
+/// units of an item recently traded at a terminal. Decays exponentially so old trades matter less
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct TradeVolume {
+    /// units customers bought from the terminal
+    pub bought: Float,
+    /// units customers sold to the terminal
+    pub sold: Float,
+}
+
+impl TradeVolume {
+    /// `dt` and `half_life` in seconds
+    pub fn decay(&mut self, dt: Float, half_life: Float) {
+        let factor = 0.5_f64.powf(dt / half_life);
+        self.bought *= factor;
+        self.sold *= factor;
+    }
+
+    pub fn is_negligible(&self) -> bool {
+        self.bought + self.sold < 0.01
+    }
+}
+
+/// sets offer prices of a trading terminal from the base price of an item, its stock
+/// and recent trade volume. Scarce and demanded items get pricier, overstocked ones cheaper
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PricingPolicy {
+    /// stock of an item at which its price equals the base price
+    pub target_stock: ItemCount,
+    /// max relative deviation from the base price, e.g. 0.5 keeps prices between 50% and 150% of it
+    pub elasticity: Float,
+    /// relative difference between the price the terminal sells at and the price it buys at
+    pub spread: Float,
+    /// seconds after which the weight of a trade in the recent volume halves
+    pub volume_half_life: Float,
+}
+
+impl Default for PricingPolicy {
+    fn default() -> Self {
+        Self {
+            target_stock: 100,
+            elasticity: 0.5,
+            spread: 0.2,
+            volume_half_life: 600.,
+        }
+    }
+}
+
+impl PricingPolicy {
+    /// demand pressure from -1 to 1. Positive if the item is scarce or customers buy more of it than they sell
+    pub fn pressure(&self, stock: ItemCount, volume: TradeVolume) -> Float {
+        let target = self.target_stock.max(1) as Float;
+        let scarcity = ((target - stock as Float) / target).clamp(-1., 1.);
+        let demand = ((volume.bought - volume.sold) / target).clamp(-1., 1.);
+        (scarcity + demand) / 2.
+    }
+
+    fn mid_price(&self, base_price: Money, stock: ItemCount, volume: TradeVolume) -> Float {
+        base_price as Float * (1. + self.elasticity * self.pressure(stock, volume)).max(0.)
+    }
+
+    /// price per unit customers pay the terminal. At least 1
+    pub fn sell_price(&self, base_price: Money, stock: ItemCount, volume: TradeVolume) -> Money {
+        let price = self.mid_price(base_price, stock, volume) * (1. + self.spread / 2.);
+        (price.round() as Money).max(1)
+    }
+
+    /// price per unit the terminal pays customers. Never above the sell price
+    pub fn buy_price(&self, base_price: Money, stock: ItemCount, volume: TradeVolume) -> Money {
+        let price = self.mid_price(base_price, stock, volume) * (1. - self.spread / 2.).max(0.);
+        (price.floor() as Money).min(self.sell_price(base_price, stock, volume))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PricingPolicy, TradeVolume};
+
+    #[test]
+    fn prices_follow_supply_and_demand() {
+        let policy = PricingPolicy::default();
+        let calm = TradeVolume::default();
+
+        assert_eq!(policy.sell_price(100, 100, calm), 110);
+        assert_eq!(policy.buy_price(100, 100, calm), 90);
+        // scarce items are pricier, overstocked ones cheaper
+        assert!(policy.sell_price(100, 0, calm) > policy.sell_price(100, 100, calm));
+        assert!(policy.sell_price(100, 1000, calm) < policy.sell_price(100, 100, calm));
+        assert!(policy.buy_price(100, 1000, calm) < policy.buy_price(100, 100, calm));
+
+        let rush = TradeVolume {
+            bought: 50.,
+            sold: 0.,
+        };
+        assert!(policy.sell_price(100, 100, rush) > policy.sell_price(100, 100, calm));
+
+        // elasticity bounds the deviation from the base price
+        let frenzy = TradeVolume {
+            bought: 1000.,
+            sold: 0.,
+        };
+        assert_eq!(policy.sell_price(100, 0, frenzy), 165);
+        assert!(policy.buy_price(1, 1000, calm) <= policy.sell_price(1, 1000, calm));
+        assert_eq!(policy.sell_price(0, 0, frenzy), 1);
+    }
+
+    #[test]
+    fn volume_decays() {
+        let mut volume = TradeVolume {
+            bought: 8.,
+            sold: 4.,
+        };
+        volume.decay(600., 600.);
+        assert_eq!(
+            volume,
+            TradeVolume {
+                bought: 4.,
+                sold: 2.
+            }
+        );
+        volume.decay(6000., 600.);
+        assert!(volume.is_negligible());
+    }
+}
@@ -1,9 +1,11 @@
 #![feature(map_try_insert)]
 
 pub mod environment;
+pub mod finance;
 pub mod item;
 pub mod module;
 pub mod person;
 pub mod recipe;
+pub mod research;
 pub mod utils;
 pub mod vessel;
@@ -1,13 +1,17 @@
+use crate::finance::Wallet;
 use crate::item::{
-    BuyOffer, ItemCount, ItemId, ItemStorage, Money, SellOffer, WeakBuyOrder, WeakSellOrder,
+    BuyOffer, Contract, ContractId, ExpiredOrder, Item, ItemCount, ItemId, ItemRegistry,
+    ItemStorage, Money, OrderHolder, OrderId, PricingPolicy, RefundError, SellOffer, Settlement,
+    WeakBuyOrder, WeakSellOrder,
 };
 use crate::module::module::ModuleId;
 use crate::module::{ModuleCapability, ModuleStorage, PackageId, ProcessToken};
 use crate::person::Role;
-use crate::recipe::AssemblyRecipe;
+use crate::recipe::{AssemblyRecipe, Recipe};
 use crate::utils::math::Vector;
 use crate::utils::range::Range;
-use crate::vessel::DockingClamp;
+use crate::utils::time_point::StaticTimePoint;
+use crate::vessel::{DockingClamp, VesselId};
 use std::collections::BTreeSet;
 use std::ops::Deref;
 
@@ -27,6 +31,9 @@ pub trait ModuleConsole {
     fn assembly_console(&self) -> Option<&dyn AssemblyConsole>;
     fn assembly_console_mut(&mut self) -> Option<&mut dyn AssemblyConsole>;
 
+    fn fabrication_console(&self) -> Option<&dyn FabricationConsole>;
+    fn fabrication_console_mut(&mut self) -> Option<&mut dyn FabricationConsole>;
+
     fn dockyard_console(&self) -> Option<&dyn DockyardConsole>;
     fn dockyard_console_mut(&mut self) -> Option<&mut dyn DockyardConsole>;
 
@@ -36,6 +43,9 @@ pub trait ModuleConsole {
     fn trading_admin_console(&self) -> Option<&dyn TradingAdminConsole>;
     fn trading_admin_console_mut(&mut self) -> Option<&mut dyn TradingAdminConsole>;
 
+    fn contract_board_console(&self) -> Option<&dyn ContractBoardConsole>;
+    fn contract_board_console_mut(&mut self) -> Option<&mut dyn ContractBoardConsole>;
+
     fn storages(&self) -> &[ItemStorage];
     fn storages_mut(&mut self) -> &mut [ItemStorage];
 
@@ -89,6 +99,14 @@ impl ModuleConsole for DefaultModuleConsole {
         todo!()
     }
 
+    fn fabrication_console(&self) -> Option<&dyn FabricationConsole> {
+        None
+    }
+
+    fn fabrication_console_mut(&mut self) -> Option<&mut dyn FabricationConsole> {
+        None
+    }
+
     fn dockyard_console(&self) -> Option<&dyn DockyardConsole> {
         todo!()
     }
@@ -113,6 +131,14 @@ impl ModuleConsole for DefaultModuleConsole {
         todo!()
     }
 
+    fn contract_board_console(&self) -> Option<&dyn ContractBoardConsole> {
+        None
+    }
+
+    fn contract_board_console_mut(&mut self) -> Option<&mut dyn ContractBoardConsole> {
+        None
+    }
+
     fn storages(&self) -> &[ItemStorage] {
         todo!()
     }
@@ -130,11 +156,11 @@ impl ModuleConsole for DefaultModuleConsole {
     }
 
     fn docking_clamps(&self) -> &[DockingClamp] {
-        todo!()
+        &[]
     }
 
     fn docking_clamps_mut(&mut self) -> &mut [DockingClamp] {
-        todo!()
+        &mut []
     }
 }
 
@@ -144,6 +170,8 @@ pub trait AssemblyConsole {
     // returns index in array. TODO replace with uuid
     fn recipe_by_output_capability(&self, capability: ModuleCapability) -> Option<usize>;
     fn recipe_output_capabilities(&self, index: usize) -> &[ModuleCapability];
+    /// false if the owner has not researched a tech needed to assemble the recipe output
+    fn is_recipe_unlocked(&self, index: usize) -> bool;
     // returns index in array. TODO replace with uuid
     fn has_resources_for_recipe(&self, index: usize) -> bool;
     fn active_recipe(&self) -> Option<usize>;
@@ -153,6 +181,17 @@ pub trait AssemblyConsole {
     fn recipes(&self) -> &[AssemblyRecipe];
 }
 
+pub trait FabricationConsole {
+    // returns index in array. TODO replace with uuid
+    fn recipe_by_output_item(&self, item: &ItemId) -> Option<usize>;
+    fn has_resources_for_recipe(&self, index: usize) -> bool;
+    fn active_recipe(&self) -> Option<usize>;
+    /// inputs index in array. TODO replace with uuid
+    /// consumes the recipe input from this module storage, the output is put to the same storage
+    fn start(&mut self, index: usize) -> Option<ProcessToken>;
+    fn recipes(&self) -> &[Recipe];
+}
+
 pub trait DockyardConsole {
     fn start(&mut self, modules: BTreeSet<ModuleId>) -> Option<ProcessToken>;
 }
@@ -160,8 +199,53 @@ pub trait DockyardConsole {
 pub trait TradingConsole {
     fn buy_offers(&self) -> &[BuyOffer];
     fn sell_offers(&self) -> &[SellOffer];
-    fn place_buy_order(&mut self, offer: &BuyOffer, count: ItemCount) -> Option<WeakBuyOrder>;
-    fn place_sell_order(&mut self, offer: &SellOffer, count: ItemCount) -> Option<WeakSellOrder>;
+    /// reserves `count` offered items for the buyer vessel with order `id` until `deadline`. `None` if the count
+    /// is out of the offer range, the offer is gone or there are not enough goods in stock
+    fn place_buy_order(
+        &mut self,
+        id: OrderId,
+        offer: &BuyOffer,
+        count: ItemCount,
+        buyer: VesselId,
+        deadline: StaticTimePoint,
+    ) -> Option<WeakBuyOrder>;
+    /// reserves money from `till` for `count` items the seller vessel will bring with order `id` until `deadline`.
+    /// `None` if the count is out of the offer range, the offer is gone or the till can not afford it
+    fn place_sell_order(
+        &mut self,
+        id: OrderId,
+        offer: &SellOffer,
+        count: ItemCount,
+        seller: VesselId,
+        deadline: StaticTimePoint,
+        till: &mut Wallet,
+    ) -> Option<WeakSellOrder>;
+    /// returns reserved goods back to the terminal or reserved money back to `till`. False if there is
+    /// no such pending order or the order holds the reward of an accepted contract
+    fn cancel_order(&mut self, id: OrderId, till: &mut Wallet) -> bool;
+    /// cancels orders with passed deadline except the ones holding the reward of a contract,
+    /// those expire with the contract. Sell orders whose money does not fit into `till` stay pending
+    fn expire_orders(
+        &mut self,
+        now: StaticTimePoint,
+        till: &mut Wallet,
+    ) -> Vec<Result<ExpiredOrder, RefundError>>;
+    /// orders placed by the customer vessel which are not settled yet
+    fn pending_orders(&self, customer: VesselId) -> Vec<OrderId>;
+    /// all pending orders. Resolves handles returned by `place_buy_order` and `place_sell_order`
+    fn orders(&self) -> &OrderHolder;
+    /// settles pending orders of the customer vessel which is present at the terminal:
+    /// bought items are put to `customer_storages` and paid from `customer_wallet` to `till`,
+    /// sold items are taken from the storages and paid to the wallet with money reserved by the order.
+    /// Nothing is taken from `till`. Orders which can not be settled yet stay pending
+    fn settle(
+        &mut self,
+        customer: VesselId,
+        customer_storages: &mut [&mut ItemStorage],
+        customer_wallet: &mut Wallet,
+        till: &mut Wallet,
+        item_registry: &ItemRegistry,
+    ) -> Vec<Settlement>;
 }
 
 pub trait TradingAdminConsole {
@@ -177,6 +261,43 @@ pub trait TradingAdminConsole {
         count_range: Range<ItemCount>,
         price_per_unit: Money,
     ) -> Option<&SellOffer>;
+    /// with a policy set, prices of all offers are replaced each tick with ones
+    /// derived from base item prices, stock and recent trade volume. `None` keeps prices as placed
+    fn set_pricing_policy(&mut self, policy: Option<PricingPolicy>);
+    fn pricing_policy(&self) -> Option<&PricingPolicy>;
+}
+
+/// delivery contracts posted by a trading terminal. Deliveries are settled together with orders of the terminal
+pub trait ContractBoardConsole {
+    fn contracts(&self) -> &[Contract];
+    /// posts contract `id` and takes the reward from `till` into escrow. `None` if there are no items
+    /// or the till can not afford the reward
+    fn post_contract(
+        &mut self,
+        id: ContractId,
+        items: Vec<Item>,
+        reward: Money,
+        penalty: Money,
+        deadline: StaticTimePoint,
+        till: &mut Wallet,
+    ) -> Option<&Contract>;
+    /// takes the penalty from `contractor_wallet` as collateral and places sell order `order_id` of the contract items
+    /// for the contractor vessel. `None` if the contract is gone, already accepted or the collateral can not be paid
+    fn accept_contract(
+        &mut self,
+        id: ContractId,
+        order_id: OrderId,
+        contractor: VesselId,
+        contractor_wallet: &mut Wallet,
+    ) -> Option<OrderId>;
+    /// removes contracts with passed deadline. The reward goes back to `till`,
+    /// collateral of the contractor goes there as well as the penalty.
+    /// Contracts whose refund does not fit into `till` stay on the board
+    fn expire_contracts(
+        &mut self,
+        now: StaticTimePoint,
+        till: &mut Wallet,
+    ) -> Vec<Result<Contract, RefundError>>;
 }
 
 pub(crate) trait CaptainControlPanel {
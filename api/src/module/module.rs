@@ -1,10 +1,15 @@
-use crate::item::ItemStorage;
-use crate::module::{ModuleCapability, ModuleStorage, ProcessTokenContext, TradingConsole};
+use crate::environment::{MiningLaserSpec, SimulationClock};
+use crate::item::{ItemRegistry, ItemStorage};
+use crate::module::{
+    ContractBoardConsole, ModuleCapability, ModuleStorage, ProcessTokenContext, TradingConsole,
+};
 use crate::person::{Logger, ObjectiveDeciderVault, Person, PersonId};
 use crate::recipe::{AssemblyRecipe, Recipe};
-use crate::vessel::{DockingClamp, VesselModuleInterface};
+use crate::utils::utils::Float;
+use crate::vessel::{DockingClamp, RadarSpec, VesselModuleInterface};
 use dyn_serde::DynSerialize;
 use dyn_serde_macro::dyn_serde_trait;
+use rand::RngCore;
 use std::fmt::Debug;
 use uuid::Uuid;
 
@@ -22,21 +27,42 @@ pub trait Module: Debug + DynSerialize {
     fn proceed(
         &mut self,
         this_vessel: &dyn VesselModuleInterface,
+        rng: &mut dyn RngCore,
+        clock: &SimulationClock,
+        item_registry: &ItemRegistry,
         process_token_context: &ProcessTokenContext,
         decider_vault: &ObjectiveDeciderVault,
         logger: &mut dyn Logger,
     );
 
+    /// engine. Thrust in newtons, 0 if the module is not an engine
+    fn thrust(&self) -> Float;
+    /// engine. Fuel energy burned per meter flown under thrust, in kilojoules
+    fn engine_energy_per_meter(&self) -> Float;
+
+    /// power. Power needed for the module to work, in kilowatts.
+    /// Modules which draw power are not proceeded while the vessel is dark
+    fn power_draw(&self) -> Float;
+    /// power. Max power a reactor produces from fuel, in kilowatts. 0 if the module is not a reactor
+    fn power_output(&self) -> Float;
+
+    /// radar. `None` if the module is not a radar
+    fn radar(&self) -> Option<RadarSpec>;
+    /// mining. `None` if the module is not a mining laser
+    fn mining_laser(&self) -> Option<MiningLaserSpec>;
+
     /// crafting
-    fn recipes(&self) -> Vec<Recipe>;
+    fn recipes(&self) -> &[Recipe];
     /// assembly
     fn assembly_recipes(&self) -> &[AssemblyRecipe];
 
     /// persons
     fn extract_person(&mut self, id: PersonId) -> Option<Person>;
-    fn insert_person(&mut self, person: Person) -> bool;
+    /// gives the person back if there is no room for it
+    fn insert_person(&mut self, person: Person) -> Result<(), Person>;
     fn can_insert_person(&self) -> bool;
     fn contains_person(&self, id: PersonId) -> bool;
+    fn person_ids(&self) -> Vec<PersonId>;
 
     /// storage
     fn storages(&self) -> &[ItemStorage];
@@ -46,9 +72,13 @@ pub trait Module: Debug + DynSerialize {
     fn module_storages_mut(&mut self) -> &mut [ModuleStorage];
 
     fn docking_clamps(&self) -> &[DockingClamp];
+    fn docking_clamps_mut(&mut self) -> &mut [DockingClamp];
 
     fn trading_console(&self) -> Option<&dyn TradingConsole>;
     fn trading_console_mut(&mut self) -> Option<&mut dyn TradingConsole>;
+
+    fn contract_board_console(&self) -> Option<&dyn ContractBoardConsole>;
+    fn contract_board_console_mut(&mut self) -> Option<&mut dyn ContractBoardConsole>;
 }
 
 dyn_serde_trait!(Module, ModuleSeed);
@@ -8,17 +8,21 @@ pub enum ModuleCapability {
     ItemStorage,
     Dockyard,
     Crafting,
+    Fabrication,
     PersonnelRoom,
     DockingClamp,
     TradingTerminal,
+    ContractBoard,
 
     Cockpit,
     FuelTank,
     Radar,
     Engine,
     Weapon,
+    MiningLaser,
     WarpDrive,
     Reactor,
+    ResearchLab,
 }
 
 pub trait ConcatModuleCapabilities<Rhs> {
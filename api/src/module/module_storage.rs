@@ -4,23 +4,42 @@ use dyn_serde_macro::DeserializeSeedXXX;
 use serde::Serialize;
 use std::ops::{Deref, DerefMut};
 
-#[derive(Debug, Serialize, DeserializeSeedXXX, Default)]
+#[derive(Debug, Serialize, DeserializeSeedXXX)]
 #[deserialize_seed_xxx(seed = crate::module::ModuleStorageSeed::<'v>)]
 pub struct ModuleStorage {
     #[deserialize_seed_xxx(seed = self.seed.content_seed)]
     content: Vec<Box<dyn Module>>,
+    /// max count of stored modules. Each module takes one slot
+    #[deserialize_seed_xxx(default = self.seed.capacity)]
+    capacity: usize,
 }
 
 impl ModuleStorage {
-    pub(crate) fn new() -> Self {
+    pub fn new(capacity: usize) -> Self {
         Self {
             content: Vec::new(),
+            capacity,
         }
     }
 
-    /// TODO: add capacity
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn len(&self) -> usize {
+        self.content.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.content.is_empty()
+    }
+
+    pub fn free_slots(&self) -> usize {
+        self.capacity.saturating_sub(self.content.len())
+    }
+
     pub fn has_space(&self) -> bool {
-        true
+        self.free_slots() > 0
     }
 
     pub fn add(&mut self, module: Box<dyn Module>) -> bool {
@@ -89,12 +108,15 @@ impl ModuleStorage {
 #[derive(Clone)]
 pub struct ModuleStorageSeed<'v> {
     content_seed: VecSeed<ModuleSeed<'v>>,
+    /// capacity of storages from saves which predate it. The owning module knows it
+    capacity: usize,
 }
 
 impl<'v> ModuleStorageSeed<'v> {
-    pub fn new(vault: &'v DynDeserializeSeedVault<dyn Module>) -> Self {
+    pub fn new(vault: &'v DynDeserializeSeedVault<dyn Module>, capacity: usize) -> Self {
         Self {
             content_seed: VecSeed::new(ModuleSeed::new(vault)),
+            capacity,
         }
     }
 }
@@ -9,10 +9,18 @@ use std::ops::Deref;
 use std::rc::{Rc, Weak};
 use uuid::Uuid;
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ProcessStatus {
+    InProgress,
+    Completed,
+    /// the process was aborted by the module. The result will never be produced
+    Failed,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ProcessToken {
     #[serde(skip)]
-    completed: Option<Weak<RefCell<bool>>>,
+    status: Option<Weak<RefCell<ProcessStatus>>>,
     id: Uuid,
 }
 
@@ -21,13 +29,27 @@ impl ProcessToken {
         &mut self,
         context: &ProcessTokenContext,
     ) -> Result<bool, ProcessTokenExpiredError> {
-        match &self.completed {
+        Ok(self.status(context)? == ProcessStatus::Completed)
+    }
+
+    pub fn is_failed(
+        &mut self,
+        context: &ProcessTokenContext,
+    ) -> Result<bool, ProcessTokenExpiredError> {
+        Ok(self.status(context)? == ProcessStatus::Failed)
+    }
+
+    pub fn status(
+        &mut self,
+        context: &ProcessTokenContext,
+    ) -> Result<ProcessStatus, ProcessTokenExpiredError> {
+        match &self.status {
             None => {
                 let data = context.data.borrow();
 
                 let weak = data.get(&self.id).ok_or(ProcessTokenExpiredError)?;
 
-                self.completed = Some(weak.clone());
+                self.status = Some(weak.clone());
                 match weak.upgrade() {
                     None => {
                         context.data.borrow_mut().remove(&self.id);
@@ -36,7 +58,7 @@ impl ProcessToken {
                     Some(rc) => Ok(*rc.borrow()),
                 }
             }
-            Some(completed) => match completed.upgrade() {
+            Some(status) => match status.upgrade() {
                 None => Err(ProcessTokenExpiredError),
                 Some(rc) => Ok(*rc.borrow()),
             },
@@ -46,7 +68,7 @@ impl ProcessToken {
 
 #[derive(Debug, Serialize)]
 pub struct ProcessTokenMut {
-    completed: Rc<RefCell<bool>>,
+    status: Rc<RefCell<ProcessStatus>>,
     id: Uuid,
 }
 
@@ -70,30 +92,34 @@ impl<'de, 'context> DeserializeSeed<'de> for ProcessTokenMutSeed<'context> {
     {
         #[derive(Deserialize)]
         struct Impl {
-            completed: bool,
+            status: ProcessStatus,
             id: Uuid,
         }
 
-        let Impl { completed, id } = Impl::deserialize(deserializer)?;
-        Ok(self.context.register(completed, id))
+        let Impl { status, id } = Impl::deserialize(deserializer)?;
+        Ok(self.context.register(status, id))
     }
 }
 
 impl ProcessTokenMut {
-    pub fn new() -> (ProcessToken, Self) {
-        let completed = Rc::new(RefCell::new(false));
-        let id = Uuid::new_v4();
+    /// `id` is drawn from the simulation rng so that a replayed simulation produces the same tokens
+    pub fn new(id: Uuid) -> (ProcessToken, Self) {
+        let status = Rc::new(RefCell::new(ProcessStatus::InProgress));
         (
             ProcessToken {
-                completed: Some(Rc::downgrade(&completed)),
+                status: Some(Rc::downgrade(&status)),
                 id,
             },
-            Self { completed, id },
+            Self { status, id },
         )
     }
 
     pub fn mark_completed(&mut self, context: &ProcessTokenContext) {
-        *self.completed.borrow_mut() = true
+        *self.status.borrow_mut() = ProcessStatus::Completed
+    }
+
+    pub fn mark_failed(&mut self, context: &ProcessTokenContext) {
+        *self.status.borrow_mut() = ProcessStatus::Failed
     }
 }
 
@@ -109,7 +135,7 @@ impl Display for ProcessTokenExpiredError {
 impl Error for ProcessTokenExpiredError {}
 
 pub struct ProcessTokenContext {
-    data: RefCell<BTreeMap<Uuid, Weak<RefCell<bool>>>>,
+    data: RefCell<BTreeMap<Uuid, Weak<RefCell<ProcessStatus>>>>,
 }
 
 impl ProcessTokenContext {
@@ -119,12 +145,32 @@ impl ProcessTokenContext {
         }
     }
 
-    fn register(&self, completed: bool, id: Uuid) -> ProcessTokenMut {
-        let completed = Rc::new(RefCell::new(completed));
+    fn register(&self, status: ProcessStatus, id: Uuid) -> ProcessTokenMut {
+        let status = Rc::new(RefCell::new(status));
         self.data
             .borrow_mut()
-            .try_insert(id, Rc::downgrade(&completed))
+            .try_insert(id, Rc::downgrade(&status))
             .unwrap();
-        ProcessTokenMut { completed, id }
+        ProcessTokenMut { status, id }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ProcessStatus, ProcessTokenContext, ProcessTokenMut};
+    use uuid::Uuid;
+
+    #[test]
+    fn status() {
+        let context = ProcessTokenContext::new();
+        let (mut token, mut token_mut) = ProcessTokenMut::new(Uuid::nil());
+        assert_eq!(token.status(&context).unwrap(), ProcessStatus::InProgress);
+
+        token_mut.mark_failed(&context);
+        assert!(token.is_failed(&context).unwrap());
+        assert!(!token.is_completed(&context).unwrap());
+
+        drop(token_mut);
+        assert!(token.status(&context).is_err());
     }
 }
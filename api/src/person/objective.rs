@@ -1,6 +1,6 @@
 use crate::module::{ModuleConsole, ProcessTokenContext};
 use crate::person::{Awareness, Boldness, Gender, Morale, Passion, PersonId};
-use crate::vessel::VesselConsole;
+use crate::vessel::{VesselConsole, VesselRequestError};
 use dyn_serde::DynSerialize;
 use dyn_serde_macro::dyn_serde_trait;
 use rand::Rng;
@@ -25,6 +25,12 @@ pub trait Objective {
         process_token_context: &ProcessTokenContext,
         logger: PersonLogger,
     ) -> Result<ObjectiveStatus, Self::Error>;
+
+    /// called before `pursue` for every request of the person the vessel failed to process
+    /// during the last tick. The objective fails if an error is returned
+    fn on_request_error(&mut self, _error: &VesselRequestError) -> Result<(), Self::Error> {
+        Ok(())
+    }
 }
 
 pub trait DynObjective: Debug + DynSerialize {
@@ -35,6 +41,8 @@ pub trait DynObjective: Debug + DynSerialize {
         process_token_context: &ProcessTokenContext,
         logger: PersonLogger,
     ) -> Result<ObjectiveStatus, Box<dyn Error>>;
+
+    fn on_request_error(&mut self, error: &VesselRequestError) -> Result<(), Box<dyn Error>>;
 }
 
 dyn_serde_trait!(DynObjective, ObjectiveSeed);
@@ -51,6 +59,10 @@ impl<T: Objective + Debug + DynSerialize> DynObjective for T {
             .pursue(this_module, this_vessel, process_token_context, logger)
             .map_err(|e| Box::new(e))?)
     }
+
+    fn on_request_error(&mut self, error: &VesselRequestError) -> Result<(), Box<dyn Error>> {
+        Ok(Objective::on_request_error(self, error)?)
+    }
 }
 
 pub trait ObjectiveDecider {
@@ -76,7 +88,7 @@ impl ObjectiveDeciderVault {
         Self { data: Vec::new() }
     }
 
-    pub fn decide<R: Rng>(
+    pub fn decide<R: Rng + ?Sized>(
         &self,
         rng: &mut R,
         person_id: PersonId,
@@ -13,11 +13,10 @@ use rand::prelude::{Distribution, IndexedRandom, IteratorRandom, SliceRandom};
 use serde::de::DeserializeSeed;
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeSet;
-use std::error::Error;
 use uuid::Uuid;
-use crate::person::logger::{Logger, PersonLogger};
+use crate::person::logger::{Logger, PersonLogger, Severity};
 
-fn random_name<R: Rng>(rng: &mut R, gender: Gender) -> String {
+fn random_name<R: Rng + ?Sized>(rng: &mut R, gender: Gender) -> String {
     let male_names = [
         "Tyler",
         "Yurem",
@@ -235,10 +234,10 @@ impl Person {
         self.id
     }
 
-    pub fn random<R: Rng>(rng: &mut R) -> Self {
+    pub fn random<R: Rng + ?Sized>(rng: &mut R) -> Self {
         let gender = rng.random();
         Self {
-            id: Uuid::new_v4(),
+            id: uuid::Builder::from_random_bytes(rng.random()).into_uuid(),
             name: random_name(rng, gender),
             age: rng.random_range(15..=80),
             gender,
@@ -254,7 +253,7 @@ impl Person {
         }
     }
 
-    pub fn proceed<R: Rng>(
+    pub fn proceed<R: Rng + ?Sized>(
         &mut self,
         rng: &mut R,
         this_module: &mut dyn ModuleConsole,
@@ -281,13 +280,26 @@ impl Person {
                 )
             }
             Some(objective) => {
-                match objective.pursue(this_module, this_vessel, process_token_context, PersonLogger::new(&self.id, logger)) {
+                let status = this_vessel
+                    .request_errors(self.id)
+                    .iter()
+                    .try_for_each(|error| objective.on_request_error(error))
+                    .and_then(|_| {
+                        objective.pursue(
+                            this_module,
+                            this_vessel,
+                            process_token_context,
+                            PersonLogger::new(&self.id, logger),
+                        )
+                    });
+                match status {
                     Ok(ObjectiveStatus::InProgress) => {}
                     Ok(ObjectiveStatus::Done) => self.objective = None,
                     Err(err) => {
-                        eprintln!(
-                            "Objective performed by person {} ({}) failed: {}",
-                            self.id, self.name, err
+                        logger.log(
+                            &self.id,
+                            Severity::Error,
+                            format!("Objective performed by {} failed: {}", self.name, err),
                         );
                         self.objective = None
                     }
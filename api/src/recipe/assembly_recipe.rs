@@ -2,13 +2,15 @@ use crate::module::{Module, ModuleCapability, ModuleTypeId};
 use crate::recipe::InputRecipe;
 use dyn_serde::{DynDeserializeSeedVault, DynSerialize};
 use dyn_serde_macro::{DeserializeSeedXXX, dyn_serde_trait};
+use rand::RngCore;
 use serde::Serialize;
 use std::fmt::Debug;
 use std::rc::Rc;
+use std::time::Duration;
 
 pub trait ModuleFactory: Debug + DynSerialize {
     fn output_type_id(&self) -> ModuleTypeId;
-    fn create(&self, recipe: &InputRecipe) -> Box<dyn Module>;
+    fn create(&self, recipe: &InputRecipe, rng: &mut dyn RngCore) -> Box<dyn Module>;
     fn output_capabilities(&self) -> &[ModuleCapability];
 }
 
@@ -20,6 +22,8 @@ pub struct AssemblyRecipe {
     input: InputRecipe,
     #[deserialize_seed_xxx(seed = self.seed.module_factory_seed)]
     output: Rc<dyn ModuleFactory>,
+    /// operator work time needed to assemble the output
+    duration: Duration,
 }
 
 #[derive(Clone)]
@@ -36,16 +40,26 @@ impl<'v> AssemblyRecipeSeed<'v> {
 }
 
 impl AssemblyRecipe {
-    pub fn new(input: InputRecipe, output: Rc<dyn ModuleFactory>) -> Self {
-        Self { input, output }
+    pub fn new(input: InputRecipe, output: Rc<dyn ModuleFactory>, duration: Duration) -> Self {
+        Self {
+            input,
+            output,
+            duration,
+        }
     }
     pub fn input(&self) -> &InputRecipe {
         &self.input
     }
-    pub fn create(&self) -> Box<dyn Module> {
-        self.output.create(&self.input)
+    pub fn duration(&self) -> Duration {
+        self.duration
+    }
+    pub fn create(&self, rng: &mut dyn RngCore) -> Box<dyn Module> {
+        self.output.create(&self.input, rng)
     }
     pub fn output_capabilities(&self) -> &[ModuleCapability] {
         self.output.output_capabilities()
     }
+    pub fn output_type_id(&self) -> ModuleTypeId {
+        self.output.output_type_id()
+    }
 }
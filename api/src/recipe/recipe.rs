@@ -1,10 +1,33 @@
 use crate::item::{DuplicateItemError, Item, ItemCount, ItemId};
 use serde::{Deserialize, Serialize};
 use std::collections::{BTreeMap, btree_map};
+use std::time::Duration;
 
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Recipe {
-    input: Vec<Item>,
-    output: Vec<Item>,
+    input: InputRecipe,
+    output: OutputRecipe,
+    /// operator work time needed to produce the output
+    duration: Duration,
+}
+
+impl Recipe {
+    pub fn new(input: InputRecipe, output: OutputRecipe, duration: Duration) -> Self {
+        Self {
+            input,
+            output,
+            duration,
+        }
+    }
+    pub fn input(&self) -> &InputRecipe {
+        &self.input
+    }
+    pub fn output(&self) -> &OutputRecipe {
+        &self.output
+    }
+    pub fn duration(&self) -> Duration {
+        self.duration
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -13,6 +36,14 @@ pub struct InputRecipe {
     input: BTreeMap<ItemId, ItemCount>,
 }
 
+impl InputRecipe {
+    pub fn iter(&self) -> impl Iterator<Item = Item> {
+        self.input
+            .iter()
+            .map(|(id, count)| Item::new(id.clone(), *count))
+    }
+}
+
 pub struct InputRecipeIntoIter {
     i: btree_map::IntoIter<ItemId, ItemCount>,
 }
@@ -43,18 +74,72 @@ impl TryFrom<Vec<Item>> for InputRecipe {
     type Error = DuplicateItemError;
 
     fn try_from(value: Vec<Item>) -> Result<Self, Self::Error> {
-        todo!()
+        Ok(Self {
+            input: items_to_map(value)?,
+        })
     }
 }
 
-pub(crate) struct OutputRecipe {
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct OutputRecipe {
+    #[serde(flatten)]
     output: BTreeMap<ItemId, ItemCount>,
 }
 
+impl OutputRecipe {
+    pub fn contains(&self, item_id: &ItemId) -> bool {
+        self.output.contains_key(item_id)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = Item> {
+        self.output
+            .iter()
+            .map(|(id, count)| Item::new(id.clone(), *count))
+    }
+}
+
+pub struct OutputRecipeIntoIter {
+    i: btree_map::IntoIter<ItemId, ItemCount>,
+}
+
+impl Iterator for OutputRecipeIntoIter {
+    type Item = Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.i.next() {
+            None => None,
+            Some((id, count)) => Some(Item::new(id, count)),
+        }
+    }
+}
+
+impl IntoIterator for OutputRecipe {
+    type Item = Item;
+    type IntoIter = OutputRecipeIntoIter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        OutputRecipeIntoIter {
+            i: self.output.into_iter(),
+        }
+    }
+}
+
 impl TryFrom<Vec<Item>> for OutputRecipe {
     type Error = DuplicateItemError;
 
     fn try_from(value: Vec<Item>) -> Result<Self, Self::Error> {
-        todo!()
+        Ok(Self {
+            output: items_to_map(value)?,
+        })
+    }
+}
+
+fn items_to_map(items: Vec<Item>) -> Result<BTreeMap<ItemId, ItemCount>, DuplicateItemError> {
+    let mut result = BTreeMap::new();
+    for item in items {
+        result
+            .try_insert(item.id, item.count)
+            .map_err(|_| DuplicateItemError)?;
     }
+    Ok(result)
 }
@@ -0,0 +1,5 @@
+mod tech_tree;
+pub use tech_tree::*;
+
+mod research;
+pub use research::*;
@@ -0,0 +1,88 @@
+use crate::item::ItemCount;
+use crate::research::{Tech, TechId, TechTree};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+
+/// research progress of one owner
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct Research {
+    /// research data processed by labs and not yet spent on techs
+    data: ItemCount,
+    researched: BTreeSet<TechId>,
+}
+
+impl Research {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn data(&self) -> ItemCount {
+        self.data
+    }
+
+    pub fn researched(&self) -> &BTreeSet<TechId> {
+        &self.researched
+    }
+
+    pub fn is_researched(&self, id: &TechId) -> bool {
+        self.researched.contains(id)
+    }
+
+    /// adds processed data and researches techs in tree order while there is enough data.
+    /// Returns newly researched techs
+    pub(crate) fn add_data<'t>(&mut self, count: ItemCount, tree: &'t TechTree) -> Vec<&'t Tech> {
+        self.data = self.data.saturating_add(count);
+        let mut result = Vec::new();
+        for tech in tree.iter() {
+            if self.researched.contains(tech.id())
+                || !tech
+                    .requires()
+                    .iter()
+                    .all(|id| self.researched.contains(id))
+                || self.data < tech.cost()
+            {
+                continue;
+            }
+            self.data -= tech.cost();
+            self.researched.insert(tech.id().clone());
+            result.push(tech);
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Research;
+    use crate::research::{Tech, TechTree};
+
+    #[test]
+    fn researches_in_tree_order() {
+        let tech = |id: &str, cost, requires: &[&str]| {
+            Tech::new(
+                id.to_string(),
+                id.to_string(),
+                cost,
+                requires.iter().map(|id| id.to_string()).collect(),
+                vec![],
+            )
+        };
+        let tree = TechTree::new()
+            .with(tech("a", 10, &[]))
+            .with(tech("b", 5, &["a"]))
+            .with(tech("c", 100, &[]));
+
+        let mut research = Research::new();
+        assert!(research.add_data(8, &tree).is_empty());
+        let researched = research.add_data(9, &tree);
+        assert_eq!(
+            researched
+                .iter()
+                .map(|tech| tech.id().as_str())
+                .collect::<Vec<_>>(),
+            ["a", "b"]
+        );
+        assert_eq!(research.data(), 2);
+        assert!(!research.is_researched(&"c".to_string()));
+    }
+}
@@ -0,0 +1,201 @@
+use crate::item::ItemCount;
+use crate::module::ModuleTypeId;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::fmt::{Display, Formatter};
+
+pub type TechId = String;
+
+/// item which research labs process into research progress
+pub static RESEARCH_DATA_ITEM_ID: &str = "research_data";
+
+/// technology which allows to assemble new module types once researched
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Tech {
+    id: TechId,
+    name: String,
+    /// units of research data needed to research the tech
+    cost: ItemCount,
+    /// techs which must be researched first
+    #[serde(default)]
+    requires: Vec<TechId>,
+    /// module types which can not be assembled until the tech is researched
+    unlocks: Vec<ModuleTypeId>,
+}
+
+impl Tech {
+    pub fn new(
+        id: TechId,
+        name: String,
+        cost: ItemCount,
+        requires: Vec<TechId>,
+        unlocks: Vec<ModuleTypeId>,
+    ) -> Self {
+        Self {
+            id,
+            name,
+            cost,
+            requires,
+            unlocks,
+        }
+    }
+
+    pub fn id(&self) -> &TechId {
+        &self.id
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn cost(&self) -> ItemCount {
+        self.cost
+    }
+
+    pub fn requires(&self) -> &[TechId] {
+        &self.requires
+    }
+
+    pub fn unlocks(&self) -> &[ModuleTypeId] {
+        &self.unlocks
+    }
+}
+
+#[derive(Debug)]
+pub enum TechTreeError {
+    Ron(ron::error::SpannedError),
+    DuplicateTech(TechId),
+    UnknownRequirement { tech: TechId, requirement: TechId },
+}
+
+impl Display for TechTreeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TechTreeError::Ron(err) => write!(f, "Invalid tech data: {}", err),
+            TechTreeError::DuplicateTech(id) => write!(f, "Tech `{}` is defined twice", id),
+            TechTreeError::UnknownRequirement { tech, requirement } => write!(
+                f,
+                "Tech `{}` requires `{}` which is not defined before it",
+                tech, requirement
+            ),
+        }
+    }
+}
+
+impl std::error::Error for TechTreeError {}
+
+/// all technologies known to the simulation in research order.
+/// A tech can only require techs defined before it, so the tree has no cycles
+#[derive(Debug, Default)]
+pub struct TechTree {
+    techs: Vec<Tech>,
+}
+
+impl TechTree {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// panics if the tech can not be inserted
+    pub fn with(mut self, tech: Tech) -> Self {
+        if let Err(err) = self.insert(tech) {
+            panic!("{}", err)
+        }
+        self
+    }
+
+    pub fn insert(&mut self, tech: Tech) -> Result<(), TechTreeError> {
+        if self.get(&tech.id).is_some() {
+            return Err(TechTreeError::DuplicateTech(tech.id));
+        }
+        if let Some(requirement) = tech.requires.iter().find(|id| self.get(id).is_none()) {
+            return Err(TechTreeError::UnknownRequirement {
+                tech: tech.id.clone(),
+                requirement: requirement.clone(),
+            });
+        }
+        self.techs.push(tech);
+        Ok(())
+    }
+
+    pub fn extend(&mut self, techs: impl IntoIterator<Item = Tech>) -> Result<(), TechTreeError> {
+        for tech in techs {
+            self.insert(tech)?;
+        }
+        Ok(())
+    }
+
+    pub fn extend_from_ron(&mut self, str: &str) -> Result<(), TechTreeError> {
+        let techs: Vec<Tech> = ron::from_str(str).map_err(TechTreeError::Ron)?;
+        self.extend(techs)
+    }
+
+    pub fn get(&self, id: &TechId) -> Option<&Tech> {
+        self.techs.iter().find(|tech| &tech.id == id)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Tech> {
+        self.techs.iter()
+    }
+
+    /// module types which are unlocked by some tech, but by none of the researched ones
+    pub fn locked_modules(&self, researched: &BTreeSet<TechId>) -> BTreeSet<ModuleTypeId> {
+        let unlocked: BTreeSet<&ModuleTypeId> = self
+            .techs
+            .iter()
+            .filter(|tech| researched.contains(&tech.id))
+            .flat_map(|tech| tech.unlocks.iter())
+            .collect();
+        self.techs
+            .iter()
+            .flat_map(|tech| tech.unlocks.iter())
+            .filter(|module_type| !unlocked.contains(module_type))
+            .cloned()
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Tech, TechTree, TechTreeError};
+    use std::collections::BTreeSet;
+
+    #[test]
+    fn extend_from_ron() {
+        let mut tree = TechTree::new();
+        tree.extend_from_ron(
+            r#"[
+                (id: "a", name: "A", cost: 10, unlocks: ["X"]),
+                (id: "b", name: "B", cost: 20, requires: ["a"], unlocks: ["Y", "Z"]),
+            ]"#,
+        )
+        .unwrap();
+        assert_eq!(tree.get(&"b".to_string()).unwrap().requires(), ["a"]);
+
+        assert!(matches!(
+            tree.insert(Tech::new(
+                "a".to_string(),
+                "A".to_string(),
+                1,
+                vec![],
+                vec![]
+            )),
+            Err(TechTreeError::DuplicateTech(_))
+        ));
+        assert!(matches!(
+            tree.insert(Tech::new(
+                "c".to_string(),
+                "C".to_string(),
+                1,
+                vec!["d".to_string()],
+                vec![]
+            )),
+            Err(TechTreeError::UnknownRequirement { .. })
+        ));
+
+        assert_eq!(
+            tree.locked_modules(&BTreeSet::from(["a".to_string()])),
+            BTreeSet::from(["Y".to_string(), "Z".to_string()])
+        );
+    }
+}
@@ -1,4 +1,4 @@
-use std::ops::Sub;
+use std::ops::{Add, Sub};
 
 use serde::{Deserialize, Serialize};
 
@@ -38,6 +38,17 @@ impl<T: Sub> Sub for Point<T> {
     }
 }
 
+impl<T: Add> Add<Vector<T>> for Point<T> {
+    type Output = Point<<T as Add>::Output>;
+    fn add(self, rhs: Vector<T>) -> Self::Output {
+        let (x, y) = rhs.into();
+        Point {
+            x: self.x + x,
+            y: self.y + y,
+        }
+    }
+}
+
 impl<T> Point<T> {
     pub fn origin() -> Self
     where
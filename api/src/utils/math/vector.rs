@@ -1,8 +1,10 @@
-use std::ops::Add;
+use std::ops::{Add, Mul};
 
-use super::{Abs, Angle, Atan2, Sqr, Sqrt};
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Copy)]
+use super::{Abs, Angle, Atan2, Cos, Sin, Sqr, Sqrt};
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub struct Vector<T> {
     x: T,
     y: T,
@@ -37,7 +39,32 @@ where
     }
 }
 
+impl<T> Mul<T> for Vector<T>
+where
+    T: Mul + Clone,
+{
+    type Output = Vector<<T as Mul>::Output>;
+
+    fn mul(self, rhs: T) -> Self::Output {
+        Self::Output {
+            x: self.x * rhs.clone(),
+            y: self.y * rhs,
+        }
+    }
+}
+
 impl<T> Vector<T> {
+    /// unit vector pointing in the direction of the angle
+    pub fn from_angle(angle: Angle<T>) -> Self
+    where
+        T: Cos<Output = T> + Sin<Output = T> + Clone,
+    {
+        Self {
+            x: angle.clone().cos(),
+            y: angle.sin(),
+        }
+    }
+
     pub fn len(self) -> <<<T as Sqr>::Output as Add>::Output as Sqrt>::Output
     where
         T: Sqr,
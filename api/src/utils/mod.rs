@@ -1,6 +1,7 @@
 pub mod color;
 pub mod math;
 pub mod range;
+pub mod simulation_rng;
 pub mod tagged_option;
 pub mod time_point;
 pub mod untagged_option;
@@ -1,5 +1,5 @@
 use std::{
-    ops::AddAssign,
+    ops::{Add, AddAssign},
     time::{Duration, Instant},
 };
 
@@ -16,7 +16,7 @@ impl TimePoint for Instant {
 }
 
 // nanoseconds stored in u64 can be max ~ 600 years
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub struct StaticTimePoint(u64);
 
 impl Default for StaticTimePoint {
@@ -25,6 +25,15 @@ impl Default for StaticTimePoint {
     }
 }
 
+impl Add<Duration> for StaticTimePoint {
+    type Output = Self;
+
+    fn add(mut self, rhs: Duration) -> Self::Output {
+        self += rhs;
+        self
+    }
+}
+
 impl AddAssign<Duration> for StaticTimePoint {
     fn add_assign(&mut self, rhs: Duration) {
         let nanos = rhs.as_nanos();
@@ -1,18 +1,106 @@
-use crate::module::{Module, ModuleCapability, ModuleId};
+use crate::environment::{AsteroidId, WreckId};
+use crate::item::{ContractId, ItemCount, ItemId, Money, OfferId, OrderId};
+use crate::module::{Module, ModuleCapability, ModuleId, ModuleTypeId};
+use crate::utils::math::Point;
+use crate::utils::utils::Float;
+use crate::vessel::{
+    AsteroidContact, Contact, NavTarget, TerminalContact, VesselId, VesselRequestError,
+    WreckContact,
+};
 use crate::person::PersonId;
+use serde::{Deserialize, Serialize};
 use std::cell::RefMut;
 use std::collections::BTreeSet;
 
+/// trading terminal module of another vessel
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TerminalRef {
+    pub vessel_id: VesselId,
+    pub module_id: ModuleId,
+}
+
+impl From<&TerminalContact> for TerminalRef {
+    fn from(contact: &TerminalContact) -> Self {
+        Self {
+            vessel_id: contact.vessel_id(),
+            module_id: contact.module_id(),
+        }
+    }
+}
+
 /// interface through which a module can interact with a vessel it is contained in
 pub trait VesselModuleInterface {
     fn add_module(&self, module: Box<dyn Module>);
     fn owner(&self) -> PersonId;
     fn console(&self) -> &dyn VesselConsole;
+    /// true if the owner has not researched any tech which unlocks the module type
+    fn is_module_locked(&self, module_type: &ModuleTypeId) -> bool;
+    /// add processed research data to research progress of the owner
+    fn research(&self, data: ItemCount);
 }
 
 /// interface through which a person can interact with a vessel
 pub trait VesselConsole {
+    fn id(&self) -> VesselId;
     fn modules_with_cap(&self, cap: ModuleCapability) -> Vec<RefMut<Box<dyn Module>>>;
+    /// move the person to the module. The module may belong to a vessel docked to this one
+    /// or to the vessel this one is docked to
     fn move_to_module(&self, person: PersonId, id: ModuleId);
+    /// move the person to any module with free room of a vessel docked to this one
+    /// or of the vessel this one is docked to
+    fn move_to_vessel(&self, person: PersonId, vessel_id: VesselId);
+    /// errors of requests the person made during the last tick
+    fn request_errors(&self, person: PersonId) -> Vec<VesselRequestError>;
+    fn pos(&self) -> Point<Float>;
+    fn nav_target(&self) -> Option<NavTarget>;
+    /// vessels detected by radars of this vessel. Other vessels are unknown to the crew
+    fn contacts(&self) -> &[Contact];
+    /// asteroids detected by radars of this vessel
+    fn asteroid_contacts(&self) -> &[AsteroidContact];
+    /// wrecks detected by radars of this vessel
+    fn wreck_contacts(&self) -> &[WreckContact];
+    /// fly to the point and stop there. Needs engines
+    fn set_destination(&self, point: Point<Float>);
+    /// fly to the vessel and keep following it. Needs engines and the vessel to be in contacts
+    fn follow_vessel(&self, vessel_id: VesselId);
+    /// clear the navigation target and brake to a full stop
+    fn stop(&self);
+    /// dock a vessel from the environment to a free clamp of the module. The vessel must be in docking range
+    fn dock(&self, module_id: ModuleId, vessel_id: VesselId);
+    /// release a vessel docked to the module into the environment
+    fn undock(&self, module_id: ModuleId, vessel_id: VesselId);
+    /// vessel this one is docked to
+    fn carrier(&self) -> Option<VesselId>;
+    /// dock this vessel to a free clamp of the vessel. The vessel must be in docking range
+    fn dock_to(&self, vessel_id: VesselId);
+    /// leave the vessel this one is docked to
+    fn undock_from_carrier(&self);
+    /// collect up to `count` units of gas from the nebula the vessel is in into storages of the module
+    fn harvest_gas(&self, module_id: ModuleId, count: ItemCount);
+    /// mine the asteroid for one tick with all mining lasers in range. Ore goes to item storages
+    fn mine(&self, asteroid_id: AsteroidId);
+    /// move modules of the wreck to module storages and its items to item storages as long as there is space.
+    /// The wreck must be in salvage range
+    fn salvage(&self, wreck_id: WreckId);
+    /// leave the vessel to become a wreck
+    fn abandon(&self);
+    /// move research data of the wreck to storages of research labs as long as there is space.
+    /// The wreck must be in salvage range
+    fn gather_research_data(&self, wreck_id: WreckId);
+    /// trading terminals detected by radars of this vessel with their offers
+    fn terminal_contacts(&self) -> &[TerminalContact];
+    /// money the owner had at the end of the last tick. Orders are paid from it on settlement
+    fn owner_balance(&self) -> Money;
+    /// units of the item which fit into item storages. Known for items offered by detected terminals only
+    fn cargo_space(&self, item_id: &ItemId) -> ItemCount;
+    /// buy goods offered by the terminal. The order is settled when the vessel is in docking range of the terminal
+    fn place_buy_order(&self, terminal: TerminalRef, offer_id: OfferId, count: ItemCount);
+    /// sell goods the terminal asks for. The order is settled when the vessel is in docking range of the terminal
+    fn place_sell_order(&self, terminal: TerminalRef, offer_id: OfferId, count: ItemCount);
+    /// cancel a pending order placed by this vessel
+    fn cancel_order(&self, terminal: TerminalRef, order_id: OrderId);
+    /// accept a contract posted by the terminal. The owner pays the contract penalty as collateral.
+    /// The contract is delivered when the vessel is in docking range of the terminal with the contract items
+    fn accept_contract(&self, terminal: TerminalRef, contract_id: ContractId);
     fn capabilities(&self) -> BTreeSet<ModuleCapability>;
 }
@@ -3,7 +3,17 @@ use crate::utils::tagged_option::TaggedOptionSeed;
 use crate::vessel::{Vessel, VesselId, VesselSeed};
 use dyn_serde::{BoxSeed, DynDeserializeSeedVault, OptionSeed};
 use dyn_serde_macro::DeserializeSeedXXX;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+
+/// what happens to a vessel while it is docked
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DockedVesselPolicy {
+    /// the docked vessel is proceeded together with the carrier but stays at the carrier position
+    #[default]
+    Proceed,
+    /// the docked vessel and its crew are not proceeded until it is undocked
+    Freeze,
+}
 
 #[derive(Debug, Default, Serialize, DeserializeSeedXXX)]
 #[deserialize_seed_xxx(seed = crate::vessel::DockingClampSeed::<'v>)]
@@ -11,6 +21,7 @@ pub struct DockingClamp {
     #[serde(with = "crate::utils::tagged_option")]
     #[deserialize_seed_xxx(seed = self.seed.vessel_seed)]
     vessel: Option<Vessel>,
+    policy: DockedVesselPolicy,
 }
 
 #[derive(Clone)]
@@ -27,6 +38,21 @@ impl<'v> DockingClampSeed<'v> {
 }
 
 impl DockingClamp {
+    pub fn new(policy: DockedVesselPolicy) -> Self {
+        Self {
+            vessel: None,
+            policy,
+        }
+    }
+
+    pub fn policy(&self) -> DockedVesselPolicy {
+        self.policy
+    }
+
+    pub fn set_policy(&mut self, policy: DockedVesselPolicy) {
+        self.policy = policy
+    }
+
     pub fn is_docked(&self) -> bool {
         self.vessel.is_some()
     }
@@ -41,14 +67,14 @@ impl DockingClamp {
     }
 
     pub fn undock(&mut self) -> Option<Vessel> {
-        todo!()
+        self.vessel.take()
     }
 
     pub fn vessel_docked(&self) -> Option<&Vessel> {
-        todo!()
+        self.vessel.as_ref()
     }
 
     pub fn vessel_docked_mut(&mut self) -> Option<&mut Vessel> {
-        todo!()
+        self.vessel.as_mut()
     }
 }
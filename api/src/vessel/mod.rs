@@ -4,5 +4,8 @@ pub use console::*;
 mod vessel;
 pub use vessel::*;
 
+mod radar;
+pub use radar::*;
+
 mod docking_clamp;
 pub use docking_clamp::*;
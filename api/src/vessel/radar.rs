@@ -0,0 +1,204 @@
+use crate::environment::{Asteroid, AsteroidId, Wreck, WreckId};
+use crate::item::{BuyOffer, Contract, ItemCount, ItemId, OrderId, SellOffer};
+use crate::module::{ModuleCapability, ModuleId};
+use crate::utils::math::Point;
+use crate::utils::utils::Float;
+use crate::vessel::{Vessel, VesselId};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+
+/// what a radar is able to see
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct RadarSpec {
+    /// max distance at which vessels are detected, in meters
+    pub range: Float,
+    /// precision with which positions of detected vessels are known, in meters
+    pub resolution: Float,
+}
+
+impl RadarSpec {
+    fn round(&self, pos: Point<Float>) -> Point<Float> {
+        let round = |v: Float| {
+            if self.resolution > 0. {
+                (v / self.resolution).round() * self.resolution
+            } else {
+                v
+            }
+        };
+        (round(*pos.x()), round(*pos.y())).into()
+    }
+
+    pub(crate) fn detect(&self, vessel: &Vessel) -> Contact {
+        Contact {
+            vessel_id: vessel.id(),
+            pos: self.round(vessel.pos()),
+            capabilities: vessel
+                .modules()
+                .flat_map(|module| module.capabilities().to_vec())
+                .collect(),
+        }
+    }
+
+    pub(crate) fn detect_asteroid(&self, asteroid: &Asteroid) -> AsteroidContact {
+        AsteroidContact {
+            asteroid_id: asteroid.id(),
+            pos: self.round(asteroid.pos()),
+            ore: asteroid.ore().clone(),
+            amount: asteroid.amount(),
+        }
+    }
+
+    /// trading terminals of the vessel with pending orders of the `customer` vessel
+    pub(crate) fn detect_terminals(
+        &self,
+        vessel: &Vessel,
+        customer: VesselId,
+    ) -> Vec<TerminalContact> {
+        vessel
+            .modules()
+            .filter_map(|module| {
+                module.trading_console().map(|console| TerminalContact {
+                    vessel_id: vessel.id(),
+                    module_id: module.id(),
+                    pos: self.round(vessel.pos()),
+                    buy_offers: console.buy_offers().to_vec(),
+                    sell_offers: console.sell_offers().to_vec(),
+                    pending_orders: console.pending_orders(customer),
+                    contracts: module
+                        .contract_board_console()
+                        .map(|board| board.contracts().to_vec())
+                        .unwrap_or_default(),
+                })
+            })
+            .collect()
+    }
+
+    pub(crate) fn detect_wreck(&self, wreck: &Wreck) -> WreckContact {
+        WreckContact {
+            wreck_id: wreck.id(),
+            pos: self.round(wreck.pos()),
+            capabilities: wreck
+                .modules()
+                .flat_map(|module| module.capabilities().to_vec())
+                .collect(),
+            module_count: wreck.modules().count(),
+            item_count: wreck.items().iter().map(|item| item.count()).sum(),
+            research_data: wreck.research_data(),
+        }
+    }
+}
+
+/// vessel detected by a radar during the last scan
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Contact {
+    vessel_id: VesselId,
+    pos: Point<Float>,
+    capabilities: BTreeSet<ModuleCapability>,
+}
+
+impl Contact {
+    pub fn vessel_id(&self) -> VesselId {
+        self.vessel_id
+    }
+    pub fn pos(&self) -> Point<Float> {
+        self.pos
+    }
+    pub fn capabilities(&self) -> &BTreeSet<ModuleCapability> {
+        &self.capabilities
+    }
+}
+
+/// asteroid detected by a radar during the last scan
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AsteroidContact {
+    asteroid_id: AsteroidId,
+    pos: Point<Float>,
+    ore: ItemId,
+    amount: ItemCount,
+}
+
+impl AsteroidContact {
+    pub fn asteroid_id(&self) -> AsteroidId {
+        self.asteroid_id
+    }
+    pub fn pos(&self) -> Point<Float> {
+        self.pos
+    }
+    pub fn ore(&self) -> &ItemId {
+        &self.ore
+    }
+    pub fn amount(&self) -> ItemCount {
+        self.amount
+    }
+}
+
+/// wreck detected by a radar during the last scan
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WreckContact {
+    wreck_id: WreckId,
+    pos: Point<Float>,
+    /// capabilities of modules left in the wreck
+    capabilities: BTreeSet<ModuleCapability>,
+    module_count: usize,
+    /// units of all items left in the wreck
+    item_count: ItemCount,
+    research_data: ItemCount,
+}
+
+impl WreckContact {
+    pub fn wreck_id(&self) -> WreckId {
+        self.wreck_id
+    }
+    pub fn pos(&self) -> Point<Float> {
+        self.pos
+    }
+    pub fn capabilities(&self) -> &BTreeSet<ModuleCapability> {
+        &self.capabilities
+    }
+    pub fn module_count(&self) -> usize {
+        self.module_count
+    }
+    pub fn item_count(&self) -> ItemCount {
+        self.item_count
+    }
+    pub fn research_data(&self) -> ItemCount {
+        self.research_data
+    }
+}
+
+/// trading terminal detected by a radar during the last scan
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TerminalContact {
+    vessel_id: VesselId,
+    module_id: ModuleId,
+    pos: Point<Float>,
+    buy_offers: Vec<BuyOffer>,
+    sell_offers: Vec<SellOffer>,
+    /// orders of the scanning vessel which are not settled yet
+    pending_orders: Vec<OrderId>,
+    contracts: Vec<Contract>,
+}
+
+impl TerminalContact {
+    pub fn vessel_id(&self) -> VesselId {
+        self.vessel_id
+    }
+    pub fn module_id(&self) -> ModuleId {
+        self.module_id
+    }
+    pub fn pos(&self) -> Point<Float> {
+        self.pos
+    }
+    pub fn buy_offers(&self) -> &[BuyOffer] {
+        &self.buy_offers
+    }
+    pub fn sell_offers(&self) -> &[SellOffer] {
+        &self.sell_offers
+    }
+    pub fn pending_orders(&self) -> &[OrderId] {
+        &self.pending_orders
+    }
+    pub fn contracts(&self) -> &[Contract] {
+        &self.contracts
+    }
+}
@@ -1,18 +1,90 @@
-use crate::module::{Module, ModuleCapability, ModuleId, ModuleSeed, ProcessTokenContext};
-use crate::person::{Logger, ObjectiveDeciderVault, Person, PersonId};
-use crate::utils::math::Point;
+use crate::environment::{AsteroidId, EnvironmentRequest, SimulationClock, WreckId};
+use crate::item::{ContractId, ItemCount, ItemId, ItemRegistry, Money, OfferId, OrderId};
+use crate::module::{
+    Module, ModuleCapability, ModuleId, ModuleSeed, ModuleTypeId, ProcessTokenContext,
+};
+use crate::person::{Logger, ObjectiveDeciderVault, Person, PersonId, Severity};
+use crate::utils::math::{Angle, Point, Vector};
 use crate::utils::utils::Float;
-use crate::vessel::{VesselConsole, VesselModuleInterface};
+use crate::vessel::{
+    AsteroidContact, Contact, DockedVesselPolicy, TerminalContact, TerminalRef, VesselConsole,
+    VesselModuleInterface, WreckContact,
+};
 use dyn_serde::DynDeserializeSeedVault;
 use dyn_serde_macro::DeserializeSeedXXX;
+use rand::{Rng, RngCore};
 use serde::de::{DeserializeSeed, MapAccess, SeqAccess, Visitor};
 use serde::{Deserialize, Deserializer, Serialize, de};
 use std::cell::{Ref, RefCell, RefMut};
-use std::collections::BTreeSet;
-use std::fmt::Formatter;
+use std::collections::{BTreeMap, BTreeSet};
+use std::error::Error;
+use std::fmt;
+use std::fmt::{Display, Formatter};
 use uuid::Uuid;
 
-pub(crate) type VesselId = Uuid;
+pub type VesselId = Uuid;
+
+/// rough mass of one module in kilograms. Used to get vessel acceleration from engine thrust
+static MODULE_MASS: Float = 10000.;
+
+/// where the vessel is flying to
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub enum NavTarget {
+    /// fly to the point and stop there
+    Point(Point<Float>),
+    /// fly to the vessel and keep following it
+    Vessel(VesselId),
+}
+
+/// where a person leaving its module goes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PersonDestination {
+    Module(ModuleId),
+    /// any module with free room
+    Vessel(VesselId),
+}
+
+impl Display for PersonDestination {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            PersonDestination::Module(id) => write!(f, "module {}", id),
+            PersonDestination::Vessel(id) => write!(f, "vessel {}", id),
+        }
+    }
+}
+
+/// reason a request of a person could not be processed. Reported to the person on the next tick
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum VesselRequestError {
+    /// the person is in none of the modules of the vessel
+    PersonNotFound { person_id: PersonId },
+    /// there is no such module on the vessel and vessels docked to it or its carrier
+    DestinationNotFound { destination: PersonDestination },
+    DestinationFull { destination: PersonDestination },
+    /// the person is already in the module
+    SameModule { module_id: ModuleId },
+}
+
+impl Display for VesselRequestError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            VesselRequestError::PersonNotFound { person_id } => {
+                write!(f, "Person {} is not on board", person_id)
+            }
+            VesselRequestError::DestinationNotFound { destination } => {
+                write!(f, "Can not find {}", destination)
+            }
+            VesselRequestError::DestinationFull { destination } => {
+                write!(f, "No room in {}", destination)
+            }
+            VesselRequestError::SameModule { module_id } => {
+                write!(f, "Already in module {}", module_id)
+            }
+        }
+    }
+}
+
+impl Error for VesselRequestError {}
 
 #[derive(Debug)]
 enum VesselRequest {
@@ -20,9 +92,63 @@ enum VesselRequest {
         person_id: PersonId,
         module_id: ModuleId,
     },
+    MoveToVessel {
+        person_id: PersonId,
+        vessel_id: VesselId,
+    },
     AddModule {
         module: Box<dyn Module>,
     },
+    Dock {
+        module_id: ModuleId,
+        vessel_id: VesselId,
+    },
+    Undock {
+        module_id: ModuleId,
+        vessel_id: VesselId,
+    },
+    DockTo {
+        vessel_id: VesselId,
+    },
+    UndockFromCarrier,
+    SetNavTarget {
+        target: Option<NavTarget>,
+    },
+    HarvestGas {
+        module_id: ModuleId,
+        count: ItemCount,
+    },
+    Mine {
+        asteroid_id: AsteroidId,
+    },
+    Salvage {
+        wreck_id: WreckId,
+    },
+    Abandon,
+    Research {
+        data: ItemCount,
+    },
+    GatherResearchData {
+        wreck_id: WreckId,
+    },
+    PlaceBuyOrder {
+        terminal: TerminalRef,
+        offer_id: OfferId,
+        count: ItemCount,
+    },
+    PlaceSellOrder {
+        terminal: TerminalRef,
+        offer_id: OfferId,
+        count: ItemCount,
+    },
+    CancelOrder {
+        terminal: TerminalRef,
+        order_id: OrderId,
+    },
+    AcceptContract {
+        terminal: TerminalRef,
+        contract_id: ContractId,
+    },
 }
 
 #[derive(Debug, Serialize, DeserializeSeedXXX)]
@@ -31,10 +157,40 @@ pub struct Vessel {
     id: VesselId,
     owner: PersonId,
     pos: Point<Float>,
+    /// meters per second
+    velocity: Vector<Float>,
+    heading: Angle<Float>,
+    #[serde(with = "crate::utils::tagged_option")]
+    nav_target: Option<NavTarget>,
+    /// vessel this one is docked to
+    #[serde(with = "crate::utils::untagged_option")]
+    carrier: Option<VesselId>,
+    /// kilojoules released from burned fuel and not yet consumed
+    energy: Float,
+    /// false if the vessel went dark because power demand could not be satisfied
+    powered: bool,
+    /// vessels detected by radars during the last scan
+    contacts: Vec<Contact>,
+    asteroid_contacts: Vec<AsteroidContact>,
+    wreck_contacts: Vec<WreckContact>,
+    terminal_contacts: Vec<TerminalContact>,
+    /// balance of the owner at the end of the last tick
+    owner_balance: Money,
+    /// units of each item offered by detected terminals which fit into item storages
+    cargo_space: BTreeMap<ItemId, ItemCount>,
+    /// module types the owner has not researched yet. Updated by the environment every tick
+    #[serde(skip)]
+    locked_modules: BTreeSet<ModuleTypeId>,
     #[deserialize_seed_xxx(seed = self.seed.module_seq_seed)]
     modules: Vec<RefCell<Box<dyn Module>>>,
     #[serde(skip)]
     requests: RefCell<Vec<VesselRequest>>,
+    /// errors of requests made during the last tick, visible to persons during this one
+    #[serde(default)]
+    request_errors: Vec<(PersonId, VesselRequestError)>,
+    /// errors of requests made during this tick
+    #[serde(default)]
+    new_request_errors: RefCell<Vec<(PersonId, VesselRequestError)>>,
 }
 
 #[derive(Clone)]
@@ -92,20 +248,126 @@ impl<'v> VesselSeed<'v> {
 }
 
 impl Vessel {
-    pub(crate) fn id(&self) -> VesselId {
+    pub fn id(&self) -> VesselId {
         self.id
     }
-    pub(crate) fn owner(&self) -> PersonId {
+    pub fn owner(&self) -> PersonId {
         self.owner
     }
+    pub fn pos(&self) -> Point<Float> {
+        self.pos
+    }
+    pub fn velocity(&self) -> Vector<Float> {
+        self.velocity
+    }
+    pub fn heading(&self) -> Angle<Float> {
+        self.heading
+    }
+    pub fn nav_target(&self) -> Option<NavTarget> {
+        self.nav_target
+    }
+    pub fn energy(&self) -> Float {
+        self.energy
+    }
+    pub fn powered(&self) -> bool {
+        self.powered
+    }
+    pub fn contacts(&self) -> &[Contact] {
+        &self.contacts
+    }
+    pub fn contact(&self, vessel_id: VesselId) -> Option<&Contact> {
+        self.contacts.iter().find(|c| c.vessel_id() == vessel_id)
+    }
+    pub fn asteroid_contacts(&self) -> &[AsteroidContact] {
+        &self.asteroid_contacts
+    }
+    pub fn wreck_contacts(&self) -> &[WreckContact] {
+        &self.wreck_contacts
+    }
+    pub fn terminal_contacts(&self) -> &[TerminalContact] {
+        &self.terminal_contacts
+    }
+
+    /// total power draw of all modules in kilowatts
+    pub fn power_draw(&self) -> Float {
+        self.modules.iter().map(|m| m.borrow().power_draw()).sum()
+    }
+
+    /// total power output of all generators in kilowatts
+    pub fn power_output(&self) -> Float {
+        self.modules.iter().map(|m| m.borrow().power_output()).sum()
+    }
+
+    /// total energy all engines need to fly one meter, in kilojoules
+    pub fn engine_energy_per_meter(&self) -> Float {
+        self.modules
+            .iter()
+            .map(|m| m.borrow().engine_energy_per_meter())
+            .sum()
+    }
+
+    /// fuel units of all kinds stored in fuel tanks
+    pub fn fuel(&self, item_registry: &ItemRegistry) -> ItemCount {
+        self.modules
+            .iter()
+            .filter(|m| m.borrow().capabilities().contains(&ModuleCapability::FuelTank))
+            .map(|m| {
+                m.borrow()
+                    .storages()
+                    .iter()
+                    .flat_map(|storage| storage.iter())
+                    .filter(|item| {
+                        item_registry
+                            .get(item.id())
+                            .map(|info| info.is_fuel())
+                            .unwrap_or(false)
+                    })
+                    .map(|item| item.count())
+                    .sum::<ItemCount>()
+            })
+            .sum()
+    }
+
+    /// total thrust of all engines in newtons
+    pub fn thrust(&self) -> Float {
+        self.modules.iter().map(|m| m.borrow().thrust()).sum()
+    }
 
-    pub fn new(owner: PersonId, pos: Point<Float>, modules: Vec<Box<dyn Module>>) -> Self {
+    /// meters per second squared
+    pub fn acceleration(&self) -> Float {
+        if self.modules.is_empty() {
+            return 0.;
+        }
+        self.thrust() / (MODULE_MASS * self.modules.len() as Float)
+    }
+
+    pub fn new(
+        owner: PersonId,
+        pos: Point<Float>,
+        modules: Vec<Box<dyn Module>>,
+        rng: &mut dyn RngCore,
+    ) -> Self {
         Self {
-            id: VesselId::new_v4(),
+            id: uuid::Builder::from_random_bytes(rng.random()).into_uuid(),
             owner,
             pos,
+            velocity: (0., 0.).into(),
+            heading: Angle::from_radians(0.),
+            nav_target: None,
+            carrier: None,
+            energy: 0.,
+            powered: true,
+            contacts: Vec::new(),
+            asteroid_contacts: Vec::new(),
+            wreck_contacts: Vec::new(),
+            terminal_contacts: Vec::new(),
+            owner_balance: 0,
+            cargo_space: BTreeMap::new(),
+            locked_modules: BTreeSet::new(),
             modules: modules.into_iter().map(RefCell::new).collect(),
             requests: Default::default(),
+            request_errors: Vec::new(),
+            new_request_errors: Default::default(),
         }
     }
 
@@ -121,15 +383,448 @@ impl Vessel {
         self.modules.push(RefCell::new(module));
     }
 
+    /// docks `vessel` to a free clamp of the module. Returns the vessel back if it can not be docked
+    pub(crate) fn dock(&self, module_id: ModuleId, mut vessel: Vessel) -> Result<(), Vessel> {
+        let Some(module) = self.modules.iter().find(|m| m.borrow().id() == module_id) else {
+            return Err(vessel);
+        };
+        let mut module = module.borrow_mut();
+        match module
+            .docking_clamps_mut()
+            .iter_mut()
+            .find(|clamp| !clamp.is_docked())
+        {
+            None => Err(vessel),
+            Some(clamp) => {
+                vessel.pos = self.pos;
+                vessel.velocity = (0., 0.).into();
+                vessel.nav_target = None;
+                vessel.carrier = Some(self.id);
+                let ok = clamp.dock(vessel);
+                assert!(ok);
+                Ok(())
+            }
+        }
+    }
+
+    pub(crate) fn set_contacts(
+        &mut self,
+        contacts: Vec<Contact>,
+        asteroid_contacts: Vec<AsteroidContact>,
+        wreck_contacts: Vec<WreckContact>,
+        terminal_contacts: Vec<TerminalContact>,
+    ) {
+        self.contacts = contacts;
+        self.asteroid_contacts = asteroid_contacts;
+        self.wreck_contacts = wreck_contacts;
+        self.terminal_contacts = terminal_contacts;
+    }
+
+    pub(crate) fn set_market_knowledge(
+        &mut self,
+        owner_balance: Money,
+        cargo_space: BTreeMap<ItemId, ItemCount>,
+    ) {
+        self.owner_balance = owner_balance;
+        self.cargo_space = cargo_space;
+    }
+
+    /// `locked_modules` gives module types locked for an owner. Applied to docked vessels as well
+    pub(crate) fn set_locked_modules(
+        &mut self,
+        locked_modules: &dyn Fn(PersonId) -> BTreeSet<ModuleTypeId>,
+    ) {
+        self.locked_modules = locked_modules(self.owner);
+        for module in &self.modules {
+            for clamp in module.borrow_mut().docking_clamps_mut() {
+                if let Some(vessel) = clamp.vessel_docked_mut() {
+                    vessel.set_locked_modules(locked_modules);
+                }
+            }
+        }
+    }
+
+    /// splits the vessel into its modules and vessels which were docked to it.
+    /// Released vessels are placed where this one was
+    pub(crate) fn into_modules(self) -> (Vec<Box<dyn Module>>, Vec<Vessel>) {
+        let mut released = Vec::new();
+        let modules = self
+            .modules
+            .into_iter()
+            .map(|module| {
+                let mut module = module.into_inner();
+                for clamp in module.docking_clamps_mut() {
+                    if let Some(mut vessel) = clamp.undock() {
+                        vessel.carrier = None;
+                        vessel.pos = self.pos;
+                        vessel.velocity = self.velocity;
+                        vessel.heading = self.heading;
+                        released.push(vessel);
+                    }
+                }
+                module
+            })
+            .collect();
+        (modules, released)
+    }
+
+    pub(crate) fn set_nav_target(&mut self, target: Option<NavTarget>) {
+        self.nav_target = target
+    }
+
+    /// burns one unit of fuel from any fuel tank. Returns false if there is no fuel left
+    fn burn_fuel(&mut self, item_registry: &ItemRegistry) -> bool {
+        for module in &self.modules {
+            let mut module = module.borrow_mut();
+            if !module.capabilities().contains(&ModuleCapability::FuelTank) {
+                continue;
+            }
+            for storage in module.storages_mut() {
+                let fuel = storage.iter().find_map(|item| {
+                    item_registry
+                        .get(item.id())
+                        .filter(|info| info.is_fuel())
+                        .map(|info| (item.id().clone(), info.fuel_energy()))
+                });
+                if let Some((id, fuel_energy)) = fuel {
+                    storage.remove_item(id, 1);
+                    self.energy += fuel_energy;
+                    return true;
+                }
+            }
+        }
+        false
+    }
+
+    /// takes `amount` kilojoules from the energy buffer burning fuel if needed.
+    /// Returns false and takes nothing if there is not enough fuel
+    fn take_energy(&mut self, amount: Float, item_registry: &ItemRegistry) -> bool {
+        while self.energy < amount {
+            if !self.burn_fuel(item_registry) {
+                return false;
+            }
+        }
+        self.energy -= amount;
+        true
+    }
+
+    /// checks that generators can cover the power draw of all modules and burns fuel for one tick.
+    /// Vessel goes dark if they can not
+    fn supply_power(&mut self, item_registry: &ItemRegistry, dt: Float) {
+        let demand = self.power_draw();
+        self.powered =
+            demand <= self.power_output() && self.take_energy(demand * dt, item_registry);
+    }
+
+    /// moves the vessel one tick (`dt` seconds) towards the target, braking so it stops at the target.
+    /// Brakes to a full stop if there is no target. Returns true if the target is reached.
+    /// Engines burn fuel for every meter flown. Without power or fuel the vessel drifts.
+    /// `speed_factor` is the drag of a nebula the vessel is in, 1 in open space
+    pub(crate) fn navigate(
+        &mut self,
+        target: Option<Point<Float>>,
+        dt: Float,
+        item_registry: &ItemRegistry,
+        speed_factor: Float,
+    ) -> bool {
+        self.velocity = self.velocity * speed_factor.powf(dt);
+
+        let energy_per_meter = self.engine_energy_per_meter();
+        if !self.powered || energy_per_meter <= 0. {
+            return self.fly(target, 0., dt);
+        }
+
+        let (pos, velocity, heading) = (self.pos, self.velocity, self.heading);
+        let arrived = self.fly(target, self.acceleration() * speed_factor, dt);
+        let distance = (self.pos - pos).len();
+        if self.take_energy(distance * energy_per_meter, item_registry) {
+            return arrived;
+        }
+
+        (self.pos, self.velocity, self.heading) = (pos, velocity, heading);
+        self.fly(target, 0., dt)
+    }
+
+    fn fly(&mut self, target: Option<Point<Float>>, acceleration: Float, dt: Float) -> bool {
+        let speed = self.velocity.len();
+
+        let Some(target) = target else {
+            let speed = (speed - acceleration * dt).max(0.);
+            self.velocity = Vector::from_angle(self.heading) * speed;
+            self.pos = self.pos + self.velocity * dt;
+            return false;
+        };
+
+        let to_target = target - self.pos;
+        let distance = to_target.len();
+        if distance > 0. {
+            self.heading = to_target.angle();
+        }
+
+        // max speed from which the vessel is still able to stop at the target
+        let stopping_speed = (2. * acceleration * distance).sqrt();
+        let speed = if speed < stopping_speed {
+            (speed + acceleration * dt).min(stopping_speed)
+        } else {
+            (speed - acceleration * dt).max(stopping_speed)
+        };
+
+        if speed * dt >= distance {
+            self.pos = target;
+            self.velocity = (0., 0.).into();
+            return true;
+        }
+
+        self.velocity = Vector::from_angle(self.heading) * speed;
+        self.pos = self.pos + self.velocity * dt;
+        false
+    }
+
+    fn undock(&self, module_id: ModuleId, vessel_id: VesselId) -> Option<Vessel> {
+        let module = self.modules.iter().find(|m| m.borrow().id() == module_id)?;
+        let mut module = module.borrow_mut();
+        let mut vessel = module
+            .docking_clamps_mut()
+            .iter_mut()
+            .find(|clamp| {
+                clamp
+                    .vessel_docked()
+                    .map(|vessel| vessel.id() == vessel_id)
+                    .unwrap_or(false)
+            })?
+            .undock()?;
+        vessel.carrier = None;
+        vessel.pos = self.pos;
+        vessel.velocity = self.velocity;
+        vessel.heading = self.heading;
+        Some(vessel)
+    }
+
+    /// releases the vessel from whichever clamp of this one it is docked to
+    pub(crate) fn undock_vessel(&self, vessel_id: VesselId) -> Option<Vessel> {
+        let module_id = self.modules.iter().find_map(|module| {
+            let module = module.borrow();
+            module
+                .docking_clamps()
+                .iter()
+                .any(|clamp| {
+                    clamp
+                        .vessel_docked()
+                        .map(|vessel| vessel.id() == vessel_id)
+                        .unwrap_or(false)
+                })
+                .then(|| module.id())
+        })?;
+        self.undock(module_id, vessel_id)
+    }
+
+    fn for_each_docked(&self, f: &mut dyn FnMut(&Vessel)) {
+        for module in &self.modules {
+            for clamp in module.borrow().docking_clamps() {
+                if let Some(vessel) = clamp.vessel_docked() {
+                    f(vessel)
+                }
+            }
+        }
+    }
+
+    /// puts the person into a module with free room matching the destination. Takes `person` on success.
+    /// Returns false if no module of the vessel matches the destination
+    fn place_person(&self, destination: PersonDestination, person: &mut Option<Person>) -> bool {
+        let mut found = false;
+        for module in &self.modules {
+            let matches = match destination {
+                PersonDestination::Module(id) => module.borrow().id() == id,
+                PersonDestination::Vessel(id) => self.id == id,
+            };
+            found |= matches;
+            if matches && person.is_some() && module.borrow().can_insert_person() {
+                *person = module
+                    .borrow_mut()
+                    .insert_person(person.take().unwrap())
+                    .err();
+            }
+        }
+        found
+    }
+
+    /// moves everyone aboard to free seats on vessels docked to this one so that it can be abandoned.
+    /// Returns false if someone found no seat. That person stays aboard, persons moved before stay on the docked vessels
+    pub(crate) fn evacuate(&self) -> bool {
+        for module in &self.modules {
+            let person_ids = module.borrow().person_ids();
+            for person_id in person_ids {
+                let mut person = module.borrow_mut().extract_person(person_id);
+                self.for_each_docked(&mut |vessel| {
+                    vessel.place_person(PersonDestination::Vessel(vessel.id), &mut person);
+                });
+                if let Some(person) = person {
+                    module
+                        .borrow_mut()
+                        .insert_person(person)
+                        .expect("module refused a person which just left it");
+                    return false;
+                }
+            }
+        }
+        true
+    }
+
+    /// moves the person which left vessel `from` to the destination if it is on a vessel docked
+    /// to `from` or on the vessel `from` is docked to. Docked vessels are searched recursively.
+    /// Takes `person` on success. Returns false if the destination was not found
+    pub(crate) fn transfer_person(
+        &self,
+        from: VesselId,
+        destination: PersonDestination,
+        person: &mut Option<Person>,
+    ) -> bool {
+        let mut found = false;
+        if self.id == from {
+            self.for_each_docked(&mut |vessel| found |= vessel.place_person(destination, person));
+            return found;
+        }
+        let mut carries_from = false;
+        self.for_each_docked(&mut |vessel| carries_from |= vessel.id == from);
+        if carries_from {
+            found |= self.place_person(destination, person);
+        }
+        self.for_each_docked(&mut |vessel| {
+            found |= vessel.transfer_person(from, destination, person)
+        });
+        found
+    }
+
+    /// puts the person back into the module of the vessel it left and reports why its request failed.
+    /// If that module is full the person takes any free seat on the vessel, then on its carrier.
+    /// Docked vessels are searched recursively. Takes `person` on success
+    pub(crate) fn return_person(
+        &self,
+        vessel_id: VesselId,
+        module_id: ModuleId,
+        person: &mut Option<Person>,
+        error: &VesselRequestError,
+    ) {
+        let Some(person_id) = person.as_ref().map(|person| person.id()) else {
+            return;
+        };
+        if self.id == vessel_id {
+            self.place_person(PersonDestination::Module(module_id), person);
+            self.place_person(PersonDestination::Vessel(self.id), person);
+        } else {
+            let mut carries_from = false;
+            self.for_each_docked(&mut |vessel| {
+                carries_from |= vessel.id == vessel_id;
+                vessel.return_person(vessel_id, module_id, person, error)
+            });
+            if !carries_from || person.is_none() {
+                return;
+            }
+            self.place_person(PersonDestination::Vessel(self.id), person);
+        }
+        if person.is_none() {
+            self.new_request_errors
+                .borrow_mut()
+                .push((person_id, error.clone()));
+        }
+    }
+
+    /// logs the error and keeps it for the person to see on the next tick
+    fn report_request_error(
+        &self,
+        person_id: PersonId,
+        error: VesselRequestError,
+        logger: &mut dyn Logger,
+    ) {
+        logger.log(&person_id, Severity::Warning, error.to_string());
+        self.new_request_errors.borrow_mut().push((person_id, error));
+    }
+
+    fn module_of_person(
+        &self,
+        person_id: PersonId,
+    ) -> Result<&RefCell<Box<dyn Module>>, VesselRequestError> {
+        self.modules
+            .iter()
+            .find(|m| m.borrow().contains_person(person_id))
+            .ok_or(VesselRequestError::PersonNotFound { person_id })
+    }
+
+    /// takes the person out of `src` to be placed into a docked vessel by the environment
+    fn transfer_request(
+        &self,
+        src: &RefCell<Box<dyn Module>>,
+        person_id: PersonId,
+        destination: PersonDestination,
+    ) -> Result<EnvironmentRequest, VesselRequestError> {
+        let mut src = src.borrow_mut();
+        Ok(EnvironmentRequest::TransferPerson {
+            person: src
+                .extract_person(person_id)
+                .ok_or(VesselRequestError::PersonNotFound { person_id })?,
+            vessel_id: self.id,
+            module_id: src.id(),
+            destination,
+        })
+    }
+
+    /// moves the person between modules of this vessel. Modules of other vessels are left to the environment
+    fn move_person(
+        &self,
+        person_id: PersonId,
+        module_id: ModuleId,
+        environment_requests: &mut Vec<EnvironmentRequest>,
+    ) -> Result<(), VesselRequestError> {
+        let src = self.module_of_person(person_id)?;
+        let destination = PersonDestination::Module(module_id);
+        let Some(dst) = self.modules.iter().find(|m| m.borrow().id() == module_id) else {
+            // module of a docked vessel or of the carrier
+            environment_requests.push(self.transfer_request(src, person_id, destination)?);
+            return Ok(());
+        };
+        if src.as_ptr() == dst.as_ptr() {
+            return Err(VesselRequestError::SameModule { module_id });
+        }
+        let mut dst = dst.borrow_mut();
+        if !dst.can_insert_person() {
+            return Err(VesselRequestError::DestinationFull { destination });
+        }
+        let mut src = src.borrow_mut();
+        let person = src
+            .extract_person(person_id)
+            .ok_or(VesselRequestError::PersonNotFound { person_id })?;
+        dst.insert_person(person).map_err(|person| {
+            src.insert_person(person)
+                .expect("module refused a person which just left it");
+            VesselRequestError::DestinationFull { destination }
+        })
+    }
+
     pub(crate) fn proceed(
         &mut self,
+        rng: &mut dyn RngCore,
+        clock: &SimulationClock,
+        item_registry: &ItemRegistry,
         process_token_context: &ProcessTokenContext,
         decider_vault: &ObjectiveDeciderVault,
         logger: &mut dyn Logger,
-    ) {
+    ) -> Vec<EnvironmentRequest> {
+        let mut environment_requests = Vec::new();
+        self.request_errors = self.new_request_errors.take();
+        self.supply_power(item_registry, clock.tick_duration().as_secs_f64());
         for v in &self.modules {
-            v.borrow_mut()
-                .proceed(self, process_token_context, decider_vault, logger)
+            if !self.powered && v.borrow().power_draw() > 0. {
+                continue;
+            }
+            v.borrow_mut().proceed(
+                self,
+                rng,
+                clock,
+                item_registry,
+                process_token_context,
+                decider_vault,
+                logger,
+            )
         }
         for request in self.requests.take() {
             match request {
@@ -137,30 +832,147 @@ impl Vessel {
                     person_id,
                     module_id,
                 } => {
-                    let src = self
-                        .modules
-                        .iter()
-                        .find(|m| m.borrow().contains_person(person_id))
-                        .unwrap();
-                    let dst = self
-                        .modules
-                        .iter()
-                        .find(|m| m.borrow().id() == module_id)
-                        .unwrap();
-                    assert_ne!(src.as_ptr(), dst.as_ptr());
-                    let mut src = src.borrow_mut();
-                    let mut dst = dst.borrow_mut();
-                    assert!(dst.can_insert_person());
-                    let ok = dst.insert_person(src.extract_person(person_id).unwrap());
-                    assert!(ok);
-                    assert!(!src.contains_person(person_id));
-                    assert!(dst.contains_person(person_id));
+                    if let Err(error) =
+                        self.move_person(person_id, module_id, &mut environment_requests)
+                    {
+                        self.report_request_error(person_id, error, logger);
+                    }
+                }
+                VesselRequest::MoveToVessel {
+                    person_id,
+                    vessel_id,
+                } => {
+                    let request = self.module_of_person(person_id).and_then(|src| {
+                        self.transfer_request(src, person_id, PersonDestination::Vessel(vessel_id))
+                    });
+                    match request {
+                        Ok(request) => environment_requests.push(request),
+                        Err(error) => self.report_request_error(person_id, error, logger),
+                    }
                 }
                 VesselRequest::AddModule { module } => {
                     self.modules.push(RefCell::new(module));
                 }
+                VesselRequest::Dock {
+                    module_id,
+                    vessel_id,
+                } => environment_requests.push(EnvironmentRequest::Dock {
+                    vessel_id,
+                    dst_vessel_id: self.id,
+                    dst_module_id: module_id,
+                }),
+                VesselRequest::Undock {
+                    module_id,
+                    vessel_id,
+                } => {
+                    if let Some(vessel) = self.undock(module_id, vessel_id) {
+                        environment_requests.push(EnvironmentRequest::Release { vessel });
+                    }
+                }
+                VesselRequest::DockTo { vessel_id } => {
+                    environment_requests.push(EnvironmentRequest::DockTo {
+                        vessel_id: self.id,
+                        dst_vessel_id: vessel_id,
+                    })
+                }
+                VesselRequest::UndockFromCarrier => {
+                    if let Some(carrier_id) = self.carrier {
+                        environment_requests.push(EnvironmentRequest::UndockFromCarrier {
+                            vessel_id: self.id,
+                            carrier_id,
+                        })
+                    }
+                }
+                VesselRequest::SetNavTarget { target } => self.nav_target = target,
+                VesselRequest::HarvestGas { module_id, count } => {
+                    environment_requests.push(EnvironmentRequest::HarvestGas {
+                        vessel_id: self.id,
+                        module_id,
+                        count,
+                    })
+                }
+                VesselRequest::Mine { asteroid_id } => {
+                    environment_requests.push(EnvironmentRequest::Mine {
+                        vessel_id: self.id,
+                        asteroid_id,
+                    })
+                }
+                VesselRequest::Salvage { wreck_id } => {
+                    environment_requests.push(EnvironmentRequest::Salvage {
+                        vessel_id: self.id,
+                        wreck_id,
+                    })
+                }
+                VesselRequest::Abandon => environment_requests
+                    .push(EnvironmentRequest::Abandon { vessel_id: self.id }),
+                VesselRequest::Research { data } => {
+                    environment_requests.push(EnvironmentRequest::Research {
+                        owner: self.owner,
+                        data,
+                    })
+                }
+                VesselRequest::GatherResearchData { wreck_id } => {
+                    environment_requests.push(EnvironmentRequest::GatherResearchData {
+                        vessel_id: self.id,
+                        wreck_id,
+                    })
+                }
+                VesselRequest::PlaceBuyOrder {
+                    terminal,
+                    offer_id,
+                    count,
+                } => environment_requests.push(EnvironmentRequest::PlaceBuyOrder {
+                    vessel_id: self.id,
+                    owner: self.owner,
+                    terminal,
+                    offer_id,
+                    count,
+                }),
+                VesselRequest::PlaceSellOrder {
+                    terminal,
+                    offer_id,
+                    count,
+                } => environment_requests.push(EnvironmentRequest::PlaceSellOrder {
+                    vessel_id: self.id,
+                    owner: self.owner,
+                    terminal,
+                    offer_id,
+                    count,
+                }),
+                VesselRequest::CancelOrder { terminal, order_id } => {
+                    environment_requests.push(EnvironmentRequest::CancelOrder { terminal, order_id })
+                }
+                VesselRequest::AcceptContract {
+                    terminal,
+                    contract_id,
+                } => environment_requests.push(EnvironmentRequest::AcceptContract {
+                    vessel_id: self.id,
+                    owner: self.owner,
+                    terminal,
+                    contract_id,
+                }),
             }
         }
+        for module in &self.modules {
+            for clamp in module.borrow_mut().docking_clamps_mut() {
+                if clamp.policy() != DockedVesselPolicy::Proceed {
+                    continue;
+                }
+                if let Some(vessel) = clamp.vessel_docked_mut() {
+                    vessel.pos = self.pos;
+                    vessel.velocity = self.velocity;
+                    environment_requests.extend(vessel.proceed(
+                        rng,
+                        clock,
+                        item_registry,
+                        process_token_context,
+                        decider_vault,
+                        logger,
+                    ));
+                }
+            }
+        }
+        environment_requests
     }
 }
 
@@ -172,15 +984,29 @@ impl VesselModuleInterface for Vessel {
     }
 
     fn owner(&self) -> PersonId {
-        todo!()
+        self.owner
     }
 
     fn console(&self) -> &dyn VesselConsole {
         self
     }
+
+    fn is_module_locked(&self, module_type: &ModuleTypeId) -> bool {
+        self.locked_modules.contains(module_type)
+    }
+
+    fn research(&self, data: ItemCount) {
+        self.requests
+            .borrow_mut()
+            .push(VesselRequest::Research { data })
+    }
 }
 
 impl VesselConsole for Vessel {
+    fn id(&self) -> VesselId {
+        self.id
+    }
+
     fn modules_with_cap(&self, cap: ModuleCapability) -> Vec<RefMut<Box<dyn Module>>> {
         self.modules
             .iter()
@@ -204,6 +1030,160 @@ impl VesselConsole for Vessel {
             })
     }
 
+    fn move_to_vessel(&self, person_id: PersonId, vessel_id: VesselId) {
+        self.requests
+            .borrow_mut()
+            .push(VesselRequest::MoveToVessel {
+                person_id,
+                vessel_id,
+            })
+    }
+
+    fn request_errors(&self, person_id: PersonId) -> Vec<VesselRequestError> {
+        self.request_errors
+            .iter()
+            .filter(|(id, _)| *id == person_id)
+            .map(|(_, error)| error.clone())
+            .collect()
+    }
+
+    fn pos(&self) -> Point<Float> {
+        self.pos
+    }
+
+    fn nav_target(&self) -> Option<NavTarget> {
+        self.nav_target
+    }
+
+    fn contacts(&self) -> &[Contact] {
+        &self.contacts
+    }
+
+    fn asteroid_contacts(&self) -> &[AsteroidContact] {
+        &self.asteroid_contacts
+    }
+
+    fn wreck_contacts(&self) -> &[WreckContact] {
+        &self.wreck_contacts
+    }
+
+    fn set_destination(&self, point: Point<Float>) {
+        self.requests.borrow_mut().push(VesselRequest::SetNavTarget {
+            target: Some(NavTarget::Point(point)),
+        })
+    }
+
+    fn follow_vessel(&self, vessel_id: VesselId) {
+        self.requests.borrow_mut().push(VesselRequest::SetNavTarget {
+            target: Some(NavTarget::Vessel(vessel_id)),
+        })
+    }
+
+    fn stop(&self) {
+        self.requests
+            .borrow_mut()
+            .push(VesselRequest::SetNavTarget { target: None })
+    }
+
+    fn dock(&self, module_id: ModuleId, vessel_id: VesselId) {
+        self.requests.borrow_mut().push(VesselRequest::Dock {
+            module_id,
+            vessel_id,
+        })
+    }
+
+    fn undock(&self, module_id: ModuleId, vessel_id: VesselId) {
+        self.requests.borrow_mut().push(VesselRequest::Undock {
+            module_id,
+            vessel_id,
+        })
+    }
+
+    fn carrier(&self) -> Option<VesselId> {
+        self.carrier
+    }
+
+    fn dock_to(&self, vessel_id: VesselId) {
+        self.requests
+            .borrow_mut()
+            .push(VesselRequest::DockTo { vessel_id })
+    }
+
+    fn undock_from_carrier(&self) {
+        self.requests
+            .borrow_mut()
+            .push(VesselRequest::UndockFromCarrier)
+    }
+
+    fn harvest_gas(&self, module_id: ModuleId, count: ItemCount) {
+        self.requests
+            .borrow_mut()
+            .push(VesselRequest::HarvestGas { module_id, count })
+    }
+
+    fn mine(&self, asteroid_id: AsteroidId) {
+        self.requests
+            .borrow_mut()
+            .push(VesselRequest::Mine { asteroid_id })
+    }
+
+    fn salvage(&self, wreck_id: WreckId) {
+        self.requests
+            .borrow_mut()
+            .push(VesselRequest::Salvage { wreck_id })
+    }
+
+    fn abandon(&self) {
+        self.requests.borrow_mut().push(VesselRequest::Abandon)
+    }
+
+    fn gather_research_data(&self, wreck_id: WreckId) {
+        self.requests
+            .borrow_mut()
+            .push(VesselRequest::GatherResearchData { wreck_id })
+    }
+
+    fn terminal_contacts(&self) -> &[TerminalContact] {
+        &self.terminal_contacts
+    }
+
+    fn owner_balance(&self) -> Money {
+        self.owner_balance
+    }
+
+    fn cargo_space(&self, item_id: &ItemId) -> ItemCount {
+        self.cargo_space.get(item_id).cloned().unwrap_or(0)
+    }
+
+    fn place_buy_order(&self, terminal: TerminalRef, offer_id: OfferId, count: ItemCount) {
+        self.requests.borrow_mut().push(VesselRequest::PlaceBuyOrder {
+            terminal,
+            offer_id,
+            count,
+        })
+    }
+
+    fn place_sell_order(&self, terminal: TerminalRef, offer_id: OfferId, count: ItemCount) {
+        self.requests.borrow_mut().push(VesselRequest::PlaceSellOrder {
+            terminal,
+            offer_id,
+            count,
+        })
+    }
+
+    fn cancel_order(&self, terminal: TerminalRef, order_id: OrderId) {
+        self.requests
+            .borrow_mut()
+            .push(VesselRequest::CancelOrder { terminal, order_id })
+    }
+
+    fn accept_contract(&self, terminal: TerminalRef, contract_id: ContractId) {
+        self.requests.borrow_mut().push(VesselRequest::AcceptContract {
+            terminal,
+            contract_id,
+        })
+    }
+
     fn capabilities(&self) -> BTreeSet<ModuleCapability> {
         self.modules
             .iter()
@@ -0,0 +1,8 @@
+use dudes_in_space_api::item::ItemRegistry;
+
+static ITEMS: &str = include_str!("items.ron");
+
+pub fn register_items(mut registry: ItemRegistry) -> ItemRegistry {
+    registry.extend_from_ron(ITEMS).unwrap();
+    registry
+}
@@ -1,9 +1,13 @@
 pub(crate) const CORE_PACKAGE_ID: &str = "core";
 
+mod items;
 mod modules;
 mod objectives;
+mod techs;
 
+pub use items::register_items;
 pub use modules::register_module_factories;
 pub use modules::register_modules;
 pub use objectives::register_objective_deciders;
 pub use objectives::register_objectives;
+pub use techs::register_techs;
@@ -1,20 +1,28 @@
 use crate::modules::{CoreModule, ModuleVisitor, ModuleVisitorMut};
-use dudes_in_space_api::item::ItemStorage;
-use dudes_in_space_api::module::{AssemblyConsole, DockyardConsole, Module, ModuleCapability, ModuleConsole, ModuleId, ModuleStorage, PackageId, ProcessToken, ProcessTokenContext, ProcessTokenMut, ProcessTokenMutSeed, TradingAdminConsole, TradingConsole};
+use dudes_in_space_api::environment::{MiningLaserSpec, SimulationClock};
+use dudes_in_space_api::item::{ItemRegistry, ItemStorage};
+use dudes_in_space_api::module::{
+    AssemblyConsole, ContractBoardConsole, DockyardConsole, FabricationConsole, Module,
+    ModuleCapability, ModuleConsole, ModuleId, ModuleStorage, PackageId, ProcessToken,
+    ProcessTokenContext, ProcessTokenMut, ProcessTokenMutSeed, TradingAdminConsole, TradingConsole,
+};
 use dudes_in_space_api::person::{DynObjective, Logger, ObjectiveDeciderVault, Person, PersonId, PersonSeed};
 use dudes_in_space_api::recipe::{AssemblyRecipe, AssemblyRecipeSeed, ModuleFactory, Recipe};
 use dudes_in_space_api::utils::tagged_option::TaggedOptionSeed;
-use dudes_in_space_api::vessel::{DockingClamp, VesselModuleInterface};
+use dudes_in_space_api::utils::utils::Float;
+use dudes_in_space_api::vessel::{DockingClamp, RadarSpec, VesselModuleInterface};
 use dyn_serde::{
     DynDeserializeSeed, DynDeserializeSeedVault, DynSerialize, VecSeed, from_intermediate_seed,
 };
 use dyn_serde_macro::DeserializeSeedXXX;
-use rand::rng;
+use rand::{Rng, RngCore};
 use serde::{Deserialize, Serialize};
 use serde_intermediate::{Intermediate, to_intermediate};
 use std::error::Error;
 use std::ops::Deref;
 use std::rc::Rc;
+use std::time::Duration;
+use uuid::Uuid;
 
 static TYPE_ID: &str = "Assembler";
 static CAPABILITIES: &[ModuleCapability] = &[
@@ -22,6 +30,8 @@ static CAPABILITIES: &[ModuleCapability] = &[
     ModuleCapability::ItemStorage,
     ModuleCapability::PersonnelRoom,
 ];
+/// kilowatts
+static POWER_DRAW: Float = 50.;
 
 #[derive(Debug, Serialize, DeserializeSeedXXX)]
 #[deserialize_seed_xxx(seed = crate::modules::assembler::AssemblerStateSeed::<'context>)]
@@ -33,6 +43,8 @@ pub enum AssemblerState {
         recipe_index: usize,
         deploy: bool,
         process_token: ProcessTokenMut,
+        /// operator work time spent on the active recipe so far
+        progress: Duration,
     },
 }
 
@@ -85,9 +97,9 @@ impl<'v, 'context> AssemblerSeed<'v, 'context> {
 }
 
 impl Assembler {
-    pub fn new(recipes: Vec<AssemblyRecipe>) -> Box<Self> {
+    pub fn new(recipes: Vec<AssemblyRecipe>, rng: &mut dyn RngCore) -> Box<Self> {
         Box::new(Self {
-            id: ModuleId::new_v4(),
+            id: uuid::Builder::from_random_bytes(rng.random()).into_uuid(),
             recipes,
             state: AssemblerState::Idle,
             storage: Default::default(),
@@ -118,9 +130,14 @@ enum AssemblerRequest {
 struct Console<'a> {
     id: PersonId,
     recipes: &'a [AssemblyRecipe],
+    /// for each recipe
+    unlocked: Vec<bool>,
     requests: Vec<AssemblerRequest>,
     state: &'a mut AssemblerState,
     storage: &'a mut ItemStorage,
+    /// id of a process started through the console this tick. Drawn up front because the
+    /// simulation rng is lent to the operator meanwhile
+    process_id: Uuid,
 }
 
 impl<'a> ModuleConsole for Console<'a> {
@@ -169,6 +186,14 @@ impl<'a> ModuleConsole for Console<'a> {
         Some(self)
     }
 
+    fn fabrication_console(&self) -> Option<&dyn FabricationConsole> {
+        None
+    }
+
+    fn fabrication_console_mut(&mut self) -> Option<&mut dyn FabricationConsole> {
+        None
+    }
+
     fn dockyard_console(&self) -> Option<&dyn DockyardConsole> {
         todo!()
     }
@@ -193,6 +218,14 @@ impl<'a> ModuleConsole for Console<'a> {
         todo!()
     }
 
+    fn contract_board_console(&self) -> Option<&dyn ContractBoardConsole> {
+        None
+    }
+
+    fn contract_board_console_mut(&mut self) -> Option<&mut dyn ContractBoardConsole> {
+        None
+    }
+
     fn storages(&self) -> &[ItemStorage] {
         todo!()
     }
@@ -210,11 +243,11 @@ impl<'a> ModuleConsole for Console<'a> {
     }
 
     fn docking_clamps(&self) -> &[DockingClamp] {
-        todo!()
+        &[]
     }
 
     fn docking_clamps_mut(&mut self) -> &mut [DockingClamp] {
-        todo!()
+        &mut []
     }
 }
 
@@ -222,13 +255,20 @@ impl<'a> AssemblyConsole for Console<'a> {
     fn recipe_by_output_capability(&self, capability: ModuleCapability) -> Option<usize> {
         self.recipes
             .iter()
-            .position(|recipe| recipe.output_capabilities().contains(&capability))
+            .enumerate()
+            .position(|(index, recipe)| {
+                self.unlocked[index] && recipe.output_capabilities().contains(&capability)
+            })
     }
 
     fn recipe_output_capabilities(&self, index: usize) -> &[ModuleCapability] {
         self.recipes[index].output_capabilities()
     }
 
+    fn is_recipe_unlocked(&self, index: usize) -> bool {
+        self.unlocked[index]
+    }
+
     fn has_resources_for_recipe(&self, index: usize) -> bool {
         self.storage
             .contains_for_input(self.recipes[index].input().clone())
@@ -242,12 +282,20 @@ impl<'a> AssemblyConsole for Console<'a> {
     }
 
     fn start(&mut self, index: usize, deploy: bool) -> Option<ProcessToken> {
-        let (token, token_mut) = ProcessTokenMut::new();
+        if !self.unlocked[index] {
+            return None;
+        }
+        if !self.storage.try_consume(self.recipes[index].input().clone()) {
+            return None;
+        }
+
+        let (token, token_mut) = ProcessTokenMut::new(self.process_id);
 
         *self.state = AssemblerState::Assembling {
             recipe_index: index,
             deploy,
             process_token: token_mut,
+            progress: Duration::ZERO,
         };
 
         Some(token)
@@ -278,6 +326,9 @@ impl Module for Assembler {
     fn proceed(
         &mut self,
         this_vessel: &dyn VesselModuleInterface,
+        rng: &mut dyn RngCore,
+        clock: &SimulationClock,
+        item_registry: &ItemRegistry,
         process_token_context: &ProcessTokenContext,
         decider_vault: &ObjectiveDeciderVault,
         logger: &mut dyn Logger,
@@ -285,14 +336,20 @@ impl Module for Assembler {
         let mut console = Console {
             id: self.id,
             recipes: &self.recipes,
+            unlocked: self
+                .recipes
+                .iter()
+                .map(|recipe| !this_vessel.is_module_locked(&recipe.output_type_id()))
+                .collect(),
             requests: vec![],
             state: &mut self.state,
             storage: &mut self.storage,
+            process_id: uuid::Builder::from_random_bytes(rng.random()).into_uuid(),
         };
 
         if let Some(operator) = &mut self.operator {
             operator.proceed(
-                &mut rng(),
+                rng,
                 &mut console,
                 this_vessel.console(),
                 process_token_context,
@@ -306,41 +363,86 @@ impl Module for Assembler {
                 AssemblerRequest::SetRecipe(_) => {
                     todo!()
                 }
-                AssemblerRequest::Interact => match &self.state {
+                AssemblerRequest::Interact => match &mut self.state {
                     AssemblerState::Idle => todo!(),
                     AssemblerState::Assembling {
                         recipe_index,
                         deploy,
                         process_token,
+                        progress,
                     } => {
                         let active_recipe = &self.recipes[*recipe_index];
 
-                        let ok = self.storage.try_consume(active_recipe.input().clone());
-                        assert!(ok);
+                        *progress += clock.tick_duration();
+                        if *progress < active_recipe.duration() {
+                            continue;
+                        }
 
                         if *deploy {
-                            this_vessel.add_module(active_recipe.create());
-                            self.state = AssemblerState::Idle;
+                            this_vessel.add_module(active_recipe.create(rng));
+                            process_token.mark_completed(process_token_context);
                         } else {
                             let mut storage_modules = this_vessel
                                 .console()
                                 .modules_with_cap(ModuleCapability::ModuleStorage);
-                            assert!(!storage_modules.is_empty());
-                            assert!(!storage_modules[0].module_storages().is_empty());
-                            let storage = &mut storage_modules[0].module_storages_mut()[0];
-                            assert!(storage.has_space());
-                            let ok = storage.add(active_recipe.create());
-                            assert!(ok);
-                            self.state = AssemblerState::Idle;
+                            let storage = storage_modules
+                                .iter_mut()
+                                .flat_map(|module| module.module_storages_mut())
+                                .find(|storage| storage.has_space());
+
+                            match storage {
+                                Some(storage) => {
+                                    let ok = storage.add(active_recipe.create(rng));
+                                    assert!(ok);
+                                    process_token.mark_completed(process_token_context);
+                                }
+                                None => {
+                                    // nowhere to put the module, so the input is given back. With
+                                    // no room for the input either the assembler waits for space
+                                    // so that nothing is lost
+                                    if !self
+                                        .storage
+                                        .try_add_items(active_recipe.input().clone(), item_registry)
+                                    {
+                                        continue;
+                                    }
+                                    process_token.mark_failed(process_token_context);
+                                }
+                            }
                         }
+                        self.state = AssemblerState::Idle;
                     }
                 },
             }
         }
     }
 
-    fn recipes(&self) -> Vec<Recipe> {
-        vec![]
+    fn thrust(&self) -> Float {
+        0.
+    }
+
+    fn engine_energy_per_meter(&self) -> Float {
+        0.
+    }
+
+    fn power_draw(&self) -> Float {
+        POWER_DRAW
+    }
+
+    fn power_output(&self) -> Float {
+        0.
+    }
+
+    fn radar(&self) -> Option<RadarSpec> {
+        None
+    }
+
+    fn mining_laser(&self) -> Option<MiningLaserSpec> {
+        None
+    }
+
+    fn recipes(&self) -> &[Recipe] {
+        &[]
     }
 
     fn assembly_recipes(&self) -> &[AssemblyRecipe] {
@@ -360,12 +462,12 @@ impl Module for Assembler {
         }
     }
 
-    fn insert_person(&mut self, person: Person) -> bool {
+    fn insert_person(&mut self, person: Person) -> Result<(), Person> {
         if self.operator.is_none() {
             self.operator = Some(person);
-            true
+            Ok(())
         } else {
-            false
+            Err(person)
         }
     }
 
@@ -380,12 +482,16 @@ impl Module for Assembler {
             .unwrap_or(false)
     }
 
+    fn person_ids(&self) -> Vec<PersonId> {
+        self.operator.iter().map(|p| p.id()).collect()
+    }
+
     fn storages(&self) -> &[ItemStorage] {
-        todo!()
+        std::slice::from_ref(&self.storage)
     }
 
     fn storages_mut(&mut self) -> &mut [ItemStorage] {
-        todo!()
+        std::slice::from_mut(&mut self.storage)
     }
 
     fn module_storages(&self) -> &[ModuleStorage] {
@@ -397,15 +503,27 @@ impl Module for Assembler {
     }
 
     fn docking_clamps(&self) -> &[DockingClamp] {
-        todo!()
+        &[]
+    }
+
+    fn docking_clamps_mut(&mut self) -> &mut [DockingClamp] {
+        &mut []
     }
 
     fn trading_console(&self) -> Option<&dyn TradingConsole> {
-        todo!()
+        None
     }
 
     fn trading_console_mut(&mut self) -> Option<&mut dyn TradingConsole> {
-        todo!()
+        None
+    }
+
+    fn contract_board_console(&self) -> Option<&dyn ContractBoardConsole> {
+        None
+    }
+
+    fn contract_board_console_mut(&mut self) -> Option<&mut dyn ContractBoardConsole> {
+        None
     }
 }
 
@@ -475,9 +593,9 @@ mod tests {
 
     #[test]
     fn serde() {
-        let mut assembler = Assembler::new(vec![]);
+        let mut assembler = Assembler::new(vec![], &mut rng());
         assert!(assembler.can_insert_person());
-        assert!(assembler.insert_person(Person::random(&mut rng())));
+        assert!(assembler.insert_person(Person::random(&mut rng())).is_ok());
         assert!(!assembler.can_insert_person());
 
         let intermediate = to_intermediate(&assembler).unwrap();
@@ -1,20 +1,28 @@
-use dudes_in_space_api::item::ItemStorage;
-use dudes_in_space_api::module::{AssemblyConsole, DockyardConsole, Module, ModuleCapability, ModuleConsole, ModuleId, ModuleStorage, ModuleStorageSeed, ModuleTypeId, PackageId, ProcessToken, ProcessTokenContext, ProcessTokenMut, ProcessTokenMutSeed, TradingAdminConsole, TradingConsole};
+use dudes_in_space_api::environment::{MiningLaserSpec, SimulationClock};
+use dudes_in_space_api::item::{ItemRegistry, ItemStorage};
+use dudes_in_space_api::module::{
+    AssemblyConsole, ContractBoardConsole, DockyardConsole, FabricationConsole, Module,
+    ModuleCapability, ModuleConsole, ModuleId, ModuleStorage, ModuleStorageSeed, ModuleTypeId,
+    PackageId, ProcessToken, ProcessTokenContext, ProcessTokenMut, ProcessTokenMutSeed,
+    TradingAdminConsole, TradingConsole,
+};
 use dudes_in_space_api::person::{DynObjective, Logger, ObjectiveDeciderVault, Person, PersonId, PersonSeed};
 use dudes_in_space_api::recipe::{AssemblyRecipe, InputRecipe, ModuleFactory, Recipe};
 use dudes_in_space_api::utils::tagged_option::TaggedOptionSeed;
-use dudes_in_space_api::vessel::{DockingClamp, DockingClampSeed, Vessel, VesselModuleInterface};
+use dudes_in_space_api::utils::utils::Float;
+use dudes_in_space_api::vessel::{DockingClamp, DockingClampSeed, RadarSpec, Vessel, VesselModuleInterface};
 use dyn_serde::{
     DynDeserializeSeed, DynDeserializeSeedVault, DynSerialize, TypeId, from_intermediate_seed,
 };
 use dyn_serde_macro::DeserializeSeedXXX;
-use rand::rng;
+use rand::{Rng, RngCore};
 use serde::{Deserialize, Serialize};
 use serde_intermediate::{Intermediate, to_intermediate};
 use std::collections::BTreeSet;
 use std::error::Error;
 use std::fmt::Debug;
 use std::rc::Rc;
+use uuid::Uuid;
 
 static TYPE_ID: &str = "Dockyard";
 static FACTORY_TYPE_ID: &str = "DockyardFactory";
@@ -24,6 +32,9 @@ static CAPABILITIES: &[ModuleCapability] = &[
     ModuleCapability::PersonnelRoom,
     ModuleCapability::DockingClamp,
 ];
+static MODULE_STORAGE_CAPACITY: usize = 16;
+/// kilowatts
+static POWER_DRAW: Float = 200.;
 
 #[derive(Debug, Serialize, DeserializeSeedXXX)]
 #[deserialize_seed_xxx(seed = crate::modules::dockyard::DockyardStateSeed::<'context>)]
@@ -66,11 +77,11 @@ pub struct Dockyard {
 }
 
 impl Dockyard {
-    fn new() -> Self {
+    pub(crate) fn new(rng: &mut dyn RngCore) -> Self {
         Self {
-            id: ModuleId::new_v4(),
+            id: uuid::Builder::from_random_bytes(rng.random()).into_uuid(),
             state: DockyardState::Idle,
-            module_storage: Default::default(),
+            module_storage: ModuleStorage::new(MODULE_STORAGE_CAPACITY),
             docking_clamp: Default::default(),
             operator: None,
         }
@@ -92,7 +103,7 @@ impl<'v, 'context> DockyardSeed<'v, 'context> {
         context: &'context ProcessTokenContext,
     ) -> Self {
         Self {
-            module_storage_seed: ModuleStorageSeed::new(module_vault),
+            module_storage_seed: ModuleStorageSeed::new(module_vault, MODULE_STORAGE_CAPACITY),
             docking_clamp_seed: DockingClampSeed::new(module_vault),
             person_seed: TaggedOptionSeed::new(PersonSeed::new(objective_vault)),
             state_seed: DockyardStateSeed::new(context),
@@ -120,6 +131,9 @@ struct Console<'a> {
     requests: Vec<DockyardRequest>,
     state: &'a mut DockyardState,
     module_storage: &'a mut ModuleStorage,
+    docking_clamp: &'a mut DockingClamp,
+    /// id of the build process if the operator starts one this tick
+    process_id: Uuid,
 }
 
 impl<'a> ModuleConsole for Console<'a> {
@@ -171,6 +185,14 @@ impl<'a> ModuleConsole for Console<'a> {
         todo!()
     }
 
+    fn fabrication_console(&self) -> Option<&dyn FabricationConsole> {
+        None
+    }
+
+    fn fabrication_console_mut(&mut self) -> Option<&mut dyn FabricationConsole> {
+        None
+    }
+
     fn dockyard_console(&self) -> Option<&dyn DockyardConsole> {
         todo!()
     }
@@ -195,6 +217,14 @@ impl<'a> ModuleConsole for Console<'a> {
         todo!()
     }
 
+    fn contract_board_console(&self) -> Option<&dyn ContractBoardConsole> {
+        None
+    }
+
+    fn contract_board_console_mut(&mut self) -> Option<&mut dyn ContractBoardConsole> {
+        None
+    }
+
     fn storages(&self) -> &[ItemStorage] {
         todo!()
     }
@@ -212,17 +242,17 @@ impl<'a> ModuleConsole for Console<'a> {
     }
 
     fn docking_clamps(&self) -> &[DockingClamp] {
-        todo!()
+        std::slice::from_ref(self.docking_clamp)
     }
 
     fn docking_clamps_mut(&mut self) -> &mut [DockingClamp] {
-        todo!()
+        std::slice::from_mut(self.docking_clamp)
     }
 }
 
 impl<'a> DockyardConsole for Console<'a> {
     fn start(&mut self, modules: BTreeSet<ModuleId>) -> Option<ProcessToken> {
-        let (token, token_mut) = ProcessTokenMut::new();
+        let (token, token_mut) = ProcessTokenMut::new(self.process_id);
         *self.state = DockyardState::Building {
             modules,
             process_token: token_mut,
@@ -251,6 +281,9 @@ impl Module for Dockyard {
     fn proceed(
         &mut self,
         this_vessel: &dyn VesselModuleInterface,
+        rng: &mut dyn RngCore,
+        clock: &SimulationClock,
+        item_registry: &ItemRegistry,
         process_token_context: &ProcessTokenContext,
         decider_vault: &ObjectiveDeciderVault,
         logger: &mut dyn Logger,
@@ -260,11 +293,13 @@ impl Module for Dockyard {
             requests: vec![],
             state: &mut self.state,
             module_storage: &mut self.module_storage,
+            docking_clamp: &mut self.docking_clamp,
+            process_id: uuid::Builder::from_random_bytes(rng.random()).into_uuid(),
         };
 
         if let Some(operator) = &mut self.operator {
             operator.proceed(
-                &mut rng(),
+                rng,
                 &mut person_interface,
                 this_vessel.console(),
                 process_token_context,
@@ -290,6 +325,7 @@ impl Module for Dockyard {
                                 this_vessel.owner(),
                                 (0., 0.).into(),
                                 modules,
+                                rng,
                             ));
                             assert!(ok);
                             process_token.mark_completed(process_token_context);
@@ -303,12 +339,36 @@ impl Module for Dockyard {
         }
     }
 
-    fn recipes(&self) -> Vec<Recipe> {
-        todo!()
+    fn thrust(&self) -> Float {
+        0.
+    }
+
+    fn engine_energy_per_meter(&self) -> Float {
+        0.
+    }
+
+    fn power_draw(&self) -> Float {
+        POWER_DRAW
+    }
+
+    fn power_output(&self) -> Float {
+        0.
+    }
+
+    fn radar(&self) -> Option<RadarSpec> {
+        None
+    }
+
+    fn mining_laser(&self) -> Option<MiningLaserSpec> {
+        None
+    }
+
+    fn recipes(&self) -> &[Recipe] {
+        &[]
     }
 
     fn assembly_recipes(&self) -> &[AssemblyRecipe] {
-        todo!()
+        &[]
     }
 
     fn extract_person(&mut self, id: PersonId) -> Option<Person> {
@@ -324,12 +384,12 @@ impl Module for Dockyard {
         }
     }
 
-    fn insert_person(&mut self, person: Person) -> bool {
+    fn insert_person(&mut self, person: Person) -> Result<(), Person> {
         if self.operator.is_none() {
             self.operator = Some(person);
-            true
+            Ok(())
         } else {
-            false
+            Err(person)
         }
     }
 
@@ -344,12 +404,16 @@ impl Module for Dockyard {
             .unwrap_or(false)
     }
 
+    fn person_ids(&self) -> Vec<PersonId> {
+        self.operator.iter().map(|p| p.id()).collect()
+    }
+
     fn storages(&self) -> &[ItemStorage] {
-        todo!()
+        &[]
     }
 
     fn storages_mut(&mut self) -> &mut [ItemStorage] {
-        todo!()
+        &mut []
     }
 
     fn module_storages(&self) -> &[ModuleStorage] {
@@ -361,15 +425,27 @@ impl Module for Dockyard {
     }
 
     fn docking_clamps(&self) -> &[DockingClamp] {
-        todo!()
+        std::slice::from_ref(&self.docking_clamp)
+    }
+
+    fn docking_clamps_mut(&mut self) -> &mut [DockingClamp] {
+        std::slice::from_mut(&mut self.docking_clamp)
     }
 
     fn trading_console(&self) -> Option<&dyn TradingConsole> {
-        todo!()
+        None
     }
 
     fn trading_console_mut(&mut self) -> Option<&mut dyn TradingConsole> {
-        todo!()
+        None
+    }
+
+    fn contract_board_console(&self) -> Option<&dyn ContractBoardConsole> {
+        None
+    }
+
+    fn contract_board_console_mut(&mut self) -> Option<&mut dyn ContractBoardConsole> {
+        None
     }
 }
 
@@ -388,11 +464,11 @@ impl DynSerialize for DockyardFactory {
 
 impl ModuleFactory for DockyardFactory {
     fn output_type_id(&self) -> ModuleTypeId {
-        todo!()
+        TYPE_ID.to_string()
     }
 
-    fn create(&self, recipe: &InputRecipe) -> Box<dyn Module> {
-        Box::new(Dockyard::new())
+    fn create(&self, recipe: &InputRecipe, rng: &mut dyn RngCore) -> Box<dyn Module> {
+        Box::new(Dockyard::new(rng))
     }
 
     fn output_capabilities(&self) -> &[ModuleCapability] {
@@ -454,3 +530,360 @@ impl DynDeserializeSeed<dyn ModuleFactory> for DockyardFactoryDynSeed {
         Ok(r)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::MODULE_STORAGE_CAPACITY;
+    use crate::items::register_items;
+    use crate::modules::{
+        Dockyard, Reactor, ShuttleFactory, register_module_factories, register_modules,
+    };
+    use crate::objectives::register_objectives;
+    use dudes_in_space_api::environment::{Environment, EnvironmentSeed};
+    use dudes_in_space_api::item::{Item, ItemCount, ItemRegistry};
+    use dudes_in_space_api::module::{Module, ModuleConsole, ModuleId, ProcessTokenContext};
+    use dudes_in_space_api::person::{
+        Awareness, Boldness, DynObjective, Gender, Logger, Morale, Objective, ObjectiveDecider,
+        ObjectiveDeciderVault, ObjectiveStatus, Passion, Person, PersonId, PersonLogger, Severity,
+    };
+    use dudes_in_space_api::recipe::{InputRecipe, ModuleFactory};
+    use dudes_in_space_api::research::TechTree;
+    use dudes_in_space_api::utils::simulation_rng::SimulationRng;
+    use dudes_in_space_api::vessel::{
+        DockedVesselPolicy, NavTarget, Vessel, VesselConsole, VesselId,
+    };
+    use dyn_serde::{DynSerialize, TypeId};
+    use serde::Serialize;
+    use serde::de::DeserializeSeed;
+    use serde_intermediate::{Intermediate, to_intermediate};
+    use std::error::Error;
+    use std::rc::Rc;
+
+    struct NoLogger;
+
+    impl Logger for NoLogger {
+        fn log(&mut self, _: &PersonId, _: Severity, _: String) {}
+    }
+
+    /// works at the module it is in every tick
+    #[derive(Debug, Serialize)]
+    struct Operate;
+
+    impl Objective for Operate {
+        type Error = std::fmt::Error;
+
+        fn pursue(
+            &mut self,
+            this_module: &mut dyn ModuleConsole,
+            _: &dyn VesselConsole,
+            _: &ProcessTokenContext,
+            _: PersonLogger,
+        ) -> Result<ObjectiveStatus, Self::Error> {
+            this_module.interact();
+            Ok(ObjectiveStatus::InProgress)
+        }
+    }
+
+    impl DynSerialize for Operate {
+        fn type_id(&self) -> TypeId {
+            "Operate".to_string()
+        }
+
+        fn serialize(&self) -> Result<Intermediate, Box<dyn Error>> {
+            to_intermediate(self).map_err(|e| e.into())
+        }
+    }
+
+    struct OperateDecider;
+
+    impl ObjectiveDecider for OperateDecider {
+        fn consider(
+            &self,
+            _: PersonId,
+            _: u8,
+            _: Gender,
+            _: &[Passion],
+            _: Morale,
+            _: Boldness,
+            _: Awareness,
+        ) -> Option<Box<dyn DynObjective>> {
+            Some(Box::new(Operate))
+        }
+    }
+
+    fn proceed(
+        environment: &mut Environment,
+        item_registry: &ItemRegistry,
+        decider_vault: &ObjectiveDeciderVault,
+    ) {
+        environment.proceed(
+            item_registry,
+            &TechTree::new(),
+            &ProcessTokenContext::new(),
+            decider_vault,
+            &mut NoLogger,
+        )
+    }
+
+    /// reactor with 100 units of fuel
+    fn reactor(item_registry: &ItemRegistry, rng: &mut SimulationRng) -> Box<Reactor> {
+        let mut reactor = Reactor::new(rng);
+        assert!(
+            reactor.storages_mut()[0]
+                .try_add_item(Item::new("fuel".to_string(), 100), item_registry)
+        );
+        reactor
+    }
+
+    fn fueled_shuttle(item_registry: &ItemRegistry, rng: &mut SimulationRng) -> Box<dyn Module> {
+        let mut shuttle = ShuttleFactory {}.create(&InputRecipe::try_from(vec![]).unwrap(), rng);
+        assert!(
+            shuttle.storages_mut()[0]
+                .try_add_item(Item::new("fuel".to_string(), 10), item_registry)
+        );
+        shuttle
+    }
+
+    /// station with a dockyard at the origin, a manned shuttle and a second vessel in docking range
+    /// and a third vessel far away
+    fn station_with_traffic(
+        item_registry: &ItemRegistry,
+    ) -> (Environment, ModuleId, Vec<VesselId>) {
+        let mut rng = SimulationRng::new(0);
+        let dockyard = Box::new(Dockyard::new(&mut rng));
+        let dockyard_id = dockyard.id();
+        let station = Vessel::new(
+            PersonId::nil(),
+            (0., 0.).into(),
+            vec![dockyard, reactor(item_registry, &mut rng)],
+            &mut rng,
+        );
+        let mut shuttle = fueled_shuttle(item_registry, &mut rng);
+        assert!(
+            shuttle
+                .insert_person(Person::random(&mut SimulationRng::new(0)))
+                .is_ok()
+        );
+        let shuttle = Vessel::new(PersonId::nil(), (5., 0.).into(), vec![shuttle], &mut rng);
+        let near = Vessel::new(
+            PersonId::nil(),
+            (0., 8.).into(),
+            vec![fueled_shuttle(item_registry, &mut rng)],
+            &mut rng,
+        );
+        let far = Vessel::new(
+            PersonId::nil(),
+            (500., 0.).into(),
+            vec![fueled_shuttle(item_registry, &mut rng)],
+            &mut rng,
+        );
+        let ids = vec![station.id(), shuttle.id(), near.id(), far.id()];
+        let environment = Environment::new(rng, vec![station, shuttle, near, far], vec![]);
+        (environment, dockyard_id, ids)
+    }
+
+    fn docked(environment: &Environment) -> Option<VesselId> {
+        let dockyard = environment.vessels()[0].modules().next().unwrap();
+        dockyard.docking_clamps()[0]
+            .vessel_docked()
+            .map(|vessel| vessel.id())
+    }
+
+    fn in_space(environment: &Environment, id: VesselId) -> bool {
+        environment.vessels().iter().any(|vessel| vessel.id() == id)
+    }
+
+    #[test]
+    fn docks_and_undocks_vessels() {
+        let item_registry = register_items(ItemRegistry::new());
+        let decider_vault = ObjectiveDeciderVault::new().with(OperateDecider);
+        let (mut environment, dockyard_id, ids) = station_with_traffic(&item_registry);
+        let (station_id, shuttle_id, near_id, far_id) = (ids[0], ids[1], ids[2], ids[3]);
+
+        environment.vessels()[0].dock(dockyard_id, shuttle_id);
+        environment.vessels()[0].dock(dockyard_id, far_id);
+        proceed(&mut environment, &item_registry, &decider_vault);
+        assert_eq!(docked(&environment), Some(shuttle_id));
+        assert!(!in_space(&environment, shuttle_id));
+        // out of docking range
+        assert!(in_space(&environment, far_id));
+        {
+            let dockyard = environment.vessels()[0].modules().next().unwrap();
+            let shuttle = dockyard.docking_clamps()[0].vessel_docked().unwrap();
+            assert_eq!(shuttle.carrier(), Some(station_id));
+            assert_eq!((shuttle.pos() - environment.vessels()[0].pos()).len(), 0.);
+        }
+
+        // the only clamp is occupied
+        environment.vessels()[0].dock(dockyard_id, near_id);
+        proceed(&mut environment, &item_registry, &decider_vault);
+        assert_eq!(docked(&environment), Some(shuttle_id));
+        assert!(in_space(&environment, near_id));
+
+        environment.vessels()[0].undock(dockyard_id, shuttle_id);
+        proceed(&mut environment, &item_registry, &decider_vault);
+        assert_eq!(docked(&environment), None);
+        let shuttle = environment
+            .vessels()
+            .iter()
+            .find(|vessel| vessel.id() == shuttle_id)
+            .unwrap();
+        assert_eq!(shuttle.carrier(), None);
+        assert_eq!((shuttle.pos() - environment.vessels()[0].pos()).len(), 0.);
+        assert_eq!(shuttle.modules().next().unwrap().person_ids().len(), 1);
+
+        // the clamp is free again
+        shuttle.dock_to(station_id);
+        proceed(&mut environment, &item_registry, &decider_vault);
+        assert_eq!(docked(&environment), Some(shuttle_id));
+        assert!(!in_space(&environment, shuttle_id));
+    }
+
+    /// station carrying a vessel which carries another one. Every vessel has a dockyard and a
+    /// reactor, so it burns fuel whenever it is proceeded
+    fn nested(
+        station_policy: DockedVesselPolicy,
+        carrier_policy: DockedVesselPolicy,
+        item_registry: &ItemRegistry,
+    ) -> Environment {
+        let mut rng = SimulationRng::new(0);
+        let mut vessel = Vessel::new(
+            PersonId::nil(),
+            (0., 0.).into(),
+            vec![
+                Box::new(Dockyard::new(&mut rng)),
+                reactor(item_registry, &mut rng),
+            ],
+            &mut rng,
+        );
+        for policy in [carrier_policy, station_policy] {
+            let mut dockyard = Box::new(Dockyard::new(&mut rng));
+            dockyard.docking_clamps_mut()[0].set_policy(policy);
+            assert!(dockyard.docking_clamps_mut()[0].dock(vessel));
+            vessel = Vessel::new(
+                PersonId::nil(),
+                (0., 0.).into(),
+                vec![dockyard, reactor(item_registry, &mut rng)],
+                &mut rng,
+            );
+        }
+        Environment::new(rng, vec![vessel], vec![])
+    }
+
+    /// fuel left in the station and in every vessel docked below it
+    fn fuel_by_depth(vessel: &Vessel, item_registry: &ItemRegistry, fuel: &mut Vec<ItemCount>) {
+        fuel.push(vessel.fuel(item_registry));
+        let dockyard = vessel.modules().next().unwrap();
+        if let Some(docked) = dockyard.docking_clamps()[0].vessel_docked() {
+            fuel_by_depth(docked, item_registry, fuel)
+        }
+    }
+
+    fn burning(
+        station_policy: DockedVesselPolicy,
+        carrier_policy: DockedVesselPolicy,
+    ) -> Vec<bool> {
+        let item_registry = register_items(ItemRegistry::new());
+        let decider_vault = ObjectiveDeciderVault::new();
+        let mut environment = nested(station_policy, carrier_policy, &item_registry);
+        for _ in 0..10 {
+            proceed(&mut environment, &item_registry, &decider_vault);
+        }
+        let mut fuel = Vec::new();
+        fuel_by_depth(&environment.vessels()[0], &item_registry, &mut fuel);
+        fuel.into_iter().map(|fuel| fuel < 100).collect()
+    }
+
+    #[test]
+    fn proceeds_docked_vessels_recursively() {
+        use DockedVesselPolicy::*;
+        assert_eq!(burning(Proceed, Proceed), vec![true, true, true]);
+        assert_eq!(burning(Proceed, Freeze), vec![true, true, false]);
+        assert_eq!(burning(Freeze, Proceed), vec![true, false, false]);
+    }
+
+    #[test]
+    fn docked_vessel_keeps_destination_after_save() {
+        let mut rng = SimulationRng::new(0);
+        let item_registry = register_items(ItemRegistry::new());
+        let mut dockyard = Box::new(Dockyard::new(&mut rng));
+        assert!(dockyard.docking_clamps_mut()[0].dock(Vessel::new(
+            PersonId::nil(),
+            (0., 0.).into(),
+            vec![fueled_shuttle(&item_registry, &mut rng)],
+            &mut rng,
+        )));
+        let station = Vessel::new(
+            PersonId::nil(),
+            (0., 0.).into(),
+            vec![dockyard, reactor(&item_registry, &mut rng)],
+            &mut rng,
+        );
+        let mut environment = Environment::new(rng, vec![station], vec![]);
+        {
+            let dockyard = environment.vessels()[0].modules().next().unwrap();
+            let shuttle = dockyard.docking_clamps()[0].vessel_docked().unwrap();
+            shuttle.set_destination((100., 0.).into());
+        }
+        let process_token_context = Rc::new(ProcessTokenContext::new());
+        environment.proceed(
+            &item_registry,
+            &TechTree::new(),
+            &process_token_context,
+            &ObjectiveDeciderVault::new(),
+            &mut NoLogger,
+        );
+
+        let objective_vault = register_objectives(Default::default()).into_rc();
+        let module_vault = register_modules(
+            Default::default(),
+            register_module_factories(Default::default()).into_rc(),
+            objective_vault.clone(),
+            process_token_context,
+        );
+        let json = serde_json::to_string(&environment).unwrap();
+        let reloaded = EnvironmentSeed::new(&module_vault, &objective_vault)
+            .deserialize(&mut serde_json::Deserializer::from_str(&json))
+            .unwrap();
+
+        let dockyard = reloaded.vessels()[0].modules().next().unwrap();
+        let shuttle = dockyard.docking_clamps()[0].vessel_docked().unwrap();
+        assert!(matches!(
+            shuttle.nav_target(),
+            Some(NavTarget::Point(point)) if (point - (100., 0.).into()).len() == 0.
+        ));
+    }
+
+    #[test]
+    fn old_save_gets_dockyard_storage_capacity() {
+        let mut rng = SimulationRng::new(0);
+        let station = Vessel::new(
+            PersonId::nil(),
+            (0., 0.).into(),
+            vec![Box::new(Dockyard::new(&mut rng))],
+            &mut rng,
+        );
+        let environment = Environment::new(rng, vec![station], vec![]);
+        let json = serde_json::to_string(&environment).unwrap();
+        let capacity = format!(",\"capacity\":{}", MODULE_STORAGE_CAPACITY);
+        assert!(json.contains(&capacity));
+        let json = json.replace(&capacity, "");
+
+        let objective_vault = register_objectives(Default::default()).into_rc();
+        let module_vault = register_modules(
+            Default::default(),
+            register_module_factories(Default::default()).into_rc(),
+            objective_vault.clone(),
+            Rc::new(ProcessTokenContext::new()),
+        );
+        let reloaded = EnvironmentSeed::new(&module_vault, &objective_vault)
+            .deserialize(&mut serde_json::Deserializer::from_str(&json))
+            .unwrap();
+
+        let dockyard = reloaded.vessels()[0].modules().next().unwrap();
+        assert_eq!(
+            dockyard.module_storages()[0].capacity(),
+            MODULE_STORAGE_CAPACITY
+        );
+    }
+}
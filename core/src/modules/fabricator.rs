@@ -0,0 +1,631 @@
+use crate::CORE_PACKAGE_ID;
+use dudes_in_space_api::environment::{MiningLaserSpec, SimulationClock};
+use dudes_in_space_api::item::{ItemId, ItemMass, ItemRegistry, ItemStorage, ItemVolume};
+use dudes_in_space_api::module::{
+    AssemblyConsole, ContractBoardConsole, DockyardConsole, FabricationConsole, Module,
+    ModuleCapability, ModuleConsole, ModuleId, ModuleStorage, ModuleTypeId, PackageId,
+    ProcessToken, ProcessTokenContext, ProcessTokenMut, ProcessTokenMutSeed, TradingAdminConsole,
+    TradingConsole,
+};
+use dudes_in_space_api::person::{
+    DynObjective, Logger, ObjectiveDeciderVault, Person, PersonId, PersonSeed,
+};
+use dudes_in_space_api::recipe::{AssemblyRecipe, InputRecipe, ModuleFactory, Recipe};
+use dudes_in_space_api::utils::tagged_option::TaggedOptionSeed;
+use dudes_in_space_api::utils::utils::Float;
+use dudes_in_space_api::vessel::{DockingClamp, RadarSpec, VesselModuleInterface};
+use dyn_serde::{
+    DynDeserializeSeed, DynDeserializeSeedVault, DynSerialize, TypeId, from_intermediate_seed,
+};
+use dyn_serde_macro::DeserializeSeedXXX;
+use rand::{Rng, RngCore};
+use serde::{Deserialize, Serialize};
+use serde_intermediate::{Intermediate, to_intermediate};
+use std::error::Error;
+use std::rc::Rc;
+use std::time::Duration;
+use uuid::Uuid;
+
+static TYPE_ID: &str = "Fabricator";
+static FACTORY_TYPE_ID: &str = "FabricatorFactory";
+static CAPABILITIES: &[ModuleCapability] = &[
+    ModuleCapability::Fabrication,
+    ModuleCapability::ItemStorage,
+    ModuleCapability::PersonnelRoom,
+];
+static PRIMARY_CAPABILITIES: &[ModuleCapability] = &[ModuleCapability::Fabrication];
+static STORAGE_VOLUME_CAPACITY: ItemVolume = 1000;
+static STORAGE_MASS_CAPACITY: ItemMass = 5000;
+/// kilowatts
+static POWER_DRAW: Float = 30.;
+
+#[derive(Debug, Serialize, DeserializeSeedXXX)]
+#[deserialize_seed_xxx(seed = crate::modules::fabricator::FabricatorStateSeed::<'context>)]
+#[serde(tag = "tp")]
+enum FabricatorState {
+    Idle,
+    #[deserialize_seed_xxx(seeds = [(process_token, self.seed.seed.process_token_seed)])]
+    Fabricating {
+        recipe_index: usize,
+        process_token: ProcessTokenMut,
+        /// operator work time still needed before the output is ready
+        remaining: Duration,
+    },
+}
+
+#[derive(Clone)]
+struct FabricatorStateSeed<'context> {
+    process_token_seed: ProcessTokenMutSeed<'context>,
+}
+
+impl<'context> FabricatorStateSeed<'context> {
+    pub fn new(context: &'context ProcessTokenContext) -> Self {
+        Self {
+            process_token_seed: ProcessTokenMutSeed::new(context),
+        }
+    }
+}
+
+/// Turns items into other items (ore into steel, steel into parts) using the items of its own storage
+#[derive(Debug, Serialize, DeserializeSeedXXX)]
+#[deserialize_seed_xxx(seed = crate::modules::fabricator::FabricatorSeed::<'v, 'context>)]
+pub struct Fabricator {
+    id: ModuleId,
+    recipes: Vec<Recipe>,
+    #[deserialize_seed_xxx(seed = self.seed.state_seed)]
+    state: FabricatorState,
+    storage: ItemStorage,
+    #[serde(with = "dudes_in_space_api::utils::tagged_option")]
+    #[deserialize_seed_xxx(seed = self.seed.person_seed)]
+    operator: Option<Person>,
+}
+
+#[derive(Clone)]
+struct FabricatorSeed<'v, 'context> {
+    person_seed: TaggedOptionSeed<PersonSeed<'v>>,
+    state_seed: FabricatorStateSeed<'context>,
+}
+
+impl<'v, 'context> FabricatorSeed<'v, 'context> {
+    fn new(
+        objective_vault: &'v DynDeserializeSeedVault<dyn DynObjective>,
+        context: &'context ProcessTokenContext,
+    ) -> Self {
+        Self {
+            person_seed: TaggedOptionSeed::new(PersonSeed::new(objective_vault)),
+            state_seed: FabricatorStateSeed::new(context),
+        }
+    }
+}
+
+impl Fabricator {
+    pub fn new(recipes: Vec<Recipe>, rng: &mut dyn RngCore) -> Box<Self> {
+        Box::new(Self {
+            id: uuid::Builder::from_random_bytes(rng.random()).into_uuid(),
+            recipes,
+            state: FabricatorState::Idle,
+            storage: ItemStorage::with_capacity(STORAGE_VOLUME_CAPACITY, STORAGE_MASS_CAPACITY),
+            operator: None,
+        })
+    }
+}
+
+impl DynSerialize for Fabricator {
+    fn type_id(&self) -> TypeId {
+        TYPE_ID.to_string()
+    }
+
+    fn serialize(&self) -> Result<Intermediate, Box<dyn Error>> {
+        to_intermediate(self).map_err(|e| e.into())
+    }
+}
+
+enum FabricatorRequest {
+    Interact,
+}
+
+struct Console<'a> {
+    id: ModuleId,
+    recipes: &'a [Recipe],
+    requests: Vec<FabricatorRequest>,
+    state: &'a mut FabricatorState,
+    storage: &'a mut ItemStorage,
+    /// id of the fabrication process if the operator starts one this tick
+    process_id: Uuid,
+}
+
+impl<'a> ModuleConsole for Console<'a> {
+    fn id(&self) -> ModuleId {
+        self.id
+    }
+
+    fn package_id(&self) -> PackageId {
+        CORE_PACKAGE_ID.to_string()
+    }
+
+    fn capabilities(&self) -> &[ModuleCapability] {
+        CAPABILITIES
+    }
+
+    fn primary_capabilities(&self) -> &[ModuleCapability] {
+        PRIMARY_CAPABILITIES
+    }
+
+    fn interact(&mut self) -> bool {
+        match &*self.state {
+            FabricatorState::Idle => false,
+            FabricatorState::Fabricating { recipe_index, .. } => {
+                if *recipe_index < self.recipes.len() {
+                    self.requests.push(FabricatorRequest::Interact);
+                    true
+                } else {
+                    false
+                }
+            }
+        }
+    }
+
+    fn in_progress(&self) -> bool {
+        match self.state {
+            FabricatorState::Idle => false,
+            FabricatorState::Fabricating { .. } => true,
+        }
+    }
+
+    fn assembly_console(&self) -> Option<&dyn AssemblyConsole> {
+        None
+    }
+
+    fn assembly_console_mut(&mut self) -> Option<&mut dyn AssemblyConsole> {
+        None
+    }
+
+    fn fabrication_console(&self) -> Option<&dyn FabricationConsole> {
+        Some(self)
+    }
+
+    fn fabrication_console_mut(&mut self) -> Option<&mut dyn FabricationConsole> {
+        Some(self)
+    }
+
+    fn dockyard_console(&self) -> Option<&dyn DockyardConsole> {
+        None
+    }
+
+    fn dockyard_console_mut(&mut self) -> Option<&mut dyn DockyardConsole> {
+        None
+    }
+
+    fn trading_console(&self) -> Option<&dyn TradingConsole> {
+        None
+    }
+
+    fn trading_console_mut(&mut self) -> Option<&mut dyn TradingConsole> {
+        None
+    }
+
+    fn trading_admin_console(&self) -> Option<&dyn TradingAdminConsole> {
+        None
+    }
+
+    fn trading_admin_console_mut(&mut self) -> Option<&mut dyn TradingAdminConsole> {
+        None
+    }
+
+    fn contract_board_console(&self) -> Option<&dyn ContractBoardConsole> {
+        None
+    }
+
+    fn contract_board_console_mut(&mut self) -> Option<&mut dyn ContractBoardConsole> {
+        None
+    }
+
+    fn storages(&self) -> &[ItemStorage] {
+        std::slice::from_ref(self.storage)
+    }
+
+    fn storages_mut(&mut self) -> &mut [ItemStorage] {
+        std::slice::from_mut(self.storage)
+    }
+
+    fn module_storages(&self) -> &[ModuleStorage] {
+        &[]
+    }
+
+    fn module_storages_mut(&mut self) -> &mut [ModuleStorage] {
+        &mut []
+    }
+
+    fn docking_clamps(&self) -> &[DockingClamp] {
+        &[]
+    }
+
+    fn docking_clamps_mut(&mut self) -> &mut [DockingClamp] {
+        &mut []
+    }
+}
+
+impl<'a> FabricationConsole for Console<'a> {
+    fn recipe_by_output_item(&self, item: &ItemId) -> Option<usize> {
+        self.recipes
+            .iter()
+            .position(|recipe| recipe.output().contains(item))
+    }
+
+    fn has_resources_for_recipe(&self, index: usize) -> bool {
+        self.storage
+            .contains_for_input(self.recipes[index].input().clone())
+    }
+
+    fn active_recipe(&self) -> Option<usize> {
+        match &*self.state {
+            FabricatorState::Idle => None,
+            FabricatorState::Fabricating { recipe_index, .. } => Some(*recipe_index),
+        }
+    }
+
+    fn start(&mut self, index: usize) -> Option<ProcessToken> {
+        if self.in_progress() {
+            return None;
+        }
+
+        if !self
+            .storage
+            .try_consume(self.recipes[index].input().clone())
+        {
+            return None;
+        }
+
+        let (token, token_mut) = ProcessTokenMut::new(self.process_id);
+        *self.state = FabricatorState::Fabricating {
+            recipe_index: index,
+            process_token: token_mut,
+            remaining: self.recipes[index].duration(),
+        };
+        Some(token)
+    }
+
+    fn recipes(&self) -> &[Recipe] {
+        self.recipes
+    }
+}
+
+impl Module for Fabricator {
+    fn id(&self) -> ModuleId {
+        self.id
+    }
+
+    fn package_id(&self) -> PackageId {
+        CORE_PACKAGE_ID.to_string()
+    }
+
+    fn capabilities(&self) -> &[ModuleCapability] {
+        CAPABILITIES
+    }
+
+    fn primary_capabilities(&self) -> &[ModuleCapability] {
+        PRIMARY_CAPABILITIES
+    }
+
+    fn proceed(
+        &mut self,
+        this_vessel: &dyn VesselModuleInterface,
+        rng: &mut dyn RngCore,
+        clock: &SimulationClock,
+        item_registry: &ItemRegistry,
+        process_token_context: &ProcessTokenContext,
+        decider_vault: &ObjectiveDeciderVault,
+        logger: &mut dyn Logger,
+    ) {
+        let mut console = Console {
+            id: self.id,
+            recipes: &self.recipes,
+            requests: vec![],
+            state: &mut self.state,
+            storage: &mut self.storage,
+            process_id: uuid::Builder::from_random_bytes(rng.random()).into_uuid(),
+        };
+
+        if let Some(operator) = &mut self.operator {
+            operator.proceed(
+                rng,
+                &mut console,
+                this_vessel.console(),
+                process_token_context,
+                decider_vault,
+                logger,
+            )
+        }
+
+        for request in std::mem::take(&mut console.requests) {
+            match request {
+                FabricatorRequest::Interact => {
+                    let FabricatorState::Fabricating {
+                        recipe_index,
+                        process_token,
+                        remaining,
+                    } = &mut self.state
+                    else {
+                        continue;
+                    };
+                    *remaining = remaining.saturating_sub(clock.tick_duration());
+                    if !remaining.is_zero() {
+                        continue;
+                    }
+
+                    let recipe = &self.recipes[*recipe_index];
+                    if self
+                        .storage
+                        .try_produce(recipe.output().clone(), item_registry)
+                    {
+                        process_token.mark_completed(process_token_context);
+                    } else if self
+                        .storage
+                        .try_add_items(recipe.input().clone(), item_registry)
+                    {
+                        process_token.mark_failed(process_token_context);
+                    } else {
+                        // the storage has room neither for the products nor for the ingredients.
+                        // The operator tries again on the next tick
+                        continue;
+                    }
+                    self.state = FabricatorState::Idle;
+                }
+            }
+        }
+    }
+
+    fn thrust(&self) -> Float {
+        0.
+    }
+
+    fn engine_energy_per_meter(&self) -> Float {
+        0.
+    }
+
+    fn power_draw(&self) -> Float {
+        POWER_DRAW
+    }
+
+    fn power_output(&self) -> Float {
+        0.
+    }
+
+    fn radar(&self) -> Option<RadarSpec> {
+        None
+    }
+
+    fn mining_laser(&self) -> Option<MiningLaserSpec> {
+        None
+    }
+
+    fn recipes(&self) -> &[Recipe] {
+        &self.recipes
+    }
+
+    fn assembly_recipes(&self) -> &[AssemblyRecipe] {
+        &[]
+    }
+
+    fn extract_person(&mut self, id: PersonId) -> Option<Person> {
+        if self.contains_person(id) {
+            self.operator.take()
+        } else {
+            None
+        }
+    }
+
+    fn insert_person(&mut self, person: Person) -> Result<(), Person> {
+        if self.operator.is_none() {
+            self.operator = Some(person);
+            Ok(())
+        } else {
+            Err(person)
+        }
+    }
+
+    fn can_insert_person(&self) -> bool {
+        self.operator.is_none()
+    }
+
+    fn contains_person(&self, id: PersonId) -> bool {
+        self.operator
+            .as_ref()
+            .map(|p| p.id() == id)
+            .unwrap_or(false)
+    }
+
+    fn person_ids(&self) -> Vec<PersonId> {
+        self.operator.iter().map(|p| p.id()).collect()
+    }
+
+    fn storages(&self) -> &[ItemStorage] {
+        std::slice::from_ref(&self.storage)
+    }
+
+    fn storages_mut(&mut self) -> &mut [ItemStorage] {
+        std::slice::from_mut(&mut self.storage)
+    }
+
+    fn module_storages(&self) -> &[ModuleStorage] {
+        &[]
+    }
+
+    fn module_storages_mut(&mut self) -> &mut [ModuleStorage] {
+        &mut []
+    }
+
+    fn docking_clamps(&self) -> &[DockingClamp] {
+        &[]
+    }
+
+    fn docking_clamps_mut(&mut self) -> &mut [DockingClamp] {
+        &mut []
+    }
+
+    fn trading_console(&self) -> Option<&dyn TradingConsole> {
+        None
+    }
+
+    fn trading_console_mut(&mut self) -> Option<&mut dyn TradingConsole> {
+        None
+    }
+
+    fn contract_board_console(&self) -> Option<&dyn ContractBoardConsole> {
+        None
+    }
+
+    fn contract_board_console_mut(&mut self) -> Option<&mut dyn ContractBoardConsole> {
+        None
+    }
+}
+
+pub(crate) struct FabricatorDynSeed {
+    objective_seed_vault: Rc<DynDeserializeSeedVault<dyn DynObjective>>,
+    context: Rc<ProcessTokenContext>,
+}
+
+impl FabricatorDynSeed {
+    pub fn new(
+        objective_seed_vault: Rc<DynDeserializeSeedVault<dyn DynObjective>>,
+        context: Rc<ProcessTokenContext>,
+    ) -> Self {
+        Self {
+            objective_seed_vault,
+            context,
+        }
+    }
+}
+
+impl DynDeserializeSeed<dyn Module> for FabricatorDynSeed {
+    fn type_id(&self) -> TypeId {
+        TYPE_ID.to_string()
+    }
+
+    fn deserialize(
+        &self,
+        intermediate: Intermediate,
+        _: &DynDeserializeSeedVault<dyn Module>,
+    ) -> Result<Box<dyn Module>, Box<dyn Error>> {
+        let obj: Fabricator = from_intermediate_seed(
+            FabricatorSeed::new(&self.objective_seed_vault, &self.context),
+            &intermediate,
+        )
+        .map_err(|e| e.to_string())?;
+
+        Ok(Box::new(obj))
+    }
+}
+
+/// Produces fabricators able to run the given recipes
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct FabricatorFactory {
+    recipes: Vec<Recipe>,
+}
+
+impl FabricatorFactory {
+    pub fn new(recipes: Vec<Recipe>) -> Self {
+        Self { recipes }
+    }
+}
+
+impl DynSerialize for FabricatorFactory {
+    fn type_id(&self) -> TypeId {
+        FACTORY_TYPE_ID.to_string()
+    }
+
+    fn serialize(&self) -> Result<Intermediate, Box<dyn Error>> {
+        to_intermediate(self).map_err(|e| e.into())
+    }
+}
+
+impl ModuleFactory for FabricatorFactory {
+    fn output_type_id(&self) -> ModuleTypeId {
+        TYPE_ID.to_string()
+    }
+
+    fn create(&self, _: &InputRecipe, rng: &mut dyn RngCore) -> Box<dyn Module> {
+        Fabricator::new(self.recipes.clone(), rng)
+    }
+
+    fn output_capabilities(&self) -> &[ModuleCapability] {
+        CAPABILITIES
+    }
+}
+
+pub(crate) struct FabricatorFactoryDynSeed;
+
+impl DynDeserializeSeed<dyn ModuleFactory> for FabricatorFactoryDynSeed {
+    fn type_id(&self) -> TypeId {
+        FACTORY_TYPE_ID.to_string()
+    }
+
+    fn deserialize(
+        &self,
+        intermediate: Intermediate,
+        _: &DynDeserializeSeedVault<dyn ModuleFactory>,
+    ) -> Result<Box<dyn ModuleFactory>, Box<dyn Error>> {
+        let r: Box<FabricatorFactory> =
+            serde_intermediate::from_intermediate(&intermediate).map_err(|e| e.to_string())?;
+        Ok(r)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Fabricator;
+    use crate::items::register_items;
+    use crate::modules::Dockyard;
+    use dudes_in_space_api::environment::Environment;
+    use dudes_in_space_api::item::{Item, ItemRegistry, UnknownItemError};
+    use dudes_in_space_api::module::Module;
+    use dudes_in_space_api::person::PersonId;
+    use dudes_in_space_api::recipe::{InputRecipe, OutputRecipe, Recipe};
+    use dudes_in_space_api::utils::simulation_rng::SimulationRng;
+    use dudes_in_space_api::vessel::Vessel;
+    use std::time::Duration;
+
+    #[test]
+    fn unknown_items_are_found_in_nested_modules() {
+        let mut rng = SimulationRng::new(0);
+        let item_registry = register_items(ItemRegistry::new());
+        let typo = |rng: &mut SimulationRng| {
+            Fabricator::new(
+                vec![Recipe::new(
+                    InputRecipe::try_from(vec![Item::new("oer".to_string(), 10)]).unwrap(),
+                    OutputRecipe::try_from(vec![Item::new("steel".to_string(), 1)]).unwrap(),
+                    Duration::from_secs(3),
+                )],
+                rng,
+            )
+        };
+        let validate = |dockyard: Dockyard| {
+            let mut rng = SimulationRng::new(0);
+            let station = Vessel::new(
+                PersonId::nil(),
+                (0., 0.).into(),
+                vec![Box::new(dockyard)],
+                &mut rng,
+            );
+            Environment::new(rng, vec![station], vec![]).validate_items(&item_registry)
+        };
+        let error = Err(UnknownItemError {
+            id: "oer".to_string(),
+        });
+        assert_eq!(validate(Dockyard::new(&mut rng)), Ok(()));
+
+        let mut dockyard = Dockyard::new(&mut rng);
+        assert!(dockyard.module_storages_mut()[0].add(typo(&mut rng)));
+        assert_eq!(validate(dockyard), error);
+
+        let mut dockyard = Dockyard::new(&mut rng);
+        let shuttle = Vessel::new(
+            PersonId::nil(),
+            (0., 0.).into(),
+            vec![typo(&mut rng)],
+            &mut rng,
+        );
+        assert!(dockyard.docking_clamps_mut()[0].dock(shuttle));
+        assert_eq!(validate(dockyard), error);
+    }
+}
@@ -0,0 +1,242 @@
+use crate::CORE_PACKAGE_ID;
+use dudes_in_space_api::environment::{MiningLaserSpec, SimulationClock};
+use dudes_in_space_api::item::{ItemMass, ItemRegistry, ItemStorage, ItemVolume};
+use dudes_in_space_api::module::{
+    ContractBoardConsole, Module, ModuleCapability, ModuleId, ModuleStorage, ModuleTypeId,
+    PackageId, ProcessTokenContext, TradingConsole,
+};
+use dudes_in_space_api::person::{Logger, ObjectiveDeciderVault, Person, PersonId};
+use dudes_in_space_api::recipe::{AssemblyRecipe, InputRecipe, ModuleFactory, Recipe};
+use dudes_in_space_api::utils::utils::Float;
+use dudes_in_space_api::vessel::{DockingClamp, RadarSpec, VesselModuleInterface};
+use dyn_serde::{DynDeserializeSeed, DynDeserializeSeedVault, DynSerialize, TypeId};
+use rand::{Rng, RngCore};
+use serde::{Deserialize, Serialize};
+use serde_intermediate::{Intermediate, from_intermediate, to_intermediate};
+use std::error::Error;
+
+static TYPE_ID: &str = "MiningLaser";
+static FACTORY_TYPE_ID: &str = "MiningLaserFactory";
+static CAPABILITIES: &[ModuleCapability] =
+    &[ModuleCapability::MiningLaser, ModuleCapability::ItemStorage];
+/// meters
+static RANGE: Float = 100.;
+/// units per second
+static RATE: Float = 5.;
+/// kilowatts
+static POWER_DRAW: Float = 30.;
+static ORE_HOLD_VOLUME_CAPACITY: ItemVolume = 100;
+static ORE_HOLD_MASS_CAPACITY: ItemMass = 300;
+
+/// Mines asteroids in range. Has a small hold for ore
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MiningLaser {
+    id: ModuleId,
+    ore_hold: ItemStorage,
+}
+
+impl MiningLaser {
+    pub fn new(rng: &mut dyn RngCore) -> Box<Self> {
+        Box::new(Self {
+            id: uuid::Builder::from_random_bytes(rng.random()).into_uuid(),
+            ore_hold: ItemStorage::with_capacity(ORE_HOLD_VOLUME_CAPACITY, ORE_HOLD_MASS_CAPACITY),
+        })
+    }
+}
+
+impl DynSerialize for MiningLaser {
+    fn type_id(&self) -> TypeId {
+        TYPE_ID.to_string()
+    }
+
+    fn serialize(&self) -> Result<Intermediate, Box<dyn Error>> {
+        to_intermediate(self).map_err(|e| e.into())
+    }
+}
+
+impl Module for MiningLaser {
+    fn id(&self) -> ModuleId {
+        self.id
+    }
+
+    fn package_id(&self) -> PackageId {
+        CORE_PACKAGE_ID.to_string()
+    }
+
+    fn capabilities(&self) -> &[ModuleCapability] {
+        CAPABILITIES
+    }
+
+    fn primary_capabilities(&self) -> &[ModuleCapability] {
+        CAPABILITIES
+    }
+
+    fn proceed(
+        &mut self,
+        this_vessel: &dyn VesselModuleInterface,
+        rng: &mut dyn RngCore,
+        clock: &SimulationClock,
+        item_registry: &ItemRegistry,
+        process_token_context: &ProcessTokenContext,
+        decider_vault: &ObjectiveDeciderVault,
+        logger: &mut dyn Logger,
+    ) {
+    }
+
+    fn thrust(&self) -> Float {
+        0.
+    }
+
+    fn engine_energy_per_meter(&self) -> Float {
+        0.
+    }
+
+    fn power_draw(&self) -> Float {
+        POWER_DRAW
+    }
+
+    fn power_output(&self) -> Float {
+        0.
+    }
+
+    fn radar(&self) -> Option<RadarSpec> {
+        None
+    }
+
+    fn mining_laser(&self) -> Option<MiningLaserSpec> {
+        Some(MiningLaserSpec {
+            range: RANGE,
+            rate: RATE,
+        })
+    }
+
+    fn recipes(&self) -> &[Recipe] {
+        &[]
+    }
+
+    fn assembly_recipes(&self) -> &[AssemblyRecipe] {
+        &[]
+    }
+
+    fn extract_person(&mut self, id: PersonId) -> Option<Person> {
+        None
+    }
+
+    fn insert_person(&mut self, person: Person) -> Result<(), Person> {
+        Err(person)
+    }
+
+    fn can_insert_person(&self) -> bool {
+        false
+    }
+
+    fn contains_person(&self, id: PersonId) -> bool {
+        false
+    }
+
+    fn person_ids(&self) -> Vec<PersonId> {
+        Vec::new()
+    }
+
+    fn storages(&self) -> &[ItemStorage] {
+        std::slice::from_ref(&self.ore_hold)
+    }
+
+    fn storages_mut(&mut self) -> &mut [ItemStorage] {
+        std::slice::from_mut(&mut self.ore_hold)
+    }
+
+    fn module_storages(&self) -> &[ModuleStorage] {
+        &[]
+    }
+
+    fn module_storages_mut(&mut self) -> &mut [ModuleStorage] {
+        &mut []
+    }
+
+    fn docking_clamps(&self) -> &[DockingClamp] {
+        &[]
+    }
+
+    fn docking_clamps_mut(&mut self) -> &mut [DockingClamp] {
+        &mut []
+    }
+
+    fn trading_console(&self) -> Option<&dyn TradingConsole> {
+        None
+    }
+
+    fn trading_console_mut(&mut self) -> Option<&mut dyn TradingConsole> {
+        None
+    }
+
+    fn contract_board_console(&self) -> Option<&dyn ContractBoardConsole> {
+        None
+    }
+
+    fn contract_board_console_mut(&mut self) -> Option<&mut dyn ContractBoardConsole> {
+        None
+    }
+}
+
+pub(crate) struct MiningLaserDynSeed;
+
+impl DynDeserializeSeed<dyn Module> for MiningLaserDynSeed {
+    fn type_id(&self) -> TypeId {
+        TYPE_ID.to_string()
+    }
+
+    fn deserialize(
+        &self,
+        intermediate: Intermediate,
+        _: &DynDeserializeSeedVault<dyn Module>,
+    ) -> Result<Box<dyn Module>, Box<dyn Error>> {
+        let obj: MiningLaser = from_intermediate(&intermediate).map_err(|e| e.to_string())?;
+        Ok(Box::new(obj))
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct MiningLaserFactory {}
+
+impl DynSerialize for MiningLaserFactory {
+    fn type_id(&self) -> TypeId {
+        FACTORY_TYPE_ID.to_string()
+    }
+
+    fn serialize(&self) -> Result<Intermediate, Box<dyn Error>> {
+        to_intermediate(self).map_err(|e| e.into())
+    }
+}
+
+pub(crate) struct MiningLaserFactoryDynSeed;
+
+impl DynDeserializeSeed<dyn ModuleFactory> for MiningLaserFactoryDynSeed {
+    fn type_id(&self) -> TypeId {
+        FACTORY_TYPE_ID.to_string()
+    }
+
+    fn deserialize(
+        &self,
+        intermediate: Intermediate,
+        _: &DynDeserializeSeedVault<dyn ModuleFactory>,
+    ) -> Result<Box<dyn ModuleFactory>, Box<dyn Error>> {
+        let r: Box<MiningLaserFactory> =
+            from_intermediate(&intermediate).map_err(|e| e.to_string())?;
+        Ok(r)
+    }
+}
+
+impl ModuleFactory for MiningLaserFactory {
+    fn output_type_id(&self) -> ModuleTypeId {
+        TYPE_ID.to_string()
+    }
+
+    fn create(&self, _: &InputRecipe, rng: &mut dyn RngCore) -> Box<dyn Module> {
+        MiningLaser::new(rng)
+    }
+
+    fn output_capabilities(&self) -> &[ModuleCapability] {
+        CAPABILITIES
+    }
+}
@@ -1,18 +1,30 @@
 mod assembler;
 mod core_module;
 mod dockyard;
+mod fabricator;
+mod mining_laser;
 mod personnel_area;
+mod radar;
+mod reactor;
+mod research_lab;
 mod shuttle;
+mod trading_terminal;
 
 pub use assembler::*;
 pub use core_module::*;
 pub use dockyard::*;
+pub use fabricator::*;
+pub use mining_laser::*;
 use dudes_in_space_api::module::{Module, ProcessTokenContext};
 use dudes_in_space_api::person::DynObjective;
 use dudes_in_space_api::recipe::ModuleFactory;
 use dyn_serde::DynDeserializeSeedVault;
 pub use personnel_area::*;
+pub use radar::*;
+pub use reactor::*;
+pub use research_lab::*;
 pub use shuttle::*;
+pub use trading_terminal::*;
 use std::rc::Rc;
 
 pub fn register_module_factories(
@@ -21,6 +33,12 @@ pub fn register_module_factories(
     vault
         .with(ShuttleFactoryDynSeed)
         .with(DockyardFactoryDynSeed)
+        .with(FabricatorFactoryDynSeed)
+        .with(ReactorFactoryDynSeed)
+        .with(RadarFactoryDynSeed)
+        .with(MiningLaserFactoryDynSeed)
+        .with(ResearchLabFactoryDynSeed)
+        .with(TradingTerminalFactoryDynSeed)
 }
 
 pub fn register_modules(
@@ -31,11 +49,20 @@ pub fn register_modules(
 ) -> DynDeserializeSeedVault<dyn Module> {
     vault
         .with(PersonnelAreaDynSeed::new(objective_seed_vault.clone()))
-        .with(ShuttleDynSeed)
+        .with(ShuttleDynSeed::new(objective_seed_vault.clone()))
+        .with(ReactorDynSeed)
+        .with(RadarDynSeed)
+        .with(MiningLaserDynSeed)
+        .with(ResearchLabDynSeed)
+        .with(TradingTerminalDynSeed::new(objective_seed_vault.clone()))
         .with(DockyardDynSeed::new(
             objective_seed_vault.clone(),
             process_token_context.clone(),
         ))
+        .with(FabricatorDynSeed::new(
+            objective_seed_vault.clone(),
+            process_token_context.clone(),
+        ))
         .with(AssemblerDynSeed::new(
             factory_seed_vault,
             objective_seed_vault,
@@ -1,16 +1,21 @@
 use crate::CORE_PACKAGE_ID;
 use crate::modules::{CoreModule, DockyardDynSeed, ModuleVisitor, ModuleVisitorMut};
-use dudes_in_space_api::item::ItemStorage;
-use dudes_in_space_api::module::{DefaultModuleConsole, Module, ModuleCapability, ModuleConsole, ModuleId, ModuleStorage, ModuleStorageSeed, PackageId, ProcessTokenContext, TradingConsole};
+use dudes_in_space_api::environment::{MiningLaserSpec, SimulationClock};
+use dudes_in_space_api::item::{ItemRegistry, ItemStorage};
+use dudes_in_space_api::module::{
+    ContractBoardConsole, DefaultModuleConsole, Module, ModuleCapability, ModuleConsole, ModuleId,
+    ModuleStorage, ModuleStorageSeed, PackageId, ProcessTokenContext, TradingConsole,
+};
 use dudes_in_space_api::person::{DynObjective, Logger, ObjectiveDeciderVault, Person, PersonId, PersonSeed};
 use dudes_in_space_api::recipe::{AssemblyRecipe, Recipe};
 use dudes_in_space_api::utils::tagged_option::TaggedOptionSeed;
-use dudes_in_space_api::vessel::{DockingClamp, DockingClampSeed, VesselModuleInterface};
+use dudes_in_space_api::utils::utils::Float;
+use dudes_in_space_api::vessel::{DockingClamp, DockingClampSeed, RadarSpec, VesselModuleInterface};
 use dyn_serde::{
     DynDeserializeSeed, DynDeserializeSeedVault, DynSerialize, VecSeed, from_intermediate_seed,
 };
 use dyn_serde_macro::DeserializeSeedXXX;
-use rand::rng;
+use rand::{Rng, RngCore};
 use serde::{Deserialize, Serialize};
 use serde_intermediate::{Intermediate, from_intermediate, to_intermediate};
 use std::error::Error;
@@ -41,9 +46,9 @@ impl<'v> PersonnelAreaSeed<'v> {
 }
 
 impl PersonnelArea {
-    pub fn new(personnel: Vec<Person>) -> Box<Self> {
+    pub fn new(personnel: Vec<Person>, rng: &mut dyn RngCore) -> Box<Self> {
         Box::new(Self {
-            id: PersonId::new_v4(),
+            id: uuid::Builder::from_random_bytes(rng.random()).into_uuid(),
             personnel,
         })
     }
@@ -61,24 +66,27 @@ impl DynSerialize for PersonnelArea {
 
 impl Module for PersonnelArea {
     fn storages(&self) -> &[ItemStorage] {
-        todo!()
+        &[]
     }
 
     fn storages_mut(&mut self) -> &mut [ItemStorage] {
-        todo!()
+        &mut []
     }
 
     fn module_storages(&self) -> &[ModuleStorage] {
-        todo!()
+        &[]
     }
 
     fn module_storages_mut(&mut self) -> &mut [ModuleStorage] {
-        todo!()
+        &mut []
     }
 
     fn proceed(
         &mut self,
         this_vessel: &dyn VesselModuleInterface,
+        rng: &mut dyn RngCore,
+        clock: &SimulationClock,
+        item_registry: &ItemRegistry,
         process_token_context: &ProcessTokenContext,
         decider_vault: &ObjectiveDeciderVault,
         logger: &mut dyn Logger,
@@ -86,7 +94,7 @@ impl Module for PersonnelArea {
         let mut person_interface = DefaultModuleConsole::new(self.id);
         for person in &mut self.personnel {
             person.proceed(
-                &mut rng(),
+                rng,
                 &mut person_interface,
                 this_vessel.console(),
                 process_token_context,
@@ -96,12 +104,36 @@ impl Module for PersonnelArea {
         }
     }
 
-    fn recipes(&self) -> Vec<Recipe> {
-        vec![]
+    fn thrust(&self) -> Float {
+        0.
+    }
+
+    fn engine_energy_per_meter(&self) -> Float {
+        0.
+    }
+
+    fn power_draw(&self) -> Float {
+        0.
+    }
+
+    fn power_output(&self) -> Float {
+        0.
+    }
+
+    fn radar(&self) -> Option<RadarSpec> {
+        None
+    }
+
+    fn mining_laser(&self) -> Option<MiningLaserSpec> {
+        None
+    }
+
+    fn recipes(&self) -> &[Recipe] {
+        &[]
     }
 
     fn assembly_recipes(&self) -> &[AssemblyRecipe] {
-        todo!()
+        &[]
     }
 
     fn extract_person(&mut self, id: PersonId) -> Option<Person> {
@@ -111,18 +143,23 @@ impl Module for PersonnelArea {
             .map(|x| self.personnel.remove(x))
     }
 
-    fn insert_person(&mut self, person: Person) -> bool {
-        todo!()
+    fn insert_person(&mut self, person: Person) -> Result<(), Person> {
+        self.personnel.push(person);
+        Ok(())
     }
 
     fn can_insert_person(&self) -> bool {
-        todo!()
+        true
     }
 
     fn contains_person(&self, id: PersonId) -> bool {
         self.personnel.iter().find(|p| (*p).id() == id).is_some()
     }
 
+    fn person_ids(&self) -> Vec<PersonId> {
+        self.personnel.iter().map(|p| p.id()).collect()
+    }
+
     fn id(&self) -> ModuleId {
         self.id
     }
@@ -136,7 +173,11 @@ impl Module for PersonnelArea {
     }
 
     fn docking_clamps(&self) -> &[DockingClamp] {
-        todo!()
+        &[]
+    }
+
+    fn docking_clamps_mut(&mut self) -> &mut [DockingClamp] {
+        &mut []
     }
 
     fn primary_capabilities(&self) -> &[ModuleCapability] {
@@ -144,11 +185,19 @@ impl Module for PersonnelArea {
     }
 
     fn trading_console(&self) -> Option<&dyn TradingConsole> {
-        todo!()
+        None
     }
 
     fn trading_console_mut(&mut self) -> Option<&mut dyn TradingConsole> {
-        todo!()
+        None
+    }
+
+    fn contract_board_console(&self) -> Option<&dyn ContractBoardConsole> {
+        None
+    }
+
+    fn contract_board_console_mut(&mut self) -> Option<&mut dyn ContractBoardConsole> {
+        None
     }
 }
 
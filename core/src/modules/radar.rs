@@ -0,0 +1,334 @@
+use crate::CORE_PACKAGE_ID;
+use dudes_in_space_api::environment::{MiningLaserSpec, SimulationClock};
+use dudes_in_space_api::item::{ItemRegistry, ItemStorage};
+use dudes_in_space_api::module::{
+    ContractBoardConsole, Module, ModuleCapability, ModuleId, ModuleStorage, ModuleTypeId,
+    PackageId, ProcessTokenContext, TradingConsole,
+};
+use dudes_in_space_api::person::{Logger, ObjectiveDeciderVault, Person, PersonId};
+use dudes_in_space_api::recipe::{AssemblyRecipe, InputRecipe, ModuleFactory, Recipe};
+use dudes_in_space_api::utils::utils::Float;
+use dudes_in_space_api::vessel::{DockingClamp, RadarSpec, VesselModuleInterface};
+use dyn_serde::{DynDeserializeSeed, DynDeserializeSeedVault, DynSerialize, TypeId};
+use rand::{Rng, RngCore};
+use serde::{Deserialize, Serialize};
+use serde_intermediate::{Intermediate, from_intermediate, to_intermediate};
+use std::error::Error;
+
+static TYPE_ID: &str = "Radar";
+static FACTORY_TYPE_ID: &str = "RadarFactory";
+static CAPABILITIES: &[ModuleCapability] = &[ModuleCapability::Radar];
+/// meters
+static RANGE: Float = 20000.;
+/// meters
+static RESOLUTION: Float = 1.;
+/// kilowatts
+static POWER_DRAW: Float = 20.;
+
+/// Detects vessels around. Vessels which are not detected are unknown to the crew
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Radar {
+    id: ModuleId,
+}
+
+impl Radar {
+    pub fn new(rng: &mut dyn RngCore) -> Box<Self> {
+        Box::new(Self {
+            id: uuid::Builder::from_random_bytes(rng.random()).into_uuid(),
+        })
+    }
+}
+
+impl DynSerialize for Radar {
+    fn type_id(&self) -> TypeId {
+        TYPE_ID.to_string()
+    }
+
+    fn serialize(&self) -> Result<Intermediate, Box<dyn Error>> {
+        to_intermediate(self).map_err(|e| e.into())
+    }
+}
+
+impl Module for Radar {
+    fn id(&self) -> ModuleId {
+        self.id
+    }
+
+    fn package_id(&self) -> PackageId {
+        CORE_PACKAGE_ID.to_string()
+    }
+
+    fn capabilities(&self) -> &[ModuleCapability] {
+        CAPABILITIES
+    }
+
+    fn primary_capabilities(&self) -> &[ModuleCapability] {
+        CAPABILITIES
+    }
+
+    fn proceed(
+        &mut self,
+        this_vessel: &dyn VesselModuleInterface,
+        rng: &mut dyn RngCore,
+        clock: &SimulationClock,
+        item_registry: &ItemRegistry,
+        process_token_context: &ProcessTokenContext,
+        decider_vault: &ObjectiveDeciderVault,
+        logger: &mut dyn Logger,
+    ) {
+    }
+
+    fn thrust(&self) -> Float {
+        0.
+    }
+
+    fn engine_energy_per_meter(&self) -> Float {
+        0.
+    }
+
+    fn power_draw(&self) -> Float {
+        POWER_DRAW
+    }
+
+    fn power_output(&self) -> Float {
+        0.
+    }
+
+    fn radar(&self) -> Option<RadarSpec> {
+        Some(RadarSpec {
+            range: RANGE,
+            resolution: RESOLUTION,
+        })
+    }
+
+    fn mining_laser(&self) -> Option<MiningLaserSpec> {
+        None
+    }
+
+    fn recipes(&self) -> &[Recipe] {
+        &[]
+    }
+
+    fn assembly_recipes(&self) -> &[AssemblyRecipe] {
+        &[]
+    }
+
+    fn extract_person(&mut self, id: PersonId) -> Option<Person> {
+        None
+    }
+
+    fn insert_person(&mut self, person: Person) -> Result<(), Person> {
+        Err(person)
+    }
+
+    fn can_insert_person(&self) -> bool {
+        false
+    }
+
+    fn contains_person(&self, id: PersonId) -> bool {
+        false
+    }
+
+    fn person_ids(&self) -> Vec<PersonId> {
+        Vec::new()
+    }
+
+    fn storages(&self) -> &[ItemStorage] {
+        &[]
+    }
+
+    fn storages_mut(&mut self) -> &mut [ItemStorage] {
+        &mut []
+    }
+
+    fn module_storages(&self) -> &[ModuleStorage] {
+        &[]
+    }
+
+    fn module_storages_mut(&mut self) -> &mut [ModuleStorage] {
+        &mut []
+    }
+
+    fn docking_clamps(&self) -> &[DockingClamp] {
+        &[]
+    }
+
+    fn docking_clamps_mut(&mut self) -> &mut [DockingClamp] {
+        &mut []
+    }
+
+    fn trading_console(&self) -> Option<&dyn TradingConsole> {
+        None
+    }
+
+    fn trading_console_mut(&mut self) -> Option<&mut dyn TradingConsole> {
+        None
+    }
+
+    fn contract_board_console(&self) -> Option<&dyn ContractBoardConsole> {
+        None
+    }
+
+    fn contract_board_console_mut(&mut self) -> Option<&mut dyn ContractBoardConsole> {
+        None
+    }
+}
+
+pub(crate) struct RadarDynSeed;
+
+impl DynDeserializeSeed<dyn Module> for RadarDynSeed {
+    fn type_id(&self) -> TypeId {
+        TYPE_ID.to_string()
+    }
+
+    fn deserialize(
+        &self,
+        intermediate: Intermediate,
+        _: &DynDeserializeSeedVault<dyn Module>,
+    ) -> Result<Box<dyn Module>, Box<dyn Error>> {
+        let obj: Radar = from_intermediate(&intermediate).map_err(|e| e.to_string())?;
+        Ok(Box::new(obj))
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct RadarFactory {}
+
+impl DynSerialize for RadarFactory {
+    fn type_id(&self) -> TypeId {
+        FACTORY_TYPE_ID.to_string()
+    }
+
+    fn serialize(&self) -> Result<Intermediate, Box<dyn Error>> {
+        to_intermediate(self).map_err(|e| e.into())
+    }
+}
+
+pub(crate) struct RadarFactoryDynSeed;
+
+impl DynDeserializeSeed<dyn ModuleFactory> for RadarFactoryDynSeed {
+    fn type_id(&self) -> TypeId {
+        FACTORY_TYPE_ID.to_string()
+    }
+
+    fn deserialize(
+        &self,
+        intermediate: Intermediate,
+        _: &DynDeserializeSeedVault<dyn ModuleFactory>,
+    ) -> Result<Box<dyn ModuleFactory>, Box<dyn Error>> {
+        let r: Box<RadarFactory> = from_intermediate(&intermediate).map_err(|e| e.to_string())?;
+        Ok(r)
+    }
+}
+
+impl ModuleFactory for RadarFactory {
+    fn output_type_id(&self) -> ModuleTypeId {
+        TYPE_ID.to_string()
+    }
+
+    fn create(&self, _: &InputRecipe, rng: &mut dyn RngCore) -> Box<dyn Module> {
+        Radar::new(rng)
+    }
+
+    fn output_capabilities(&self) -> &[ModuleCapability] {
+        CAPABILITIES
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Radar;
+    use crate::items::register_items;
+    use crate::modules::{Reactor, ShuttleFactory};
+    use dudes_in_space_api::environment::{Environment, Nebula};
+    use dudes_in_space_api::item::{Item, ItemCount, ItemRegistry};
+    use dudes_in_space_api::module::{Module, ModuleCapability, ProcessTokenContext};
+    use dudes_in_space_api::person::{Logger, ObjectiveDeciderVault, PersonId, Severity};
+    use dudes_in_space_api::recipe::{InputRecipe, ModuleFactory};
+    use dudes_in_space_api::research::TechTree;
+    use dudes_in_space_api::utils::simulation_rng::SimulationRng;
+    use dudes_in_space_api::vessel::Vessel;
+
+    struct NoLogger;
+
+    impl Logger for NoLogger {
+        fn log(&mut self, _: &PersonId, _: Severity, _: String) {}
+    }
+
+    fn scan(fuel: ItemCount, nebulae: Vec<Nebula>) -> Environment {
+        let item_registry = register_items(ItemRegistry::new());
+        let mut rng = SimulationRng::new(0);
+        let mut reactor = Reactor::new(&mut rng);
+        assert!(
+            reactor.storages_mut()[0]
+                .try_add_item(Item::new("fuel".to_string(), fuel), &item_registry)
+        );
+        let shuttle = |rng: &mut SimulationRng| {
+            ShuttleFactory {}.create(&InputRecipe::try_from(vec![]).unwrap(), rng)
+        };
+        let vessels = vec![
+            Vessel::new(
+                PersonId::nil(),
+                (0., 0.).into(),
+                vec![Radar::new(&mut rng), reactor],
+                &mut rng,
+            ),
+            Vessel::new(
+                PersonId::nil(),
+                (500.3, 0.).into(),
+                vec![shuttle(&mut rng)],
+                &mut rng,
+            ),
+            Vessel::new(
+                PersonId::nil(),
+                (50000., 0.).into(),
+                vec![shuttle(&mut rng)],
+                &mut rng,
+            ),
+        ];
+        let mut environment = Environment::new(rng, vessels, nebulae);
+        environment.proceed(
+            &item_registry,
+            &TechTree::new(),
+            &ProcessTokenContext::new(),
+            &ObjectiveDeciderVault::new(),
+            &mut NoLogger,
+        );
+        environment
+    }
+
+    #[test]
+    fn detects_vessels_in_range() {
+        let environment = scan(1, vec![]);
+        let vessels = environment.vessels();
+        let contacts = vessels[0].contacts();
+        assert_eq!(contacts.len(), 1);
+        assert_eq!(contacts[0].vessel_id(), vessels[1].id());
+        assert_eq!(*contacts[0].pos().x(), 500.);
+        assert!(contacts[0].capabilities().contains(&ModuleCapability::Engine));
+        assert!(vessels[1].contacts().is_empty());
+    }
+
+    #[test]
+    fn dark_radar_detects_nothing() {
+        let environment = scan(0, vec![]);
+        assert!(!environment.vessels()[0].powered());
+        assert!(environment.vessels()[0].contacts().is_empty());
+    }
+
+    #[test]
+    fn nebula_hides_vessels() {
+        let nebula = Nebula::new(
+            vec![
+                (400., -100.).into(),
+                (600., -100.).into(),
+                (600., 100.).into(),
+                (400., 100.).into(),
+            ],
+            0.01,
+            0.5,
+        );
+        let environment = scan(1, vec![nebula]);
+        assert!(environment.vessels()[0].contacts().is_empty());
+    }
+}
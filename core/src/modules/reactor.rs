@@ -0,0 +1,236 @@
+use crate::CORE_PACKAGE_ID;
+use dudes_in_space_api::environment::{MiningLaserSpec, SimulationClock};
+use dudes_in_space_api::item::{ItemMass, ItemRegistry, ItemStorage, ItemVolume};
+use dudes_in_space_api::module::{
+    ContractBoardConsole, Module, ModuleCapability, ModuleId, ModuleStorage, ModuleTypeId,
+    PackageId, ProcessTokenContext, TradingConsole,
+};
+use dudes_in_space_api::person::{Logger, ObjectiveDeciderVault, Person, PersonId};
+use dudes_in_space_api::recipe::{AssemblyRecipe, InputRecipe, ModuleFactory, Recipe};
+use dudes_in_space_api::utils::utils::Float;
+use dudes_in_space_api::vessel::{DockingClamp, RadarSpec, VesselModuleInterface};
+use dyn_serde::{DynDeserializeSeed, DynDeserializeSeedVault, DynSerialize, TypeId};
+use rand::{Rng, RngCore};
+use serde::{Deserialize, Serialize};
+use serde_intermediate::{Intermediate, from_intermediate, to_intermediate};
+use std::error::Error;
+
+static TYPE_ID: &str = "Reactor";
+static FACTORY_TYPE_ID: &str = "ReactorFactory";
+static CAPABILITIES: &[ModuleCapability] = &[ModuleCapability::Reactor, ModuleCapability::FuelTank];
+/// kilowatts
+static POWER_OUTPUT: Float = 1000.;
+static FUEL_TANK_VOLUME_CAPACITY: ItemVolume = 2000;
+static FUEL_TANK_MASS_CAPACITY: ItemMass = 2000;
+
+/// Powers a station. Burns fuel from its own tank or from any other fuel tank of the vessel
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Reactor {
+    id: ModuleId,
+    fuel_tank: ItemStorage,
+}
+
+impl Reactor {
+    pub fn new(rng: &mut dyn RngCore) -> Box<Self> {
+        Box::new(Self {
+            id: uuid::Builder::from_random_bytes(rng.random()).into_uuid(),
+            fuel_tank: ItemStorage::with_capacity(
+                FUEL_TANK_VOLUME_CAPACITY,
+                FUEL_TANK_MASS_CAPACITY,
+            ),
+        })
+    }
+}
+
+impl DynSerialize for Reactor {
+    fn type_id(&self) -> TypeId {
+        TYPE_ID.to_string()
+    }
+
+    fn serialize(&self) -> Result<Intermediate, Box<dyn Error>> {
+        to_intermediate(self).map_err(|e| e.into())
+    }
+}
+
+impl Module for Reactor {
+    fn id(&self) -> ModuleId {
+        self.id
+    }
+
+    fn package_id(&self) -> PackageId {
+        CORE_PACKAGE_ID.to_string()
+    }
+
+    fn capabilities(&self) -> &[ModuleCapability] {
+        CAPABILITIES
+    }
+
+    fn primary_capabilities(&self) -> &[ModuleCapability] {
+        CAPABILITIES
+    }
+
+    fn proceed(
+        &mut self,
+        this_vessel: &dyn VesselModuleInterface,
+        rng: &mut dyn RngCore,
+        clock: &SimulationClock,
+        item_registry: &ItemRegistry,
+        process_token_context: &ProcessTokenContext,
+        decider_vault: &ObjectiveDeciderVault,
+        logger: &mut dyn Logger,
+    ) {
+    }
+
+    fn thrust(&self) -> Float {
+        0.
+    }
+
+    fn engine_energy_per_meter(&self) -> Float {
+        0.
+    }
+
+    fn power_draw(&self) -> Float {
+        0.
+    }
+
+    fn power_output(&self) -> Float {
+        POWER_OUTPUT
+    }
+
+    fn radar(&self) -> Option<RadarSpec> {
+        None
+    }
+
+    fn mining_laser(&self) -> Option<MiningLaserSpec> {
+        None
+    }
+
+    fn recipes(&self) -> &[Recipe] {
+        &[]
+    }
+
+    fn assembly_recipes(&self) -> &[AssemblyRecipe] {
+        &[]
+    }
+
+    fn extract_person(&mut self, id: PersonId) -> Option<Person> {
+        None
+    }
+
+    fn insert_person(&mut self, person: Person) -> Result<(), Person> {
+        Err(person)
+    }
+
+    fn can_insert_person(&self) -> bool {
+        false
+    }
+
+    fn contains_person(&self, id: PersonId) -> bool {
+        false
+    }
+
+    fn person_ids(&self) -> Vec<PersonId> {
+        Vec::new()
+    }
+
+    fn storages(&self) -> &[ItemStorage] {
+        std::slice::from_ref(&self.fuel_tank)
+    }
+
+    fn storages_mut(&mut self) -> &mut [ItemStorage] {
+        std::slice::from_mut(&mut self.fuel_tank)
+    }
+
+    fn module_storages(&self) -> &[ModuleStorage] {
+        &[]
+    }
+
+    fn module_storages_mut(&mut self) -> &mut [ModuleStorage] {
+        &mut []
+    }
+
+    fn docking_clamps(&self) -> &[DockingClamp] {
+        &[]
+    }
+
+    fn docking_clamps_mut(&mut self) -> &mut [DockingClamp] {
+        &mut []
+    }
+
+    fn trading_console(&self) -> Option<&dyn TradingConsole> {
+        None
+    }
+
+    fn trading_console_mut(&mut self) -> Option<&mut dyn TradingConsole> {
+        None
+    }
+
+    fn contract_board_console(&self) -> Option<&dyn ContractBoardConsole> {
+        None
+    }
+
+    fn contract_board_console_mut(&mut self) -> Option<&mut dyn ContractBoardConsole> {
+        None
+    }
+}
+
+pub(crate) struct ReactorDynSeed;
+
+impl DynDeserializeSeed<dyn Module> for ReactorDynSeed {
+    fn type_id(&self) -> TypeId {
+        TYPE_ID.to_string()
+    }
+
+    fn deserialize(
+        &self,
+        intermediate: Intermediate,
+        _: &DynDeserializeSeedVault<dyn Module>,
+    ) -> Result<Box<dyn Module>, Box<dyn Error>> {
+        let obj: Reactor = from_intermediate(&intermediate).map_err(|e| e.to_string())?;
+        Ok(Box::new(obj))
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ReactorFactory {}
+
+impl DynSerialize for ReactorFactory {
+    fn type_id(&self) -> TypeId {
+        FACTORY_TYPE_ID.to_string()
+    }
+
+    fn serialize(&self) -> Result<Intermediate, Box<dyn Error>> {
+        to_intermediate(self).map_err(|e| e.into())
+    }
+}
+
+pub(crate) struct ReactorFactoryDynSeed;
+
+impl DynDeserializeSeed<dyn ModuleFactory> for ReactorFactoryDynSeed {
+    fn type_id(&self) -> TypeId {
+        FACTORY_TYPE_ID.to_string()
+    }
+
+    fn deserialize(
+        &self,
+        intermediate: Intermediate,
+        _: &DynDeserializeSeedVault<dyn ModuleFactory>,
+    ) -> Result<Box<dyn ModuleFactory>, Box<dyn Error>> {
+        let r: Box<ReactorFactory> = from_intermediate(&intermediate).map_err(|e| e.to_string())?;
+        Ok(r)
+    }
+}
+
+impl ModuleFactory for ReactorFactory {
+    fn output_type_id(&self) -> ModuleTypeId {
+        TYPE_ID.to_string()
+    }
+
+    fn create(&self, _: &InputRecipe, rng: &mut dyn RngCore) -> Box<dyn Module> {
+        Reactor::new(rng)
+    }
+
+    fn output_capabilities(&self) -> &[ModuleCapability] {
+        CAPABILITIES
+    }
+}
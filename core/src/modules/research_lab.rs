@@ -0,0 +1,256 @@
+use crate::CORE_PACKAGE_ID;
+use dudes_in_space_api::environment::{MiningLaserSpec, SimulationClock};
+use dudes_in_space_api::item::{ItemCount, ItemMass, ItemRegistry, ItemStorage, ItemVolume};
+use dudes_in_space_api::module::{
+    ContractBoardConsole, Module, ModuleCapability, ModuleId, ModuleStorage, ModuleTypeId,
+    PackageId, ProcessTokenContext, TradingConsole,
+};
+use dudes_in_space_api::person::{Logger, ObjectiveDeciderVault, Person, PersonId};
+use dudes_in_space_api::recipe::{AssemblyRecipe, InputRecipe, ModuleFactory, Recipe};
+use dudes_in_space_api::research::RESEARCH_DATA_ITEM_ID;
+use dudes_in_space_api::utils::utils::Float;
+use dudes_in_space_api::vessel::{DockingClamp, RadarSpec, VesselModuleInterface};
+use dyn_serde::{DynDeserializeSeed, DynDeserializeSeedVault, DynSerialize, TypeId};
+use rand::{Rng, RngCore};
+use serde::{Deserialize, Serialize};
+use serde_intermediate::{Intermediate, from_intermediate, to_intermediate};
+use std::error::Error;
+
+static TYPE_ID: &str = "ResearchLab";
+static FACTORY_TYPE_ID: &str = "ResearchLabFactory";
+static CAPABILITIES: &[ModuleCapability] = &[ModuleCapability::ResearchLab];
+/// units of research data processed per second
+static RATE: Float = 1.;
+/// kilowatts
+static POWER_DRAW: Float = 50.;
+static DATA_STORAGE_VOLUME_CAPACITY: ItemVolume = 500;
+static DATA_STORAGE_MASS_CAPACITY: ItemMass = 500;
+
+/// Processes research data from its storage into research progress of the vessel owner
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ResearchLab {
+    id: ModuleId,
+    data_storage: ItemStorage,
+    /// processed part of the next unit
+    progress: Float,
+}
+
+impl ResearchLab {
+    pub fn new(rng: &mut dyn RngCore) -> Box<Self> {
+        Box::new(Self {
+            id: uuid::Builder::from_random_bytes(rng.random()).into_uuid(),
+            data_storage: ItemStorage::with_capacity(
+                DATA_STORAGE_VOLUME_CAPACITY,
+                DATA_STORAGE_MASS_CAPACITY,
+            ),
+            progress: 0.,
+        })
+    }
+}
+
+impl DynSerialize for ResearchLab {
+    fn type_id(&self) -> TypeId {
+        TYPE_ID.to_string()
+    }
+
+    fn serialize(&self) -> Result<Intermediate, Box<dyn Error>> {
+        to_intermediate(self).map_err(|e| e.into())
+    }
+}
+
+impl Module for ResearchLab {
+    fn id(&self) -> ModuleId {
+        self.id
+    }
+
+    fn package_id(&self) -> PackageId {
+        CORE_PACKAGE_ID.to_string()
+    }
+
+    fn capabilities(&self) -> &[ModuleCapability] {
+        CAPABILITIES
+    }
+
+    fn primary_capabilities(&self) -> &[ModuleCapability] {
+        CAPABILITIES
+    }
+
+    fn proceed(
+        &mut self,
+        this_vessel: &dyn VesselModuleInterface,
+        rng: &mut dyn RngCore,
+        clock: &SimulationClock,
+        item_registry: &ItemRegistry,
+        process_token_context: &ProcessTokenContext,
+        decider_vault: &ObjectiveDeciderVault,
+        logger: &mut dyn Logger,
+    ) {
+        let stored = self.data_storage.count(RESEARCH_DATA_ITEM_ID.to_string());
+        if stored == 0 {
+            self.progress = 0.;
+            return;
+        }
+        self.progress += RATE * clock.tick_duration().as_secs_f64();
+        let processed = (self.progress.floor() as ItemCount).min(stored);
+        self.progress = self.progress.fract();
+        if processed > 0 {
+            self.data_storage
+                .remove_item(RESEARCH_DATA_ITEM_ID.to_string(), processed);
+            this_vessel.research(processed);
+        }
+    }
+
+    fn thrust(&self) -> Float {
+        0.
+    }
+
+    fn engine_energy_per_meter(&self) -> Float {
+        0.
+    }
+
+    fn power_draw(&self) -> Float {
+        POWER_DRAW
+    }
+
+    fn power_output(&self) -> Float {
+        0.
+    }
+
+    fn radar(&self) -> Option<RadarSpec> {
+        None
+    }
+
+    fn mining_laser(&self) -> Option<MiningLaserSpec> {
+        None
+    }
+
+    fn recipes(&self) -> &[Recipe] {
+        &[]
+    }
+
+    fn assembly_recipes(&self) -> &[AssemblyRecipe] {
+        &[]
+    }
+
+    fn extract_person(&mut self, id: PersonId) -> Option<Person> {
+        None
+    }
+
+    fn insert_person(&mut self, person: Person) -> Result<(), Person> {
+        Err(person)
+    }
+
+    fn can_insert_person(&self) -> bool {
+        false
+    }
+
+    fn contains_person(&self, id: PersonId) -> bool {
+        false
+    }
+
+    fn person_ids(&self) -> Vec<PersonId> {
+        Vec::new()
+    }
+
+    fn storages(&self) -> &[ItemStorage] {
+        std::slice::from_ref(&self.data_storage)
+    }
+
+    fn storages_mut(&mut self) -> &mut [ItemStorage] {
+        std::slice::from_mut(&mut self.data_storage)
+    }
+
+    fn module_storages(&self) -> &[ModuleStorage] {
+        &[]
+    }
+
+    fn module_storages_mut(&mut self) -> &mut [ModuleStorage] {
+        &mut []
+    }
+
+    fn docking_clamps(&self) -> &[DockingClamp] {
+        &[]
+    }
+
+    fn docking_clamps_mut(&mut self) -> &mut [DockingClamp] {
+        &mut []
+    }
+
+    fn trading_console(&self) -> Option<&dyn TradingConsole> {
+        None
+    }
+
+    fn trading_console_mut(&mut self) -> Option<&mut dyn TradingConsole> {
+        None
+    }
+
+    fn contract_board_console(&self) -> Option<&dyn ContractBoardConsole> {
+        None
+    }
+
+    fn contract_board_console_mut(&mut self) -> Option<&mut dyn ContractBoardConsole> {
+        None
+    }
+}
+
+pub(crate) struct ResearchLabDynSeed;
+
+impl DynDeserializeSeed<dyn Module> for ResearchLabDynSeed {
+    fn type_id(&self) -> TypeId {
+        TYPE_ID.to_string()
+    }
+
+    fn deserialize(
+        &self,
+        intermediate: Intermediate,
+        _: &DynDeserializeSeedVault<dyn Module>,
+    ) -> Result<Box<dyn Module>, Box<dyn Error>> {
+        let obj: ResearchLab = from_intermediate(&intermediate).map_err(|e| e.to_string())?;
+        Ok(Box::new(obj))
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ResearchLabFactory {}
+
+impl DynSerialize for ResearchLabFactory {
+    fn type_id(&self) -> TypeId {
+        FACTORY_TYPE_ID.to_string()
+    }
+
+    fn serialize(&self) -> Result<Intermediate, Box<dyn Error>> {
+        to_intermediate(self).map_err(|e| e.into())
+    }
+}
+
+pub(crate) struct ResearchLabFactoryDynSeed;
+
+impl DynDeserializeSeed<dyn ModuleFactory> for ResearchLabFactoryDynSeed {
+    fn type_id(&self) -> TypeId {
+        FACTORY_TYPE_ID.to_string()
+    }
+
+    fn deserialize(
+        &self,
+        intermediate: Intermediate,
+        _: &DynDeserializeSeedVault<dyn ModuleFactory>,
+    ) -> Result<Box<dyn ModuleFactory>, Box<dyn Error>> {
+        let r: Box<ResearchLabFactory> =
+            from_intermediate(&intermediate).map_err(|e| e.to_string())?;
+        Ok(r)
+    }
+}
+
+impl ModuleFactory for ResearchLabFactory {
+    fn output_type_id(&self) -> ModuleTypeId {
+        TYPE_ID.to_string()
+    }
+
+    fn create(&self, _: &InputRecipe, rng: &mut dyn RngCore) -> Box<dyn Module> {
+        ResearchLab::new(rng)
+    }
+
+    fn output_capabilities(&self) -> &[ModuleCapability] {
+        CAPABILITIES
+    }
+}
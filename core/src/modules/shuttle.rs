@@ -1,15 +1,26 @@
-use dudes_in_space_api::item::ItemStorage;
-use dudes_in_space_api::module::{Module, ModuleCapability, ModuleConsole, ModuleId, ModuleStorage, ModuleTypeId, PackageId, ProcessTokenContext, TradingConsole};
-use dudes_in_space_api::person::{Logger, ObjectiveDeciderVault, Person, PersonId};
+use dudes_in_space_api::environment::{MiningLaserSpec, SimulationClock};
+use dudes_in_space_api::item::{ItemMass, ItemRegistry, ItemStorage, ItemVolume};
+use dudes_in_space_api::module::{
+    ContractBoardConsole, DefaultModuleConsole, Module, ModuleCapability, ModuleConsole, ModuleId,
+    ModuleStorage, ModuleTypeId, PackageId, ProcessTokenContext, TradingConsole,
+};
+use dudes_in_space_api::person::{
+    DynObjective, Logger, ObjectiveDeciderVault, Person, PersonId, PersonSeed,
+};
 use dudes_in_space_api::recipe::{AssemblyRecipe, InputRecipe, ModuleFactory, Recipe};
-use dudes_in_space_api::vessel::{DockingClamp, VesselModuleInterface};
+use dudes_in_space_api::utils::utils::Float;
+use dudes_in_space_api::vessel::{DockingClamp, RadarSpec, VesselModuleInterface};
 use dyn_serde::{
-    DynDeserializeSeed, DynDeserializeSeedVault, DynSerialize, TypeId, from_intermediate_seed,
+    DynDeserializeSeed, DynDeserializeSeedVault, DynSerialize, TypeId, VecSeed,
+    from_intermediate_seed,
 };
+use dyn_serde_macro::DeserializeSeedXXX;
+use rand::{Rng, RngCore};
 use serde::{Deserialize, Serialize};
-use serde_intermediate::{Intermediate, from_intermediate, to_intermediate};
+use serde_intermediate::{Intermediate, to_intermediate};
 use std::error::Error;
 use std::fmt::Debug;
+use std::rc::Rc;
 
 static TYPE_ID: &str = "Shuttle";
 static FACTORY_TYPE_ID: &str = "ShuttleFactory";
@@ -19,10 +30,39 @@ static CAPABILITIES: &[ModuleCapability] = &[
     ModuleCapability::Reactor,
     ModuleCapability::FuelTank,
 ];
-
-#[derive(Debug, Serialize, Deserialize)]
+/// newtons
+static THRUST: Float = 50000.;
+/// kilojoules
+static ENGINE_ENERGY_PER_METER: Float = 5.;
+/// kilowatts
+static POWER_DRAW: Float = 10.;
+/// kilowatts
+static POWER_OUTPUT: Float = 100.;
+static FUEL_TANK_VOLUME_CAPACITY: ItemVolume = 200;
+static FUEL_TANK_MASS_CAPACITY: ItemMass = 200;
+/// persons the shuttle can carry including the pilot
+static SEATS: usize = 4;
+
+#[derive(Debug, Serialize, DeserializeSeedXXX)]
+#[deserialize_seed_xxx(seed = crate::modules::shuttle::ShuttleSeed::<'v>)]
 struct Shuttle {
     id: ModuleId,
+    fuel_tank: ItemStorage,
+    #[deserialize_seed_xxx(seed = self.seed.person_seed)]
+    crew: Vec<Person>,
+}
+
+#[derive(Clone)]
+struct ShuttleSeed<'v> {
+    person_seed: VecSeed<PersonSeed<'v>>,
+}
+
+impl<'v> ShuttleSeed<'v> {
+    fn new(objective_vault: &'v DynDeserializeSeedVault<dyn DynObjective>) -> Self {
+        Self {
+            person_seed: VecSeed::new(PersonSeed::new(objective_vault)),
+        }
+    }
 }
 
 impl DynSerialize for Shuttle {
@@ -37,53 +77,102 @@ impl DynSerialize for Shuttle {
 
 impl Module for Shuttle {
     fn storages(&self) -> &[ItemStorage] {
-        todo!()
+        std::slice::from_ref(&self.fuel_tank)
     }
 
     fn storages_mut(&mut self) -> &mut [ItemStorage] {
-        todo!()
+        std::slice::from_mut(&mut self.fuel_tank)
     }
 
     fn module_storages(&self) -> &[ModuleStorage] {
-        todo!()
+        &[]
     }
 
     fn module_storages_mut(&mut self) -> &mut [ModuleStorage] {
-        todo!()
+        &mut []
     }
 
     fn proceed(
         &mut self,
         v: &dyn VesselModuleInterface,
+        rng: &mut dyn RngCore,
+        clock: &SimulationClock,
+        item_registry: &ItemRegistry,
         process_token_context: &ProcessTokenContext,
         decider_vault: &ObjectiveDeciderVault,
         logger: &mut dyn Logger,
     ) {
-        todo!()
+        let mut person_interface = DefaultModuleConsole::new(self.id);
+        for person in &mut self.crew {
+            person.proceed(
+                rng,
+                &mut person_interface,
+                v.console(),
+                process_token_context,
+                decider_vault,
+                logger,
+            )
+        }
     }
 
-    fn recipes(&self) -> Vec<Recipe> {
-        todo!()
+    fn thrust(&self) -> Float {
+        THRUST
+    }
+
+    fn engine_energy_per_meter(&self) -> Float {
+        ENGINE_ENERGY_PER_METER
+    }
+
+    fn power_draw(&self) -> Float {
+        POWER_DRAW
+    }
+
+    fn power_output(&self) -> Float {
+        POWER_OUTPUT
+    }
+
+    fn radar(&self) -> Option<RadarSpec> {
+        None
+    }
+
+    fn mining_laser(&self) -> Option<MiningLaserSpec> {
+        None
+    }
+
+    fn recipes(&self) -> &[Recipe] {
+        &[]
     }
 
     fn assembly_recipes(&self) -> &[AssemblyRecipe] {
-        todo!()
+        &[]
     }
 
     fn extract_person(&mut self, id: PersonId) -> Option<Person> {
-        todo!()
+        self.crew
+            .iter()
+            .position(|p| p.id() == id)
+            .map(|i| self.crew.remove(i))
     }
 
-    fn insert_person(&mut self, person: Person) -> bool {
-        todo!()
+    fn insert_person(&mut self, person: Person) -> Result<(), Person> {
+        if self.can_insert_person() {
+            self.crew.push(person);
+            Ok(())
+        } else {
+            Err(person)
+        }
     }
 
     fn can_insert_person(&self) -> bool {
-        todo!()
+        self.crew.len() < SEATS
     }
 
     fn contains_person(&self, id: PersonId) -> bool {
-        todo!()
+        self.crew.iter().any(|p| p.id() == id)
+    }
+
+    fn person_ids(&self) -> Vec<PersonId> {
+        self.crew.iter().map(|p| p.id()).collect()
     }
 
     fn id(&self) -> ModuleId {
@@ -99,7 +188,11 @@ impl Module for Shuttle {
     }
 
     fn docking_clamps(&self) -> &[DockingClamp] {
-        todo!()
+        &[]
+    }
+
+    fn docking_clamps_mut(&mut self) -> &mut [DockingClamp] {
+        &mut []
     }
 
     fn primary_capabilities(&self) -> &[ModuleCapability] {
@@ -107,15 +200,33 @@ impl Module for Shuttle {
     }
 
     fn trading_console(&self) -> Option<&dyn TradingConsole> {
-        todo!()
+        None
     }
 
     fn trading_console_mut(&mut self) -> Option<&mut dyn TradingConsole> {
-        todo!()
+        None
+    }
+
+    fn contract_board_console(&self) -> Option<&dyn ContractBoardConsole> {
+        None
     }
+
+    fn contract_board_console_mut(&mut self) -> Option<&mut dyn ContractBoardConsole> {
+        None
+    }
+}
+
+pub(crate) struct ShuttleDynSeed {
+    objective_seed_vault: Rc<DynDeserializeSeedVault<dyn DynObjective>>,
 }
 
-pub(crate) struct ShuttleDynSeed;
+impl ShuttleDynSeed {
+    pub fn new(objective_seed_vault: Rc<DynDeserializeSeedVault<dyn DynObjective>>) -> Self {
+        Self {
+            objective_seed_vault,
+        }
+    }
+}
 
 impl DynDeserializeSeed<dyn Module> for ShuttleDynSeed {
     fn type_id(&self) -> TypeId {
@@ -127,14 +238,18 @@ impl DynDeserializeSeed<dyn Module> for ShuttleDynSeed {
         intermediate: Intermediate,
         _: &DynDeserializeSeedVault<dyn Module>,
     ) -> Result<Box<dyn Module>, Box<dyn Error>> {
-        let obj: Shuttle = from_intermediate(&intermediate).map_err(|e| e.to_string())?;
+        let obj: Shuttle = from_intermediate_seed(
+            ShuttleSeed::new(&self.objective_seed_vault),
+            &intermediate,
+        )
+        .map_err(|e| e.to_string())?;
 
         Ok(Box::new(obj))
     }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
-struct ShuttleFactory {}
+pub(crate) struct ShuttleFactory {}
 
 impl DynSerialize for ShuttleFactory {
     fn type_id(&self) -> TypeId {
@@ -166,12 +281,17 @@ impl DynDeserializeSeed<dyn ModuleFactory> for ShuttleFactoryDynSeed {
 
 impl ModuleFactory for ShuttleFactory {
     fn output_type_id(&self) -> ModuleTypeId {
-        todo!()
+        TYPE_ID.to_string()
     }
 
-    fn create(&self, _: &InputRecipe) -> Box<dyn Module> {
+    fn create(&self, _: &InputRecipe, rng: &mut dyn RngCore) -> Box<dyn Module> {
         Box::new(Shuttle {
-            id: ModuleId::new_v4(),
+            id: uuid::Builder::from_random_bytes(rng.random()).into_uuid(),
+            fuel_tank: ItemStorage::with_capacity(
+                FUEL_TANK_VOLUME_CAPACITY,
+                FUEL_TANK_MASS_CAPACITY,
+            ),
+            crew: Vec::new(),
         })
     }
 
@@ -179,3 +299,126 @@ impl ModuleFactory for ShuttleFactory {
         CAPABILITIES
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::ShuttleFactory;
+    use crate::items::register_items;
+    use dudes_in_space_api::environment::{Environment, Nebula};
+    use dudes_in_space_api::item::{Item, ItemCount, ItemRegistry};
+    use dudes_in_space_api::module::ProcessTokenContext;
+    use dudes_in_space_api::person::{Logger, ObjectiveDeciderVault, PersonId, Severity};
+    use dudes_in_space_api::recipe::{InputRecipe, ModuleFactory};
+    use dudes_in_space_api::research::TechTree;
+    use dudes_in_space_api::utils::simulation_rng::SimulationRng;
+    use dudes_in_space_api::vessel::{Vessel, VesselConsole};
+
+    struct NoLogger;
+
+    impl Logger for NoLogger {
+        fn log(&mut self, _: &PersonId, _: Severity, _: String) {}
+    }
+
+    fn fueled_environment(
+        fuel: ItemCount,
+        nebulae: Vec<Nebula>,
+        item_registry: &ItemRegistry,
+    ) -> Environment {
+        let mut rng = SimulationRng::new(0);
+        let mut shuttle =
+            ShuttleFactory {}.create(&InputRecipe::try_from(vec![]).unwrap(), &mut rng);
+        assert!(
+            shuttle.storages_mut()[0]
+                .try_add_item(Item::new("fuel".to_string(), fuel), item_registry)
+        );
+        let vessel = Vessel::new(PersonId::nil(), (0., 0.).into(), vec![shuttle], &mut rng);
+        Environment::new(rng, vec![vessel], nebulae)
+    }
+
+    #[test]
+    fn flies_to_destination() {
+        let item_registry = register_items(ItemRegistry::new());
+        let mut environment = fueled_environment(10, vec![], &item_registry);
+        environment.vessels()[0].set_destination((100., 0.).into());
+
+        let process_token_context = ProcessTokenContext::new();
+        let decider_vault = ObjectiveDeciderVault::new();
+
+        let mut max_x = 0.;
+        for _ in 0..30 {
+            environment.proceed(
+                &item_registry,
+                &TechTree::new(),
+                &process_token_context,
+                &decider_vault,
+                &mut NoLogger,
+            );
+            max_x = environment.vessels()[0].pos().x().max(max_x);
+        }
+
+        let vessel = &environment.vessels()[0];
+        assert_eq!(*vessel.pos().x(), 100.);
+        assert_eq!(max_x, 100.);
+        assert!(vessel.nav_target().is_none());
+        assert_eq!(vessel.velocity().len(), 0.);
+        assert!(vessel.powered());
+        assert!(vessel.fuel(&item_registry) < 10);
+    }
+
+    #[test]
+    fn goes_dark_without_fuel() {
+        let item_registry = register_items(ItemRegistry::new());
+        let mut environment = fueled_environment(0, vec![], &item_registry);
+        environment.vessels()[0].set_destination((100., 0.).into());
+
+        let process_token_context = ProcessTokenContext::new();
+        let decider_vault = ObjectiveDeciderVault::new();
+
+        for _ in 0..10 {
+            environment.proceed(
+                &item_registry,
+                &TechTree::new(),
+                &process_token_context,
+                &decider_vault,
+                &mut NoLogger,
+            );
+        }
+
+        let vessel = &environment.vessels()[0];
+        assert!(!vessel.powered());
+        assert_eq!(*vessel.pos().x(), 0.);
+        assert!(vessel.nav_target().is_some());
+    }
+
+    #[test]
+    fn harvests_gas_in_nebula() {
+        let item_registry = register_items(ItemRegistry::new());
+        let nebula = Nebula::new(
+            vec![
+                (-10., -10.).into(),
+                (10., -10.).into(),
+                (10., 10.).into(),
+                (-10., 10.).into(),
+            ],
+            1.,
+            0.5,
+        )
+        .with_gas(Item::new("gas".to_string(), 100));
+        let mut environment = fueled_environment(10, vec![nebula], &item_registry);
+        let module_id = environment.vessels()[0].modules().next().unwrap().id();
+        environment.vessels()[0].harvest_gas(module_id, 50);
+
+        environment.proceed(
+            &item_registry,
+            &TechTree::new(),
+            &ProcessTokenContext::new(),
+            &ObjectiveDeciderVault::new(),
+            &mut NoLogger,
+        );
+
+        let vessel = &environment.vessels()[0];
+        let shuttle = vessel.modules().next().unwrap();
+        assert_eq!(shuttle.storages()[0].count("gas".to_string()), 19);
+        assert_eq!(environment.nebulae()[0].gas().unwrap().count(), 81);
+    }
+}
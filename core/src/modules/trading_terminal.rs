@@ -0,0 +1,1589 @@
+use crate::CORE_PACKAGE_ID;
+use dudes_in_space_api::environment::{MiningLaserSpec, SimulationClock};
+use dudes_in_space_api::finance::{TransferError, Wallet};
+use dudes_in_space_api::item::{
+    BuyOffer, BuyOrder, Contract, ContractId, ExpiredOrder, Item, ItemCount, ItemId, ItemMass,
+    ItemRegistry, ItemStorage, ItemVolume, Money, OfferId, OrderHolder, OrderId, PricingPolicy,
+    RefundError, SellOffer, SellOrder, Settlement, TradeVolume, WeakBuyOrder, WeakSellOrder,
+};
+use dudes_in_space_api::module::{
+    AssemblyConsole, ContractBoardConsole, DockyardConsole, FabricationConsole, Module,
+    ModuleCapability, ModuleConsole, ModuleId, ModuleStorage, ModuleTypeId, PackageId,
+    ProcessTokenContext, TradingAdminConsole, TradingConsole,
+};
+use dudes_in_space_api::person::{
+    DynObjective, Logger, ObjectiveDeciderVault, Person, PersonId, PersonSeed,
+};
+use dudes_in_space_api::recipe::{AssemblyRecipe, InputRecipe, ModuleFactory, Recipe};
+use dudes_in_space_api::utils::range::Range;
+use dudes_in_space_api::utils::tagged_option::TaggedOptionSeed;
+use dudes_in_space_api::utils::time_point::StaticTimePoint;
+use dudes_in_space_api::utils::utils::Float;
+use dudes_in_space_api::vessel::{DockingClamp, RadarSpec, VesselId, VesselModuleInterface};
+use dyn_serde::{
+    DynDeserializeSeed, DynDeserializeSeedVault, DynSerialize, TypeId, from_intermediate_seed,
+};
+use dyn_serde_macro::DeserializeSeedXXX;
+use rand::{Rng, RngCore};
+use serde::{Deserialize, Serialize};
+use serde_intermediate::{Intermediate, from_intermediate, to_intermediate};
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::rc::Rc;
+
+static TYPE_ID: &str = "TradingTerminal";
+static FACTORY_TYPE_ID: &str = "TradingTerminalFactory";
+static CAPABILITIES: &[ModuleCapability] = &[
+    ModuleCapability::TradingTerminal,
+    ModuleCapability::ContractBoard,
+    ModuleCapability::PersonnelRoom,
+];
+static PRIMARY_CAPABILITIES: &[ModuleCapability] = &[ModuleCapability::TradingTerminal];
+static WAREHOUSE_VOLUME_CAPACITY: ItemVolume = 5000;
+static WAREHOUSE_MASS_CAPACITY: ItemMass = 5000;
+/// kilowatts
+static POWER_DRAW: Float = 20.;
+
+/// offers and goods of a trading terminal. The till is the bank wallet of the terminal vessel owner.
+/// Goods of pending buy orders stay in the warehouse but can not be ordered again,
+/// money of pending sell orders is taken from the till when the order is placed.
+/// Rewards of posted contracts are taken from the till as well and paid by sell orders once accepted
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Market {
+    /// vessel the terminal is installed in. Known after the first tick
+    #[serde(with = "dudes_in_space_api::utils::untagged_option")]
+    vessel_id: Option<VesselId>,
+    warehouse: ItemStorage,
+    buy_offers: Vec<BuyOffer>,
+    sell_offers: Vec<SellOffer>,
+    next_offer_id: OfferId,
+    orders: OrderHolder,
+    #[serde(with = "dudes_in_space_api::utils::untagged_option")]
+    pricing_policy: Option<PricingPolicy>,
+    /// recent trade volume of each item traded here
+    volume: BTreeMap<ItemId, TradeVolume>,
+    contracts: Vec<Contract>,
+}
+
+impl Market {
+    /// decays recent trade volume and reprices offers according to the pricing policy
+    fn reprice(&mut self, dt: Float, item_registry: &ItemRegistry) {
+        let half_life = self
+            .pricing_policy
+            .as_ref()
+            .map(|policy| policy.volume_half_life)
+            .unwrap_or(PricingPolicy::default().volume_half_life);
+        for volume in self.volume.values_mut() {
+            volume.decay(dt, half_life);
+        }
+        self.volume.retain(|_, volume| !volume.is_negligible());
+
+        let Some(policy) = &self.pricing_policy else {
+            return;
+        };
+        let quote = |item: &ItemId| {
+            item_registry.get(item).map(|info| {
+                (
+                    info.base_price(),
+                    self.available(item),
+                    self.volume.get(item).cloned().unwrap_or_default(),
+                )
+            })
+        };
+        let sell_prices: Vec<Option<Money>> = self
+            .buy_offers
+            .iter()
+            .map(|offer| {
+                quote(&offer.item)
+                    .map(|(base, stock, volume)| policy.sell_price(base, stock, volume))
+            })
+            .collect();
+        let buy_prices: Vec<Option<Money>> = self
+            .sell_offers
+            .iter()
+            .map(|offer| {
+                quote(&offer.item)
+                    .map(|(base, stock, volume)| policy.buy_price(base, stock, volume))
+            })
+            .collect();
+        for (offer, price) in self.buy_offers.iter_mut().zip(sell_prices) {
+            if let Some(price) = price {
+                offer.price_per_unit = price;
+            }
+        }
+        for (offer, price) in self.sell_offers.iter_mut().zip(buy_prices) {
+            if let Some(price) = price {
+                offer.price_per_unit = price;
+            }
+        }
+    }
+
+    fn record_volume(&mut self, items: &[Item], bought: bool) {
+        for item in items {
+            let volume = self.volume.entry(item.id().clone()).or_default();
+            if bought {
+                volume.bought += item.count() as Float;
+            } else {
+                volume.sold += item.count() as Float;
+            }
+        }
+    }
+
+    /// count of the item in the warehouse which is not reserved by pending buy orders
+    fn available(&self, item_id: &ItemId) -> ItemCount {
+        let reserved: ItemCount = self
+            .orders
+            .buy_orders()
+            .flat_map(|order| order.items())
+            .filter(|item| item.id() == item_id)
+            .map(|item| item.count())
+            .sum();
+        self.warehouse
+            .count(item_id.clone())
+            .saturating_sub(reserved)
+    }
+
+    /// index of the accepted contract the sell order pays the reward of
+    fn contract_of_order(&self, id: OrderId) -> Option<usize> {
+        self.contracts
+            .iter()
+            .position(|contract| contract.order_id() == Some(id))
+    }
+
+    /// puts all items into the storages or none of them
+    fn try_put(storages: &mut [&mut ItemStorage], items: &[Item], registry: &ItemRegistry) -> bool {
+        let mut trial: Vec<ItemStorage> =
+            storages.iter().map(|storage| (**storage).clone()).collect();
+        for item in items {
+            let mut rest = item.clone();
+            for storage in &mut trial {
+                rest = storage.add_item(rest, registry);
+            }
+            if rest.count() > 0 {
+                return false;
+            }
+        }
+        for (storage, trial) in storages.iter_mut().zip(trial) {
+            **storage = trial;
+        }
+        true
+    }
+
+    /// takes all items from the storages or none of them
+    fn try_take(storages: &mut [&mut ItemStorage], items: &[Item]) -> bool {
+        let enough = items.iter().all(|item| {
+            let count: ItemCount = storages
+                .iter()
+                .map(|storage| storage.count(item.id().clone()))
+                .sum();
+            count >= item.count()
+        });
+        if !enough {
+            return false;
+        }
+        for item in items {
+            let mut left = item.count();
+            for storage in storages.iter_mut() {
+                left -= storage.remove_item(item.id().clone(), left).count();
+            }
+        }
+        true
+    }
+}
+
+impl TradingConsole for Market {
+    fn buy_offers(&self) -> &[BuyOffer] {
+        &self.buy_offers
+    }
+
+    fn sell_offers(&self) -> &[SellOffer] {
+        &self.sell_offers
+    }
+
+    fn place_buy_order(
+        &mut self,
+        id: OrderId,
+        offer: &BuyOffer,
+        count: ItemCount,
+        buyer: VesselId,
+        deadline: StaticTimePoint,
+    ) -> Option<WeakBuyOrder> {
+        let vessel_id = self.vessel_id?;
+        let offer = self.buy_offers.iter().find(|o| o.id == offer.id)?;
+        if !offer.count_range.contains(&count) {
+            return None;
+        }
+        let price = (count as Money).checked_mul(offer.price_per_unit)?;
+        let item = Item::new(offer.item.clone(), count);
+        if self.available(item.id()) < count {
+            return None;
+        }
+        let (order, weak) = BuyOrder::new(id, vessel_id, buyer, vec![item], price, deadline);
+        self.orders.insert_buy_order(order);
+        Some(weak)
+    }
+
+    fn place_sell_order(
+        &mut self,
+        id: OrderId,
+        offer: &SellOffer,
+        count: ItemCount,
+        seller: VesselId,
+        deadline: StaticTimePoint,
+        till: &mut Wallet,
+    ) -> Option<WeakSellOrder> {
+        let vessel_id = self.vessel_id?;
+        let offer = self.sell_offers.iter().find(|o| o.id == offer.id)?;
+        if !offer.count_range.contains(&count) {
+            return None;
+        }
+        let price = (count as Money).checked_mul(offer.price_per_unit)?;
+        till.withdraw(price).ok()?;
+        let item = Item::new(offer.item.clone(), count);
+        let (order, weak) = SellOrder::new(id, vessel_id, seller, vec![item], price, deadline);
+        self.orders.insert_sell_order(order);
+        Some(weak)
+    }
+
+    fn cancel_order(&mut self, id: OrderId, till: &mut Wallet) -> bool {
+        if self.orders.remove_buy_order(id).is_some() {
+            return true;
+        }
+        let Some(order) = self.orders.sell_order(id) else {
+            return false;
+        };
+        if self.contract_of_order(id).is_some() {
+            return false;
+        }
+        if till.deposit(order.price()).is_err() {
+            return false;
+        }
+        self.orders.remove_sell_order(id);
+        true
+    }
+
+    fn expire_orders(
+        &mut self,
+        now: StaticTimePoint,
+        till: &mut Wallet,
+    ) -> Vec<Result<ExpiredOrder, RefundError>> {
+        let buy_orders: Vec<OrderId> = self
+            .orders
+            .buy_orders()
+            .filter(|order| order.is_expired(now))
+            .map(|order| order.id())
+            .collect();
+        let sell_orders: Vec<OrderId> = self
+            .orders
+            .sell_orders()
+            .filter(|order| order.is_expired(now))
+            .map(|order| order.id())
+            .filter(|id| self.contract_of_order(*id).is_none())
+            .collect();
+        let mut result = Vec::new();
+        for id in buy_orders {
+            let order = self.orders.remove_buy_order(id).unwrap();
+            result.push(Ok(ExpiredOrder::Buy(order)));
+        }
+        for id in sell_orders {
+            let price = self.orders.sell_order(id).unwrap().price();
+            match till.deposit(price) {
+                Ok(()) => {
+                    let order = self.orders.remove_sell_order(id).unwrap();
+                    result.push(Ok(ExpiredOrder::Sell(order)));
+                }
+                Err(error) => result.push(Err(RefundError { id, error })),
+            }
+        }
+        result
+    }
+
+    fn orders(&self) -> &OrderHolder {
+        &self.orders
+    }
+
+    fn pending_orders(&self, customer: VesselId) -> Vec<OrderId> {
+        self.orders
+            .buy_orders()
+            .filter(|order| order.buyer() == customer)
+            .map(|order| order.id())
+            .chain(
+                self.orders
+                    .sell_orders()
+                    .filter(|order| order.seller() == customer)
+                    .map(|order| order.id()),
+            )
+            .collect()
+    }
+
+    fn settle(
+        &mut self,
+        customer: VesselId,
+        customer_storages: &mut [&mut ItemStorage],
+        customer_wallet: &mut Wallet,
+        till: &mut Wallet,
+        item_registry: &ItemRegistry,
+    ) -> Vec<Settlement> {
+        let mut result = Vec::new();
+
+        let buy_orders: Vec<OrderId> = self
+            .orders
+            .buy_orders()
+            .filter(|order| order.buyer() == customer)
+            .map(|order| order.id())
+            .collect();
+        for id in buy_orders {
+            let order = self.orders.buy_order(id).unwrap();
+            let (items, price) = (order.items(), order.price());
+            if !items.iter().all(|item| self.warehouse.contains(item))
+                || customer_wallet.can_withdraw(price).is_err()
+                || till.can_deposit(price).is_err()
+            {
+                continue;
+            }
+            if !Self::try_put(customer_storages, &items, item_registry) {
+                continue;
+            }
+            for item in &items {
+                self.warehouse.remove_item(item.id().clone(), item.count());
+            }
+            customer_wallet.withdraw(price).unwrap();
+            till.deposit(price).unwrap();
+            self.orders.remove_buy_order(id);
+            self.record_volume(&items, true);
+            result.push(Settlement::Bought {
+                order_id: id,
+                items,
+                price,
+            });
+        }
+
+        let sell_orders: Vec<OrderId> = self
+            .orders
+            .sell_orders()
+            .filter(|order| order.seller() == customer)
+            .map(|order| order.id())
+            .collect();
+        for id in sell_orders {
+            let order = self.orders.sell_order(id).unwrap();
+            let (items, price) = (order.items(), order.price());
+            let contract = self.contract_of_order(id);
+            let collateral = contract.map(|i| self.contracts[i].penalty()).unwrap_or(0);
+            let Some(payment) = price.checked_add(collateral) else {
+                continue;
+            };
+            if customer_wallet.can_deposit(payment).is_err() {
+                continue;
+            }
+            let mut warehouse = self.warehouse.clone();
+            if !items
+                .iter()
+                .all(|item| warehouse.try_add_item(item.clone(), item_registry))
+            {
+                continue;
+            }
+            if !Self::try_take(customer_storages, &items) {
+                continue;
+            }
+            self.warehouse = warehouse;
+            // the price was taken from the till when the order was placed
+            customer_wallet.deposit(payment).unwrap();
+            self.orders.remove_sell_order(id);
+            self.record_volume(&items, false);
+            match contract {
+                Some(i) => {
+                    let contract = self.contracts.remove(i);
+                    result.push(Settlement::Delivered {
+                        contract_id: contract.id(),
+                        order_id: id,
+                        items,
+                        reward: price,
+                        collateral,
+                    });
+                }
+                None => result.push(Settlement::Sold {
+                    order_id: id,
+                    items,
+                    price,
+                }),
+            }
+        }
+
+        result
+    }
+}
+
+impl TradingAdminConsole for Market {
+    fn place_buy_offer(
+        &mut self,
+        item: ItemId,
+        count_range: Range<ItemCount>,
+        price_per_unit: Money,
+    ) -> Option<&BuyOffer> {
+        if count_range.start >= count_range.end {
+            return None;
+        }
+        let id = self.next_offer_id;
+        self.next_offer_id += 1;
+        self.buy_offers.push(BuyOffer {
+            id,
+            item,
+            count_range,
+            price_per_unit,
+        });
+        self.buy_offers.last()
+    }
+
+    fn place_sell_offer(
+        &mut self,
+        item: ItemId,
+        count_range: Range<ItemCount>,
+        price_per_unit: Money,
+    ) -> Option<&SellOffer> {
+        if count_range.start >= count_range.end {
+            return None;
+        }
+        let id = self.next_offer_id;
+        self.next_offer_id += 1;
+        self.sell_offers.push(SellOffer {
+            id,
+            item,
+            count_range,
+            price_per_unit,
+        });
+        self.sell_offers.last()
+    }
+
+    fn set_pricing_policy(&mut self, policy: Option<PricingPolicy>) {
+        self.pricing_policy = policy;
+    }
+
+    fn pricing_policy(&self) -> Option<&PricingPolicy> {
+        self.pricing_policy.as_ref()
+    }
+}
+
+impl ContractBoardConsole for Market {
+    fn contracts(&self) -> &[Contract] {
+        &self.contracts
+    }
+
+    fn post_contract(
+        &mut self,
+        id: ContractId,
+        items: Vec<Item>,
+        reward: Money,
+        penalty: Money,
+        deadline: StaticTimePoint,
+        till: &mut Wallet,
+    ) -> Option<&Contract> {
+        let vessel_id = self.vessel_id?;
+        if items.iter().all(|item| item.count() == 0) {
+            return None;
+        }
+        till.withdraw(reward).ok()?;
+        self.contracts.push(Contract::new(
+            id, vessel_id, items, reward, penalty, deadline,
+        ));
+        self.contracts.last()
+    }
+
+    fn accept_contract(
+        &mut self,
+        id: ContractId,
+        order_id: OrderId,
+        contractor: VesselId,
+        contractor_wallet: &mut Wallet,
+    ) -> Option<OrderId> {
+        let contract = self
+            .contracts
+            .iter_mut()
+            .find(|contract| contract.id() == id)?;
+        if contract.contractor().is_some() {
+            return None;
+        }
+        contractor_wallet.withdraw(contract.penalty()).ok()?;
+        let order = contract.accept(contractor, order_id).unwrap();
+        self.orders.insert_sell_order(order);
+        Some(order_id)
+    }
+
+    fn expire_contracts(
+        &mut self,
+        now: StaticTimePoint,
+        till: &mut Wallet,
+    ) -> Vec<Result<Contract, RefundError>> {
+        let mut result = Vec::new();
+        let mut contracts = Vec::new();
+        for contract in std::mem::take(&mut self.contracts) {
+            if !contract.is_expired(now) {
+                contracts.push(contract);
+                continue;
+            }
+            // the till paid the reward and keeps the collateral
+            let collateral = contract
+                .contractor()
+                .map(|_| contract.penalty())
+                .unwrap_or(0);
+            let refund = contract
+                .reward()
+                .checked_add(collateral)
+                .ok_or(TransferError::Overflow {
+                    balance: contract.reward(),
+                    amount: collateral,
+                })
+                .and_then(|refund| till.deposit(refund));
+            match refund {
+                Ok(()) => {
+                    if let Some(order_id) = contract.order_id() {
+                        self.orders.remove_sell_order(order_id);
+                    }
+                    result.push(Ok(contract));
+                }
+                Err(error) => {
+                    result.push(Err(RefundError {
+                        id: contract.id(),
+                        error,
+                    }));
+                    contracts.push(contract);
+                }
+            }
+        }
+        self.contracts = contracts;
+        result
+    }
+}
+
+/// Sells goods from its warehouse and buys goods into it according to offers placed by the operator.
+/// Orders are settled when the customer vessel is docked to the terminal vessel or is in docking range
+#[derive(Debug, Serialize, DeserializeSeedXXX)]
+#[deserialize_seed_xxx(seed = crate::modules::trading_terminal::TradingTerminalSeed::<'v>)]
+pub struct TradingTerminal {
+    id: ModuleId,
+    market: Market,
+    #[serde(with = "dudes_in_space_api::utils::tagged_option")]
+    #[deserialize_seed_xxx(seed = self.seed.person_seed)]
+    operator: Option<Person>,
+}
+
+#[derive(Clone)]
+struct TradingTerminalSeed<'v> {
+    person_seed: TaggedOptionSeed<PersonSeed<'v>>,
+}
+
+impl<'v> TradingTerminalSeed<'v> {
+    fn new(objective_vault: &'v DynDeserializeSeedVault<dyn DynObjective>) -> Self {
+        Self {
+            person_seed: TaggedOptionSeed::new(PersonSeed::new(objective_vault)),
+        }
+    }
+}
+
+impl TradingTerminal {
+    pub fn new(rng: &mut dyn RngCore) -> Box<Self> {
+        Box::new(Self {
+            id: uuid::Builder::from_random_bytes(rng.random()).into_uuid(),
+            market: Market {
+                warehouse: ItemStorage::with_capacity(
+                    WAREHOUSE_VOLUME_CAPACITY,
+                    WAREHOUSE_MASS_CAPACITY,
+                ),
+                ..Default::default()
+            },
+            operator: None,
+        })
+    }
+
+    pub fn admin_console_mut(&mut self) -> &mut dyn TradingAdminConsole {
+        &mut self.market
+    }
+}
+
+impl DynSerialize for TradingTerminal {
+    fn type_id(&self) -> TypeId {
+        TYPE_ID.to_string()
+    }
+
+    fn serialize(&self) -> Result<Intermediate, Box<dyn Error>> {
+        to_intermediate(self).map_err(|e| e.into())
+    }
+}
+
+struct Console<'a> {
+    id: ModuleId,
+    market: &'a mut Market,
+}
+
+impl<'a> ModuleConsole for Console<'a> {
+    fn id(&self) -> ModuleId {
+        self.id
+    }
+
+    fn package_id(&self) -> PackageId {
+        CORE_PACKAGE_ID.to_string()
+    }
+
+    fn capabilities(&self) -> &[ModuleCapability] {
+        CAPABILITIES
+    }
+
+    fn primary_capabilities(&self) -> &[ModuleCapability] {
+        PRIMARY_CAPABILITIES
+    }
+
+    fn interact(&mut self) -> bool {
+        false
+    }
+
+    fn in_progress(&self) -> bool {
+        false
+    }
+
+    fn assembly_console(&self) -> Option<&dyn AssemblyConsole> {
+        None
+    }
+
+    fn assembly_console_mut(&mut self) -> Option<&mut dyn AssemblyConsole> {
+        None
+    }
+
+    fn fabrication_console(&self) -> Option<&dyn FabricationConsole> {
+        None
+    }
+
+    fn fabrication_console_mut(&mut self) -> Option<&mut dyn FabricationConsole> {
+        None
+    }
+
+    fn dockyard_console(&self) -> Option<&dyn DockyardConsole> {
+        None
+    }
+
+    fn dockyard_console_mut(&mut self) -> Option<&mut dyn DockyardConsole> {
+        None
+    }
+
+    fn trading_console(&self) -> Option<&dyn TradingConsole> {
+        Some(self.market)
+    }
+
+    fn trading_console_mut(&mut self) -> Option<&mut dyn TradingConsole> {
+        Some(self.market)
+    }
+
+    fn trading_admin_console(&self) -> Option<&dyn TradingAdminConsole> {
+        Some(self.market)
+    }
+
+    fn trading_admin_console_mut(&mut self) -> Option<&mut dyn TradingAdminConsole> {
+        Some(self.market)
+    }
+
+    fn contract_board_console(&self) -> Option<&dyn ContractBoardConsole> {
+        Some(self.market)
+    }
+
+    fn contract_board_console_mut(&mut self) -> Option<&mut dyn ContractBoardConsole> {
+        Some(self.market)
+    }
+
+    fn storages(&self) -> &[ItemStorage] {
+        std::slice::from_ref(&self.market.warehouse)
+    }
+
+    fn storages_mut(&mut self) -> &mut [ItemStorage] {
+        std::slice::from_mut(&mut self.market.warehouse)
+    }
+
+    fn module_storages(&self) -> &[ModuleStorage] {
+        &[]
+    }
+
+    fn module_storages_mut(&mut self) -> &mut [ModuleStorage] {
+        &mut []
+    }
+
+    fn docking_clamps(&self) -> &[DockingClamp] {
+        &[]
+    }
+
+    fn docking_clamps_mut(&mut self) -> &mut [DockingClamp] {
+        &mut []
+    }
+}
+
+impl Module for TradingTerminal {
+    fn id(&self) -> ModuleId {
+        self.id
+    }
+
+    fn package_id(&self) -> PackageId {
+        CORE_PACKAGE_ID.to_string()
+    }
+
+    fn capabilities(&self) -> &[ModuleCapability] {
+        CAPABILITIES
+    }
+
+    fn primary_capabilities(&self) -> &[ModuleCapability] {
+        PRIMARY_CAPABILITIES
+    }
+
+    fn proceed(
+        &mut self,
+        this_vessel: &dyn VesselModuleInterface,
+        rng: &mut dyn RngCore,
+        clock: &SimulationClock,
+        item_registry: &ItemRegistry,
+        process_token_context: &ProcessTokenContext,
+        decider_vault: &ObjectiveDeciderVault,
+        logger: &mut dyn Logger,
+    ) {
+        self.market.vessel_id = Some(this_vessel.console().id());
+        self.market
+            .reprice(clock.tick_duration().as_secs_f64(), item_registry);
+
+        if let Some(operator) = &mut self.operator {
+            let mut person_interface = Console {
+                id: self.id,
+                market: &mut self.market,
+            };
+            operator.proceed(
+                rng,
+                &mut person_interface,
+                this_vessel.console(),
+                process_token_context,
+                decider_vault,
+                logger,
+            )
+        }
+    }
+
+    fn thrust(&self) -> Float {
+        0.
+    }
+
+    fn engine_energy_per_meter(&self) -> Float {
+        0.
+    }
+
+    fn power_draw(&self) -> Float {
+        POWER_DRAW
+    }
+
+    fn power_output(&self) -> Float {
+        0.
+    }
+
+    fn radar(&self) -> Option<RadarSpec> {
+        None
+    }
+
+    fn mining_laser(&self) -> Option<MiningLaserSpec> {
+        None
+    }
+
+    fn recipes(&self) -> &[Recipe] {
+        &[]
+    }
+
+    fn assembly_recipes(&self) -> &[AssemblyRecipe] {
+        &[]
+    }
+
+    fn extract_person(&mut self, id: PersonId) -> Option<Person> {
+        if self
+            .operator
+            .as_ref()
+            .map(|p| p.id() == id)
+            .unwrap_or(false)
+        {
+            self.operator.take()
+        } else {
+            None
+        }
+    }
+
+    fn insert_person(&mut self, person: Person) -> Result<(), Person> {
+        if self.operator.is_none() {
+            self.operator = Some(person);
+            Ok(())
+        } else {
+            Err(person)
+        }
+    }
+
+    fn can_insert_person(&self) -> bool {
+        self.operator.is_none()
+    }
+
+    fn contains_person(&self, id: PersonId) -> bool {
+        self.operator
+            .as_ref()
+            .map(|p| p.id() == id)
+            .unwrap_or(false)
+    }
+
+    fn person_ids(&self) -> Vec<PersonId> {
+        self.operator.iter().map(|p| p.id()).collect()
+    }
+
+    fn storages(&self) -> &[ItemStorage] {
+        std::slice::from_ref(&self.market.warehouse)
+    }
+
+    fn storages_mut(&mut self) -> &mut [ItemStorage] {
+        std::slice::from_mut(&mut self.market.warehouse)
+    }
+
+    fn module_storages(&self) -> &[ModuleStorage] {
+        &[]
+    }
+
+    fn module_storages_mut(&mut self) -> &mut [ModuleStorage] {
+        &mut []
+    }
+
+    fn docking_clamps(&self) -> &[DockingClamp] {
+        &[]
+    }
+
+    fn docking_clamps_mut(&mut self) -> &mut [DockingClamp] {
+        &mut []
+    }
+
+    fn trading_console(&self) -> Option<&dyn TradingConsole> {
+        Some(&self.market)
+    }
+
+    fn trading_console_mut(&mut self) -> Option<&mut dyn TradingConsole> {
+        Some(&mut self.market)
+    }
+
+    fn contract_board_console(&self) -> Option<&dyn ContractBoardConsole> {
+        Some(&self.market)
+    }
+
+    fn contract_board_console_mut(&mut self) -> Option<&mut dyn ContractBoardConsole> {
+        Some(&mut self.market)
+    }
+}
+
+pub(crate) struct TradingTerminalDynSeed {
+    objective_seed_vault: Rc<DynDeserializeSeedVault<dyn DynObjective>>,
+}
+
+impl TradingTerminalDynSeed {
+    pub fn new(objective_seed_vault: Rc<DynDeserializeSeedVault<dyn DynObjective>>) -> Self {
+        Self {
+            objective_seed_vault,
+        }
+    }
+}
+
+impl DynDeserializeSeed<dyn Module> for TradingTerminalDynSeed {
+    fn type_id(&self) -> TypeId {
+        TYPE_ID.to_string()
+    }
+
+    fn deserialize(
+        &self,
+        intermediate: Intermediate,
+        _: &DynDeserializeSeedVault<dyn Module>,
+    ) -> Result<Box<dyn Module>, Box<dyn Error>> {
+        let obj: TradingTerminal = from_intermediate_seed(
+            TradingTerminalSeed::new(&self.objective_seed_vault),
+            &intermediate,
+        )
+        .map_err(|e| e.to_string())?;
+        Ok(Box::new(obj))
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TradingTerminalFactory {}
+
+impl DynSerialize for TradingTerminalFactory {
+    fn type_id(&self) -> TypeId {
+        FACTORY_TYPE_ID.to_string()
+    }
+
+    fn serialize(&self) -> Result<Intermediate, Box<dyn Error>> {
+        to_intermediate(self).map_err(|e| e.into())
+    }
+}
+
+pub(crate) struct TradingTerminalFactoryDynSeed;
+
+impl DynDeserializeSeed<dyn ModuleFactory> for TradingTerminalFactoryDynSeed {
+    fn type_id(&self) -> TypeId {
+        FACTORY_TYPE_ID.to_string()
+    }
+
+    fn deserialize(
+        &self,
+        intermediate: Intermediate,
+        _: &DynDeserializeSeedVault<dyn ModuleFactory>,
+    ) -> Result<Box<dyn ModuleFactory>, Box<dyn Error>> {
+        let r: Box<TradingTerminalFactory> =
+            from_intermediate(&intermediate).map_err(|e| e.to_string())?;
+        Ok(r)
+    }
+}
+
+impl ModuleFactory for TradingTerminalFactory {
+    fn output_type_id(&self) -> ModuleTypeId {
+        TYPE_ID.to_string()
+    }
+
+    fn create(&self, _: &InputRecipe, rng: &mut dyn RngCore) -> Box<dyn Module> {
+        TradingTerminal::new(rng)
+    }
+
+    fn output_capabilities(&self) -> &[ModuleCapability] {
+        CAPABILITIES
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TradingTerminal;
+    use crate::items::register_items;
+    use crate::modules::{
+        MiningLaser, Radar, Reactor, register_module_factories, register_modules,
+    };
+    use crate::objectives::register_objectives;
+    use dudes_in_space_api::environment::{Environment, EnvironmentSeed, ORDER_LIFETIME};
+    use dudes_in_space_api::finance::{TransferError, Wallet};
+    use dudes_in_space_api::item::{
+        ContractId, Item, ItemRegistry, Money, OrderHolder, OrderId, PricingPolicy, RefundError,
+        WeakBuyOrder,
+    };
+    use dudes_in_space_api::module::{Module, ProcessTokenContext};
+    use dudes_in_space_api::person::{Logger, ObjectiveDeciderVault, PersonId, Severity};
+    use dudes_in_space_api::research::TechTree;
+    use dudes_in_space_api::utils::simulation_rng::SimulationRng;
+    use dudes_in_space_api::vessel::{TerminalRef, Vessel, VesselConsole};
+    use serde::de::DeserializeSeed;
+    use std::rc::Rc;
+    use std::time::Duration;
+
+    struct NoLogger;
+
+    impl Logger for NoLogger {
+        fn log(&mut self, _: &PersonId, _: Severity, _: String) {}
+    }
+
+    fn reactor(item_registry: &ItemRegistry, rng: &mut SimulationRng) -> Box<Reactor> {
+        let mut reactor = Reactor::new(rng);
+        assert!(
+            reactor.storages_mut()[0]
+                .try_add_item(Item::new("fuel".to_string(), 100), item_registry)
+        );
+        reactor
+    }
+
+    /// runs `f` with pending orders of the terminal of the first vessel
+    fn with_orders<T>(environment: &Environment, f: impl FnOnce(&OrderHolder) -> T) -> T {
+        let terminal = environment.vessels()[0].modules().next().unwrap();
+        f(terminal.trading_console().unwrap().orders())
+    }
+
+    #[test]
+    fn settles_orders_in_docking_range() {
+        let mut rng = SimulationRng::new(0);
+        let item_registry = &register_items(ItemRegistry::new());
+        let mut terminal = TradingTerminal::new(&mut rng);
+        assert!(
+            terminal.storages_mut()[0]
+                .try_add_item(Item::new("steel".to_string(), 20), item_registry)
+        );
+        let steel_offer = terminal
+            .admin_console_mut()
+            .place_buy_offer("steel".to_string(), (1..100).into(), 10)
+            .unwrap()
+            .clone();
+        let ore_offer = terminal
+            .admin_console_mut()
+            .place_sell_offer("ore".to_string(), (1..100).into(), 3)
+            .unwrap()
+            .clone();
+        let merchant = PersonId::new_v4();
+        let station = Vessel::new(
+            merchant,
+            (0., 0.).into(),
+            vec![terminal, reactor(item_registry, &mut rng)],
+            &mut rng,
+        );
+
+        let mut cargo = MiningLaser::new(&mut rng);
+        assert!(
+            cargo.storages_mut()[0].try_add_item(Item::new("ore".to_string(), 30), item_registry)
+        );
+        let trader = PersonId::new_v4();
+        let customer = Vessel::new(
+            trader,
+            (5., 0.).into(),
+            vec![cargo, Radar::new(&mut rng)],
+            &mut rng,
+        );
+        let customer_id = customer.id();
+        let far_customer = Vessel::new(
+            PersonId::nil(),
+            (500., 0.).into(),
+            vec![Radar::new(&mut rng)],
+            &mut rng,
+        );
+        let far_customer_id = far_customer.id();
+
+        let mut environment = Environment::new(rng, vec![station, customer, far_customer], vec![]);
+        environment
+            .bank_mut()
+            .deposit(trader, 50, Default::default(), "savings".to_string())
+            .unwrap();
+        environment
+            .bank_mut()
+            .deposit(merchant, 1000, Default::default(), "capital".to_string())
+            .unwrap();
+        let process_token_context = ProcessTokenContext::new();
+        let decider_vault = ObjectiveDeciderVault::new();
+        let proceed = |environment: &mut Environment| {
+            environment.proceed(
+                item_registry,
+                &TechTree::new(),
+                &process_token_context,
+                &decider_vault,
+                &mut NoLogger,
+            )
+        };
+        // the terminal learns its vessel
+        proceed(&mut environment);
+
+        let deadline = environment.clock().now() + ORDER_LIFETIME;
+        let (terminal_ref, buy_order, far_order) = {
+            let station = &environment.vessels()[0];
+            let mut terminal = station.modules_mut().next().unwrap();
+            let terminal_ref = TerminalRef {
+                vessel_id: station.id(),
+                module_id: terminal.id(),
+            };
+            let console = terminal.trading_console_mut().unwrap();
+            // not enough goods in stock
+            assert!(
+                console
+                    .place_buy_order(OrderId::new_v4(), &steel_offer, 21, customer_id, deadline)
+                    .is_none()
+            );
+            // out of the offer range
+            assert!(
+                console
+                    .place_buy_order(OrderId::new_v4(), &steel_offer, 0, customer_id, deadline)
+                    .is_none()
+            );
+            let buy_order = console
+                .place_buy_order(OrderId::new_v4(), &steel_offer, 12, customer_id, deadline)
+                .unwrap();
+            // the rest is reserved
+            assert!(
+                console
+                    .place_buy_order(
+                        OrderId::new_v4(),
+                        &steel_offer,
+                        9,
+                        far_customer_id,
+                        deadline
+                    )
+                    .is_none()
+            );
+            let far_order = console
+                .place_buy_order(
+                    OrderId::new_v4(),
+                    &steel_offer,
+                    8,
+                    far_customer_id,
+                    deadline,
+                )
+                .unwrap();
+            (terminal_ref, buy_order, far_order)
+        };
+        with_orders(&environment, |orders| {
+            assert_eq!(buy_order.price(orders), Some(120));
+            assert_eq!(buy_order.buyer(orders), Some(customer_id));
+        });
+        assert_eq!(
+            with_orders(&environment, |orders| buy_order.vessel_to_buy_from(orders)),
+            Some(environment.vessels()[0].id())
+        );
+        environment.vessels()[1].place_sell_order(terminal_ref, ore_offer.id, 30);
+
+        // the price of the ore is reserved from the till and paid at once,
+        // the trader can not afford the steel before the ore is sold
+        proceed(&mut environment);
+        with_orders(&environment, |orders| {
+            assert!(buy_order.is_pending(orders));
+            assert!(orders.sell_orders().next().is_none());
+        });
+        assert_eq!(environment.bank().balance(trader), 140);
+        assert_eq!(environment.bank().balance(merchant), 910);
+
+        proceed(&mut environment);
+        with_orders(&environment, |orders| {
+            assert!(!buy_order.is_pending(orders));
+            assert!(far_order.is_pending(orders));
+        });
+        assert_eq!(environment.bank().balance(trader), 20);
+        assert_eq!(environment.bank().balance(merchant), 1030);
+        assert_eq!(environment.bank().ledger().of(trader).count(), 3);
+        assert_eq!(environment.bank().ledger().of(merchant).count(), 3);
+
+        let customer = &environment.vessels()[1];
+        let cargo = customer.modules().next().unwrap();
+        assert_eq!(cargo.storages()[0].count("steel".to_string()), 12);
+        assert_eq!(cargo.storages()[0].count("ore".to_string()), 0);
+
+        let station = &environment.vessels()[0];
+        let mut terminal = station.modules_mut().next().unwrap();
+        assert_eq!(terminal.storages()[0].count("steel".to_string()), 8);
+        assert_eq!(terminal.storages()[0].count("ore".to_string()), 30);
+        let console = terminal.trading_console_mut().unwrap();
+        // buy orders reserve goods only
+        let mut till = Wallet::default();
+        assert!(console.cancel_order(far_order.id(), &mut till));
+        assert!(!far_order.is_pending(console.orders()));
+        assert!(!console.cancel_order(far_order.id(), &mut till));
+        assert_eq!(till.balance(), 0);
+    }
+
+    #[test]
+    fn order_handles_survive_save() {
+        let mut rng = SimulationRng::new(0);
+        let item_registry = &register_items(ItemRegistry::new());
+        let mut terminal = TradingTerminal::new(&mut rng);
+        assert!(
+            terminal.storages_mut()[0]
+                .try_add_item(Item::new("steel".to_string(), 20), item_registry)
+        );
+        let steel_offer = terminal
+            .admin_console_mut()
+            .place_buy_offer("steel".to_string(), (1..100).into(), 10)
+            .unwrap()
+            .clone();
+        let station = Vessel::new(
+            PersonId::nil(),
+            (0., 0.).into(),
+            vec![terminal, reactor(item_registry, &mut rng)],
+            &mut rng,
+        );
+        let customer = Vessel::new(
+            PersonId::nil(),
+            (500., 0.).into(),
+            vec![Radar::new(&mut rng)],
+            &mut rng,
+        );
+        let customer_id = customer.id();
+        let mut environment = Environment::new(rng, vec![station, customer], vec![]);
+        let process_token_context = Rc::new(ProcessTokenContext::new());
+        // the terminal learns its vessel
+        environment.proceed(
+            item_registry,
+            &TechTree::new(),
+            &process_token_context,
+            &ObjectiveDeciderVault::new(),
+            &mut NoLogger,
+        );
+        let deadline = environment.clock().now() + ORDER_LIFETIME;
+        let order = {
+            let station = &environment.vessels()[0];
+            let mut terminal = station.modules_mut().next().unwrap();
+            let console = terminal.trading_console_mut().unwrap();
+            console
+                .place_buy_order(OrderId::new_v4(), &steel_offer, 5, customer_id, deadline)
+                .unwrap()
+        };
+
+        let objective_vault = register_objectives(Default::default()).into_rc();
+        let module_vault = register_modules(
+            Default::default(),
+            register_module_factories(Default::default()).into_rc(),
+            objective_vault.clone(),
+            process_token_context.clone(),
+        );
+        let json = serde_json::to_string(&environment).unwrap();
+        let reloaded = EnvironmentSeed::new(&module_vault, &objective_vault)
+            .deserialize(&mut serde_json::Deserializer::from_str(&json))
+            .unwrap();
+        let order: WeakBuyOrder =
+            serde_json::from_str(&serde_json::to_string(&order).unwrap()).unwrap();
+
+        with_orders(&reloaded, |orders| {
+            assert!(order.is_pending(orders));
+            assert_eq!(order.price(orders), Some(50));
+            assert_eq!(order.buyer(orders), Some(customer_id));
+            assert_eq!(
+                order.items(orders),
+                Some(vec![Item::new("steel".to_string(), 5)])
+            );
+        });
+    }
+
+    #[test]
+    fn contracts_expire_with_penalty() {
+        let mut rng = SimulationRng::new(0);
+        let item_registry = &register_items(ItemRegistry::new());
+        let merchant = PersonId::new_v4();
+        let station = Vessel::new(
+            merchant,
+            (0., 0.).into(),
+            vec![
+                TradingTerminal::new(&mut rng),
+                reactor(item_registry, &mut rng),
+            ],
+            &mut rng,
+        );
+        let trader = PersonId::new_v4();
+        let contractor = Vessel::new(
+            trader,
+            (500., 0.).into(),
+            vec![Radar::new(&mut rng)],
+            &mut rng,
+        );
+        let mut environment = Environment::new(rng, vec![station, contractor], vec![]);
+        environment
+            .bank_mut()
+            .deposit(trader, 100, Default::default(), "savings".to_string())
+            .unwrap();
+        let process_token_context = ProcessTokenContext::new();
+        let decider_vault = ObjectiveDeciderVault::new();
+        let proceed = |environment: &mut Environment| {
+            environment.proceed(
+                item_registry,
+                &TechTree::new(),
+                &process_token_context,
+                &decider_vault,
+                &mut NoLogger,
+            )
+        };
+        // the terminal learns its vessel
+        proceed(&mut environment);
+
+        let deadline = environment.clock().now() + Duration::from_secs(5);
+        let mut till = Wallet::new(1000);
+        let (terminal, contract_id) = {
+            let station = &environment.vessels()[0];
+            let mut module = station.modules_mut().next().unwrap();
+            let terminal = TerminalRef {
+                vessel_id: station.id(),
+                module_id: module.id(),
+            };
+            let board = module.contract_board_console_mut().unwrap();
+            // the reward exceeds the till
+            assert!(
+                board
+                    .post_contract(
+                        ContractId::new_v4(),
+                        vec![Item::new("steel".to_string(), 10)],
+                        2000,
+                        40,
+                        deadline,
+                        &mut till,
+                    )
+                    .is_none()
+            );
+            let contract = board
+                .post_contract(
+                    ContractId::new_v4(),
+                    vec![Item::new("steel".to_string(), 10)],
+                    300,
+                    40,
+                    deadline,
+                    &mut till,
+                )
+                .unwrap();
+            (terminal, contract.id())
+        };
+
+        environment.vessels()[1].accept_contract(terminal, contract_id);
+        proceed(&mut environment);
+        assert_eq!(environment.bank().balance(trader), 60);
+        {
+            let station = &environment.vessels()[0];
+            let mut module = station.modules_mut().next().unwrap();
+            let board = module.contract_board_console_mut().unwrap();
+            let contract = &board.contracts()[0];
+            assert_eq!(contract.contractor(), Some(environment.vessels()[1].id()));
+            // the order paying the reward can not be cancelled
+            let order_id = contract.order_id().unwrap();
+            let console = module.trading_console_mut().unwrap();
+            assert!(!console.cancel_order(order_id, &mut till));
+        }
+
+        for _ in 0..5 {
+            proceed(&mut environment);
+        }
+        let station = &environment.vessels()[0];
+        let mut module = station.modules_mut().next().unwrap();
+        assert_eq!(environment.bank().balance(trader), 60);
+        assert!(
+            module
+                .trading_console_mut()
+                .unwrap()
+                .pending_orders(environment.vessels()[1].id())
+                .is_empty()
+        );
+        let board = module.contract_board_console_mut().unwrap();
+        assert!(board.contracts().is_empty());
+        assert_eq!(till.balance(), 700);
+        // the reward returned to the owner of the terminal vessel together with the collateral
+        assert_eq!(environment.bank().balance(merchant), 340);
+        let refund = environment.bank().ledger().of(merchant).last().unwrap();
+        assert_eq!((refund.from(), refund.amount()), (None, 340));
+    }
+
+    #[test]
+    fn orders_expire_and_release_reservations() {
+        let mut rng = SimulationRng::new(0);
+        let item_registry = &register_items(ItemRegistry::new());
+        let mut terminal = TradingTerminal::new(&mut rng);
+        assert!(
+            terminal.storages_mut()[0]
+                .try_add_item(Item::new("steel".to_string(), 20), item_registry)
+        );
+        let steel_offer = terminal
+            .admin_console_mut()
+            .place_buy_offer("steel".to_string(), (1..100).into(), 10)
+            .unwrap()
+            .clone();
+        let ore_offer = terminal
+            .admin_console_mut()
+            .place_sell_offer("ore".to_string(), (1..100).into(), 3)
+            .unwrap()
+            .clone();
+        let merchant = PersonId::new_v4();
+        let station = Vessel::new(
+            merchant,
+            (0., 0.).into(),
+            vec![terminal, reactor(item_registry, &mut rng)],
+            &mut rng,
+        );
+        let customer = Vessel::new(
+            PersonId::nil(),
+            (500., 0.).into(),
+            vec![Radar::new(&mut rng)],
+            &mut rng,
+        );
+        let customer_id = customer.id();
+        let mut environment = Environment::new(rng, vec![station, customer], vec![]);
+        environment
+            .bank_mut()
+            .deposit(merchant, 1000, Default::default(), "capital".to_string())
+            .unwrap();
+        let process_token_context = ProcessTokenContext::new();
+        let decider_vault = ObjectiveDeciderVault::new();
+        let proceed = |environment: &mut Environment| {
+            environment.proceed(
+                item_registry,
+                &TechTree::new(),
+                &process_token_context,
+                &decider_vault,
+                &mut NoLogger,
+            )
+        };
+        // the terminal learns its vessel
+        proceed(&mut environment);
+
+        let terminal_ref = {
+            let station = &environment.vessels()[0];
+            let mut terminal = station.modules_mut().next().unwrap();
+            let terminal_ref = TerminalRef {
+                vessel_id: station.id(),
+                module_id: terminal.id(),
+            };
+            let deadline = environment.clock().now() + ORDER_LIFETIME;
+            let console = terminal.trading_console_mut().unwrap();
+            assert!(
+                console
+                    .place_buy_order(OrderId::new_v4(), &steel_offer, 20, customer_id, deadline)
+                    .is_some()
+            );
+            terminal_ref
+        };
+        environment.vessels()[1].place_sell_order(terminal_ref, ore_offer.id, 30);
+        proceed(&mut environment);
+        assert_eq!(environment.bank().balance(merchant), 910);
+
+        environment.set_tick_duration(ORDER_LIFETIME);
+        proceed(&mut environment);
+        proceed(&mut environment);
+        with_orders(&environment, |orders| assert!(orders.is_empty()));
+        // the price of the ore returned to the till
+        assert_eq!(environment.bank().balance(merchant), 1000);
+        let refund = environment.bank().ledger().of(merchant).last().unwrap();
+        assert_eq!((refund.from(), refund.amount()), (None, 90));
+        // the steel is for sale again
+        let station = &environment.vessels()[0];
+        let mut terminal = station.modules_mut().next().unwrap();
+        let deadline = environment.clock().now() + ORDER_LIFETIME;
+        assert!(
+            terminal
+                .trading_console_mut()
+                .unwrap()
+                .place_buy_order(OrderId::new_v4(), &steel_offer, 20, customer_id, deadline)
+                .is_some()
+        );
+    }
+
+    #[test]
+    fn refunds_which_overflow_the_till_stay_pending() {
+        let mut rng = SimulationRng::new(0);
+        let item_registry = &register_items(ItemRegistry::new());
+        let mut terminal = TradingTerminal::new(&mut rng);
+        let ore_offer = terminal
+            .admin_console_mut()
+            .place_sell_offer("ore".to_string(), (1..100).into(), 3)
+            .unwrap()
+            .clone();
+        let station = Vessel::new(
+            PersonId::nil(),
+            (0., 0.).into(),
+            vec![terminal, reactor(item_registry, &mut rng)],
+            &mut rng,
+        );
+        let customer_id = station.id();
+        let mut environment = Environment::new(rng, vec![station], vec![]);
+        // the terminal learns its vessel
+        environment.proceed(
+            item_registry,
+            &TechTree::new(),
+            &ProcessTokenContext::new(),
+            &ObjectiveDeciderVault::new(),
+            &mut NoLogger,
+        );
+
+        let now = environment.clock().now();
+        let station = &environment.vessels()[0];
+        let mut terminal = station.modules_mut().next().unwrap();
+        let mut till = Wallet::new(1000);
+        let order = terminal
+            .trading_console_mut()
+            .unwrap()
+            .place_sell_order(
+                OrderId::new_v4(),
+                &ore_offer,
+                30,
+                customer_id,
+                now,
+                &mut till,
+            )
+            .unwrap();
+        let contract_id = terminal
+            .contract_board_console_mut()
+            .unwrap()
+            .post_contract(
+                ContractId::new_v4(),
+                vec![Item::new("steel".to_string(), 10)],
+                300,
+                40,
+                now,
+                &mut till,
+            )
+            .unwrap()
+            .id();
+
+        let mut full_till = Wallet::new(Money::MAX);
+        let overflow = |amount| TransferError::Overflow {
+            balance: Money::MAX,
+            amount,
+        };
+        let console = terminal.trading_console_mut().unwrap();
+        let expired = console.expire_orders(now, &mut full_till);
+        assert_eq!(
+            expired
+                .into_iter()
+                .map(|r| r.map(|o| o.id()))
+                .collect::<Vec<_>>(),
+            vec![Err(RefundError {
+                id: order.id(),
+                error: overflow(90),
+            })]
+        );
+        assert!(order.is_pending(console.orders()));
+        let board = terminal.contract_board_console_mut().unwrap();
+        let expired = board.expire_contracts(now, &mut full_till);
+        assert_eq!(
+            expired
+                .into_iter()
+                .map(|r| r.map(|c| c.id()))
+                .collect::<Vec<_>>(),
+            vec![Err(RefundError {
+                id: contract_id,
+                error: overflow(300),
+            })]
+        );
+        assert_eq!(board.contracts().len(), 1);
+
+        let console = terminal.trading_console_mut().unwrap();
+        assert_eq!(console.expire_orders(now, &mut till).len(), 1);
+        assert!(!order.is_pending(console.orders()));
+        let board = terminal.contract_board_console_mut().unwrap();
+        assert_eq!(board.expire_contracts(now, &mut till).len(), 1);
+        assert!(board.contracts().is_empty());
+        assert_eq!(till.balance(), 1000);
+    }
+
+    #[test]
+    fn prices_follow_stock() {
+        let mut rng = SimulationRng::new(0);
+        let item_registry = &register_items(ItemRegistry::new());
+        let terminal = |stock, rng: &mut SimulationRng| {
+            let mut terminal = TradingTerminal::new(rng);
+            assert!(
+                terminal.storages_mut()[0]
+                    .try_add_item(Item::new("steel".to_string(), stock), item_registry)
+            );
+            let admin = terminal.admin_console_mut();
+            admin.place_buy_offer("steel".to_string(), (1..100).into(), 1);
+            admin.place_sell_offer("steel".to_string(), (1..100).into(), 1);
+            admin.set_pricing_policy(Some(PricingPolicy::default()));
+            terminal
+        };
+        let station = Vessel::new(
+            PersonId::nil(),
+            (0., 0.).into(),
+            vec![
+                terminal(0, &mut rng),
+                terminal(400, &mut rng),
+                reactor(item_registry, &mut rng),
+            ],
+            &mut rng,
+        );
+        let mut environment = Environment::new(rng, vec![station], vec![]);
+        environment.proceed(
+            item_registry,
+            &TechTree::new(),
+            &ProcessTokenContext::new(),
+            &ObjectiveDeciderVault::new(),
+            &mut NoLogger,
+        );
+
+        let prices: Vec<_> = environment.vessels()[0]
+            .modules()
+            .take(2)
+            .map(|module| {
+                let console = module.trading_console().unwrap();
+                (
+                    console.buy_offers()[0].price_per_unit,
+                    console.sell_offers()[0].price_per_unit,
+                )
+            })
+            .collect();
+        let base = item_registry
+            .get(&"steel".to_string())
+            .unwrap()
+            .base_price();
+        let (scarce_sell, scarce_buy) = prices[0];
+        let (stocked_sell, stocked_buy) = prices[1];
+        assert!(scarce_sell > base && scarce_buy > stocked_buy);
+        assert!(stocked_sell < scarce_sell && stocked_buy < base);
+        assert!(scarce_buy <= scarce_sell && stocked_buy <= stocked_sell);
+    }
+}
@@ -1,14 +1,18 @@
 use dudes_in_space_api::module::{
     ModuleCapability, ModuleConsole, ModuleId, ProcessToken, ProcessTokenContext,
 };
-use dudes_in_space_api::person::{Objective, ObjectiveStatus, PersonId, PersonLogger};
+use dudes_in_space_api::person::{DynObjective, Objective, ObjectiveStatus, PersonId, PersonLogger};
 use dudes_in_space_api::recipe::AssemblyRecipe;
 use dudes_in_space_api::vessel::VesselConsole;
+use dyn_serde::{DynDeserializeSeed, DynDeserializeSeedVault, DynSerialize, TypeId};
 use serde::{Deserialize, Serialize};
+use serde_intermediate::{Intermediate, from_intermediate, to_intermediate};
 use std::collections::{BTreeSet, VecDeque};
 use std::error::Error;
 use std::fmt::{Debug, Display, Formatter};
 
+static TYPE_ID: &str = "CraftModulesObjective";
+
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(tag = "crafting_modules_objective_stage")]
 pub(crate) enum CraftModulesObjective {
@@ -148,6 +152,13 @@ impl Objective for CraftModulesObjective {
                     }
                 }
                 Some(some_process_token) => {
+                    if some_process_token
+                        .is_failed(process_token_context)
+                        .unwrap_or(false)
+                    {
+                        return Err(CraftModulesObjectiveError::CraftingFailed);
+                    }
+
                     if some_process_token
                         .is_completed(process_token_context)
                         .unwrap_or(true)
@@ -174,15 +185,190 @@ impl Objective for CraftModulesObjective {
     }
 }
 
+impl DynSerialize for CraftModulesObjective {
+    fn type_id(&self) -> TypeId {
+        TYPE_ID.to_string()
+    }
+
+    fn serialize(&self) -> Result<Intermediate, Box<dyn Error>> {
+        to_intermediate(self).map_err(|e| e.into())
+    }
+}
+
+pub(crate) struct CraftModulesObjectiveDynSeed;
+
+impl DynDeserializeSeed<dyn DynObjective> for CraftModulesObjectiveDynSeed {
+    fn type_id(&self) -> TypeId {
+        TYPE_ID.to_string()
+    }
+
+    fn deserialize(
+        &self,
+        intermediate: Intermediate,
+        _: &DynDeserializeSeedVault<dyn DynObjective>,
+    ) -> Result<Box<dyn DynObjective>, Box<dyn Error>> {
+        let obj: CraftModulesObjective =
+            from_intermediate(&intermediate).map_err(|e| e.to_string())?;
+        Ok(Box::new(obj))
+    }
+}
+
 #[derive(Debug)]
 pub(crate) enum CraftModulesObjectiveError {
     CanNotFindCraftingModule,
+    /// the crafting module aborted the process (e.g. there was no space for the result)
+    CraftingFailed,
 }
 
 impl Display for CraftModulesObjectiveError {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        todo!()
+        match self {
+            CraftModulesObjectiveError::CanNotFindCraftingModule => {
+                write!(f, "Can not find a crafting module with suitable recipes")
+            }
+            CraftModulesObjectiveError::CraftingFailed => {
+                write!(f, "Crafting module aborted the assembly")
+            }
+        }
     }
 }
 
 impl Error for CraftModulesObjectiveError {}
+
+#[cfg(test)]
+mod tests {
+    use super::CraftModulesObjective;
+    use crate::items::register_items;
+    use crate::modules::{Assembler, Dockyard, Reactor, ShuttleFactory};
+    use dudes_in_space_api::environment::Environment;
+    use dudes_in_space_api::item::{Item, ItemCount, ItemRegistry};
+    use dudes_in_space_api::module::{Module, ProcessTokenContext};
+    use dudes_in_space_api::person::{
+        Awareness, Boldness, DynObjective, Gender, Logger, Morale, ObjectiveDecider,
+        ObjectiveDeciderVault, Passion, Person, PersonId, Severity,
+    };
+    use dudes_in_space_api::recipe::{AssemblyRecipe, InputRecipe, ModuleFactory};
+    use dudes_in_space_api::research::TechTree;
+    use dudes_in_space_api::utils::simulation_rng::SimulationRng;
+    use dudes_in_space_api::vessel::Vessel;
+    use std::rc::Rc;
+    use std::time::Duration;
+
+    struct NoLogger;
+
+    impl Logger for NoLogger {
+        fn log(&mut self, _: &PersonId, _: Severity, _: String) {}
+    }
+
+    struct CraftShuttleDecider;
+
+    impl ObjectiveDecider for CraftShuttleDecider {
+        fn consider(
+            &self,
+            person_id: PersonId,
+            _: u8,
+            _: Gender,
+            _: &[Passion],
+            _: Morale,
+            _: Boldness,
+            _: Awareness,
+        ) -> Option<Box<dyn DynObjective>> {
+            let capabilities = ShuttleFactory {}.output_capabilities().to_vec();
+            Some(Box::new(CraftModulesObjective::new(
+                person_id,
+                capabilities,
+                false,
+            )))
+        }
+    }
+
+    /// station with a manned assembler able to assemble a shuttle from 10 steel in 3 seconds
+    /// and a dockyard with `free_slots` free module slots to store it
+    fn station(free_slots: usize, item_registry: &ItemRegistry) -> Environment {
+        let mut rng = SimulationRng::new(0);
+        let recipe = AssemblyRecipe::new(
+            InputRecipe::try_from(vec![Item::new("steel".to_string(), 10)]).unwrap(),
+            Rc::new(ShuttleFactory {}),
+            Duration::from_secs(3),
+        );
+        let mut assembler = Assembler::new(vec![recipe], &mut rng);
+        assert!(
+            assembler.storages_mut()[0]
+                .try_add_item(Item::new("steel".to_string(), 10), item_registry)
+        );
+        let operator = Person::random(&mut SimulationRng::new(0));
+        assert!(assembler.insert_person(operator).is_ok());
+
+        let mut dockyard = Dockyard::new(&mut rng);
+        let module_storage = &mut dockyard.module_storages_mut()[0];
+        while module_storage.free_slots() > free_slots {
+            let shuttle =
+                ShuttleFactory {}.create(&InputRecipe::try_from(vec![]).unwrap(), &mut rng);
+            assert!(module_storage.add(shuttle));
+        }
+
+        let mut reactor = Reactor::new(&mut rng);
+        assert!(
+            reactor.storages_mut()[0]
+                .try_add_item(Item::new("fuel".to_string(), 100), item_registry)
+        );
+        let station = Vessel::new(
+            PersonId::nil(),
+            (0., 0.).into(),
+            vec![assembler, Box::new(dockyard), reactor],
+            &mut rng,
+        );
+        Environment::new(rng, vec![station], vec![])
+    }
+
+    fn proceed(environment: &mut Environment, ticks: usize, item_registry: &ItemRegistry) {
+        let process_token_context = ProcessTokenContext::new();
+        let decider_vault = ObjectiveDeciderVault::new().with(CraftShuttleDecider);
+        for _ in 0..ticks {
+            environment.proceed(
+                item_registry,
+                &TechTree::new(),
+                &process_token_context,
+                &decider_vault,
+                &mut NoLogger,
+            );
+        }
+    }
+
+    fn steel(environment: &Environment) -> ItemCount {
+        let assembler = environment.vessels()[0].modules().next().unwrap();
+        assembler.storages()[0].count("steel".to_string())
+    }
+
+    fn stored_modules(environment: &Environment) -> usize {
+        let dockyard = environment.vessels()[0].modules().nth(1).unwrap();
+        dockyard.module_storages()[0].len()
+    }
+
+    #[test]
+    fn stores_module_after_duration() {
+        let item_registry = register_items(ItemRegistry::new());
+        let mut environment = station(1, &item_registry);
+        // the operator takes the objective, picks the assembler and the recipe, starts it
+        // and works on it for two seconds
+        proceed(&mut environment, 6, &item_registry);
+        assert_eq!(steel(&environment), 0);
+        assert_eq!(stored_modules(&environment), 15);
+
+        proceed(&mut environment, 1, &item_registry);
+        assert_eq!(stored_modules(&environment), 16);
+        assert_eq!(steel(&environment), 0);
+    }
+
+    #[test]
+    fn gives_input_back_when_slots_are_full() {
+        let item_registry = register_items(ItemRegistry::new());
+        let mut environment = station(0, &item_registry);
+        proceed(&mut environment, 6, &item_registry);
+        assert_eq!(steel(&environment), 0);
+
+        proceed(&mut environment, 1, &item_registry);
+        assert_eq!(stored_modules(&environment), 16);
+        assert_eq!(steel(&environment), 10);
+    }
+}
@@ -0,0 +1,368 @@
+use dudes_in_space_api::item::{ItemCount, ItemId};
+use dudes_in_space_api::module::{
+    ModuleCapability, ModuleConsole, ModuleId, ProcessStatus, ProcessToken, ProcessTokenContext,
+};
+use dudes_in_space_api::person::{
+    Awareness, Boldness, DynObjective, Gender, Morale, Objective, ObjectiveDecider,
+    ObjectiveStatus, Passion, PersonId, PersonLogger, Severity,
+};
+use dudes_in_space_api::vessel::{VesselConsole, VesselRequestError};
+use dyn_serde::{DynDeserializeSeed, DynDeserializeSeedVault, DynSerialize, TypeId};
+use serde::{Deserialize, Serialize};
+use serde_intermediate::{Intermediate, from_intermediate, to_intermediate};
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+static TYPE_ID: &str = "FabricateItemsObjective";
+
+/// go to a fabricator of the vessel and run its recipes while its storage has the ingredients
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "fabricate_items_objective_stage")]
+pub(crate) enum FabricateItemsObjective {
+    SearchingForFabricator {
+        this_person: PersonId,
+    },
+    MovingToFabricator {
+        dst: ModuleId,
+    },
+    ChoosingRecipe,
+    Fabricating {
+        process_token: ProcessToken,
+        /// first product of the recipe and how many of it were stored when the recipe was started
+        product: ItemId,
+        stored: ItemCount,
+    },
+    Done,
+}
+
+impl FabricateItemsObjective {
+    pub(crate) fn new(this_person: PersonId) -> Self {
+        Self::SearchingForFabricator { this_person }
+    }
+
+    fn stored(this_module: &dyn ModuleConsole, item: &ItemId) -> ItemCount {
+        this_module
+            .storages()
+            .iter()
+            .map(|storage| storage.count(item.clone()))
+            .sum()
+    }
+}
+
+impl Objective for FabricateItemsObjective {
+    type Error = FabricateItemsObjectiveError;
+
+    fn pursue(
+        &mut self,
+        this_module: &mut dyn ModuleConsole,
+        this_vessel: &dyn VesselConsole,
+        process_token_context: &ProcessTokenContext,
+        mut logger: PersonLogger,
+    ) -> Result<ObjectiveStatus, Self::Error> {
+        match self {
+            Self::SearchingForFabricator { this_person } => {
+                if this_module.fabrication_console().is_some() {
+                    *self = Self::ChoosingRecipe;
+                    return Ok(ObjectiveStatus::InProgress);
+                }
+                let dst = this_vessel
+                    .modules_with_cap(ModuleCapability::Fabrication)
+                    .iter()
+                    .find(|module| module.can_insert_person())
+                    .map(|module| module.id())
+                    .ok_or(FabricateItemsObjectiveError::NoFabricator)?;
+                this_vessel.move_to_module(*this_person, dst);
+                *self = Self::MovingToFabricator { dst };
+                Ok(ObjectiveStatus::InProgress)
+            }
+            Self::MovingToFabricator { dst } => {
+                if this_module.id() == *dst {
+                    *self = Self::ChoosingRecipe;
+                }
+                Ok(ObjectiveStatus::InProgress)
+            }
+            Self::ChoosingRecipe => {
+                // a process started by somebody else is finished first
+                if this_module.in_progress() {
+                    this_module.interact();
+                    return Ok(ObjectiveStatus::InProgress);
+                }
+                let console = this_module
+                    .fabrication_console_mut()
+                    .ok_or(FabricateItemsObjectiveError::NoFabricator)?;
+                let Some(index) =
+                    (0..console.recipes().len()).find(|i| console.has_resources_for_recipe(*i))
+                else {
+                    logger.log(Severity::Info, "Nothing left to fabricate".to_string());
+                    *self = Self::Done;
+                    return Ok(ObjectiveStatus::Done);
+                };
+                let product = console.recipes()[index]
+                    .output()
+                    .iter()
+                    .next()
+                    .ok_or(FabricateItemsObjectiveError::NothingToProduce)?;
+                let process_token = console
+                    .start(index)
+                    .ok_or(FabricateItemsObjectiveError::FabricationFailed)?;
+                let stored = Self::stored(this_module, product.id());
+                *self = Self::Fabricating {
+                    process_token,
+                    product: product.id().clone(),
+                    stored,
+                };
+                Ok(ObjectiveStatus::InProgress)
+            }
+            Self::Fabricating {
+                process_token,
+                product,
+                stored,
+            } => match process_token.status(process_token_context) {
+                Ok(ProcessStatus::InProgress) => {
+                    if !this_module.interact() {
+                        *self = Self::ChoosingRecipe;
+                    }
+                    Ok(ObjectiveStatus::InProgress)
+                }
+                Ok(ProcessStatus::Failed) => Err(FabricateItemsObjectiveError::FabricationFailed),
+                // the fabricator forgets the process once it is over, so the storage tells
+                // whether the products were made
+                Ok(ProcessStatus::Completed) | Err(_) => {
+                    if Self::stored(this_module, product) <= *stored {
+                        return Err(FabricateItemsObjectiveError::FabricationFailed);
+                    }
+                    logger.log(Severity::Info, format!("Fabricated {}", product));
+                    *self = Self::ChoosingRecipe;
+                    Ok(ObjectiveStatus::InProgress)
+                }
+            },
+            Self::Done => Ok(ObjectiveStatus::Done),
+        }
+    }
+
+    fn on_request_error(&mut self, error: &VesselRequestError) -> Result<(), Self::Error> {
+        Err(FabricateItemsObjectiveError::Request(error.clone()))
+    }
+}
+
+impl DynSerialize for FabricateItemsObjective {
+    fn type_id(&self) -> TypeId {
+        TYPE_ID.to_string()
+    }
+
+    fn serialize(&self) -> Result<Intermediate, Box<dyn Error>> {
+        to_intermediate(self).map_err(|e| e.into())
+    }
+}
+
+pub(crate) struct FabricateItemsObjectiveDynSeed;
+
+impl DynDeserializeSeed<dyn DynObjective> for FabricateItemsObjectiveDynSeed {
+    fn type_id(&self) -> TypeId {
+        TYPE_ID.to_string()
+    }
+
+    fn deserialize(
+        &self,
+        intermediate: Intermediate,
+        _: &DynDeserializeSeedVault<dyn DynObjective>,
+    ) -> Result<Box<dyn DynObjective>, Box<dyn Error>> {
+        let obj: FabricateItemsObjective =
+            from_intermediate(&intermediate).map_err(|e| e.to_string())?;
+        Ok(Box::new(obj))
+    }
+}
+
+pub(crate) struct FabricateItemsObjectiveDecider;
+
+impl ObjectiveDecider for FabricateItemsObjectiveDecider {
+    fn consider(
+        &self,
+        person_id: PersonId,
+        _: u8,
+        _: Gender,
+        passions: &[Passion],
+        _: Morale,
+        _: Boldness,
+        _: Awareness,
+    ) -> Option<Box<dyn DynObjective>> {
+        if passions.contains(&Passion::Crafting) {
+            Some(Box::new(FabricateItemsObjective::new(person_id)))
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Debug)]
+pub(crate) enum FabricateItemsObjectiveError {
+    NoFabricator,
+    NothingToProduce,
+    /// the fabricator aborted the process (e.g. there was no space for the products)
+    FabricationFailed,
+    Request(VesselRequestError),
+}
+
+impl Display for FabricateItemsObjectiveError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FabricateItemsObjectiveError::NoFabricator => {
+                write!(f, "Vessel has no free fabricator")
+            }
+            FabricateItemsObjectiveError::NothingToProduce => {
+                write!(f, "Recipe has no products")
+            }
+            FabricateItemsObjectiveError::FabricationFailed => {
+                write!(f, "Fabrication failed")
+            }
+            FabricateItemsObjectiveError::Request(error) => {
+                write!(f, "Can not get to the fabricator: {}", error)
+            }
+        }
+    }
+}
+
+impl Error for FabricateItemsObjectiveError {}
+
+#[cfg(test)]
+mod tests {
+    use super::FabricateItemsObjective;
+    use crate::items::register_items;
+    use crate::modules::{Fabricator, PersonnelArea, Reactor};
+    use dudes_in_space_api::environment::Environment;
+    use dudes_in_space_api::item::{Item, ItemCount, ItemRegistry};
+    use dudes_in_space_api::module::{Module, ProcessTokenContext};
+    use dudes_in_space_api::person::{
+        Awareness, Boldness, DynObjective, Gender, Logger, Morale, ObjectiveDecider,
+        ObjectiveDeciderVault, Passion, Person, PersonId, Severity,
+    };
+    use dudes_in_space_api::recipe::{InputRecipe, OutputRecipe, Recipe};
+    use dudes_in_space_api::research::TechTree;
+    use dudes_in_space_api::utils::simulation_rng::SimulationRng;
+    use dudes_in_space_api::vessel::Vessel;
+    use std::time::Duration;
+
+    /// keeps warnings and errors
+    #[derive(Default)]
+    struct ProblemLogger {
+        problems: Vec<String>,
+    }
+
+    impl Logger for ProblemLogger {
+        fn log(&mut self, _: &PersonId, severity: Severity, message: String) {
+            if !matches!(severity, Severity::Info) {
+                self.problems.push(message);
+            }
+        }
+    }
+
+    struct FabricateDecider;
+
+    impl ObjectiveDecider for FabricateDecider {
+        fn consider(
+            &self,
+            person_id: PersonId,
+            _: u8,
+            _: Gender,
+            _: &[Passion],
+            _: Morale,
+            _: Boldness,
+            _: Awareness,
+        ) -> Option<Box<dyn DynObjective>> {
+            Some(Box::new(FabricateItemsObjective::new(person_id)))
+        }
+    }
+
+    /// station with a fabricator turning 10 ore into `steel` steel in 3 seconds and a crew
+    /// member in a personnel area next to it
+    fn station(steel: ItemCount, item_registry: &ItemRegistry) -> Environment {
+        let mut rng = SimulationRng::new(0);
+        let recipe = Recipe::new(
+            InputRecipe::try_from(vec![Item::new("ore".to_string(), 10)]).unwrap(),
+            OutputRecipe::try_from(vec![Item::new("steel".to_string(), steel)]).unwrap(),
+            Duration::from_secs(3),
+        );
+        let mut fabricator = Fabricator::new(vec![recipe], &mut rng);
+        assert!(
+            fabricator.storages_mut()[0]
+                .try_add_item(Item::new("ore".to_string(), 10), item_registry)
+        );
+        let mut reactor = Reactor::new(&mut rng);
+        assert!(
+            reactor.storages_mut()[0]
+                .try_add_item(Item::new("fuel".to_string(), 100), item_registry)
+        );
+        let crew = PersonnelArea::new(vec![Person::random(&mut SimulationRng::new(0))], &mut rng);
+        let station = Vessel::new(
+            PersonId::nil(),
+            (0., 0.).into(),
+            vec![fabricator, crew, reactor],
+            &mut rng,
+        );
+        Environment::new(rng, vec![station], vec![])
+    }
+
+    fn stock(environment: &Environment, item: &str) -> ItemCount {
+        let fabricator = environment.vessels()[0].modules().next().unwrap();
+        fabricator.storages()[0].count(item.to_string())
+    }
+
+    /// proceeds the station `ticks` times. Returns the problems reported by the crew
+    fn proceed(
+        environment: &mut Environment,
+        ticks: usize,
+        item_registry: &ItemRegistry,
+    ) -> Vec<String> {
+        let process_token_context = ProcessTokenContext::new();
+        let decider_vault = ObjectiveDeciderVault::new().with(FabricateDecider);
+        let mut logger = ProblemLogger::default();
+        for _ in 0..ticks {
+            environment.proceed(
+                item_registry,
+                &TechTree::new(),
+                &process_token_context,
+                &decider_vault,
+                &mut logger,
+            );
+        }
+        logger.problems
+    }
+
+    #[test]
+    fn crew_runs_recipe_for_its_duration() {
+        let item_registry = register_items(ItemRegistry::new());
+        let mut environment = station(5, &item_registry);
+        // the crew member takes the objective, goes to the fabricator, starts the recipe
+        // and works on it for two seconds
+        assert!(proceed(&mut environment, 6, &item_registry).is_empty());
+        let crew = environment.vessels()[0]
+            .modules()
+            .nth(1)
+            .unwrap()
+            .person_ids();
+        assert!(crew.is_empty());
+        assert_eq!(stock(&environment, "ore"), 0);
+        assert_eq!(stock(&environment, "steel"), 0);
+
+        assert!(proceed(&mut environment, 1, &item_registry).is_empty());
+        assert_eq!(stock(&environment, "steel"), 5);
+        assert_eq!(stock(&environment, "ore"), 0);
+    }
+
+    #[test]
+    fn gives_input_back_if_output_does_not_fit() {
+        let item_registry = register_items(ItemRegistry::new());
+        // heavier than the storage can hold
+        let mut environment = station(1000, &item_registry);
+        assert!(proceed(&mut environment, 6, &item_registry).is_empty());
+        assert_eq!(stock(&environment, "ore"), 0);
+
+        assert!(proceed(&mut environment, 1, &item_registry).is_empty());
+        assert_eq!(stock(&environment, "steel"), 0);
+        assert_eq!(stock(&environment, "ore"), 10);
+        // the operator notices on the next tick
+        let problems = proceed(&mut environment, 1, &item_registry);
+        assert_eq!(problems.len(), 1);
+        assert!(problems[0].ends_with("failed: Fabrication failed"));
+    }
+}
@@ -6,3 +6,6 @@ pub use craft_modules_objective::*;
 
 mod craft_vessel_from_scratch_objective;
 pub use craft_vessel_from_scratch_objective::*;
+
+mod fabricate_items_objective;
+pub use fabricate_items_objective::*;
@@ -1,24 +1,151 @@
-use dudes_in_space_api::module::{ModuleConsole, ProcessTokenContext};
-use dudes_in_space_api::person::{Awareness, Boldness, DynObjective, Gender, Morale, Objective, ObjectiveDecider, ObjectiveStatus, Passion, PersonId, PersonLogger};
+use dudes_in_space_api::environment::{SALVAGE_RANGE, WreckId};
+use dudes_in_space_api::item::ItemCount;
+use dudes_in_space_api::module::{ModuleCapability, ModuleConsole, ProcessTokenContext};
+use dudes_in_space_api::person::{
+    Awareness, Boldness, DynObjective, Gender, Morale, Objective, ObjectiveDecider,
+    ObjectiveStatus, Passion, PersonId, PersonLogger, Severity,
+};
 use dudes_in_space_api::vessel::VesselConsole;
+use dyn_serde::{DynDeserializeSeed, DynDeserializeSeedVault, DynSerialize, TypeId};
 use serde::{Deserialize, Serialize};
+use serde_intermediate::{Intermediate, from_intermediate, to_intermediate};
 use std::error::Error;
 use std::fmt::{Display, Formatter};
 
+static TYPE_ID: &str = "GatherResearchDataObjective";
+
+/// fly to the nearest detected wreck with research data and collect it into research labs.
+/// Labs inside nebulae collect research data on their own
 #[derive(Debug, Serialize, Deserialize)]
-pub(crate) struct GatherResearchData {}
+#[serde(tag = "gather_research_data_objective_stage")]
+pub(crate) enum GatherResearchDataObjective {
+    SearchingForWreck,
+    FlyingToWreck {
+        wreck_id: WreckId,
+    },
+    Gathering {
+        wreck_id: WreckId,
+        /// research data left in the wreck when the last gathering was requested
+        left: Option<ItemCount>,
+    },
+    Done,
+}
+
+impl GatherResearchDataObjective {
+    pub(crate) fn new() -> Self {
+        Self::SearchingForWreck
+    }
+}
 
-impl Objective for GatherResearchData {
-    type Error = GatherResearchDataError;
+impl Objective for GatherResearchDataObjective {
+    type Error = GatherResearchDataObjectiveError;
 
     fn pursue(
         &mut self,
         this_module: &mut dyn ModuleConsole,
         this_vessel: &dyn VesselConsole,
         process_token_context: &ProcessTokenContext,
-        logger: PersonLogger,
+        mut logger: PersonLogger,
     ) -> Result<ObjectiveStatus, Self::Error> {
-        todo!()
+        match self {
+            Self::SearchingForWreck => {
+                if this_vessel
+                    .modules_with_cap(ModuleCapability::ResearchLab)
+                    .is_empty()
+                {
+                    return Err(GatherResearchDataObjectiveError::NoResearchLab);
+                }
+                let pos = this_vessel.pos();
+                let wreck = this_vessel
+                    .wreck_contacts()
+                    .iter()
+                    .filter(|wreck| wreck.research_data() > 0)
+                    .min_by(|a, b| (a.pos() - pos).len().total_cmp(&(b.pos() - pos).len()))
+                    .ok_or(GatherResearchDataObjectiveError::NoResearchDataDetected)?;
+                logger.log(
+                    Severity::Info,
+                    format!("Flying to wreck {} to study it", wreck.wreck_id()),
+                );
+                this_vessel.set_destination(wreck.pos());
+                *self = Self::FlyingToWreck {
+                    wreck_id: wreck.wreck_id(),
+                };
+                Ok(ObjectiveStatus::InProgress)
+            }
+            Self::FlyingToWreck { wreck_id } => {
+                let Some(wreck) = this_vessel
+                    .wreck_contacts()
+                    .iter()
+                    .find(|wreck| wreck.wreck_id() == *wreck_id && wreck.research_data() > 0)
+                else {
+                    *self = Self::SearchingForWreck;
+                    return Ok(ObjectiveStatus::InProgress);
+                };
+                if (wreck.pos() - this_vessel.pos()).len() <= SALVAGE_RANGE {
+                    this_vessel.stop();
+                    *self = Self::Gathering {
+                        wreck_id: *wreck_id,
+                        left: None,
+                    };
+                } else if this_vessel.nav_target().is_none() {
+                    this_vessel.set_destination(wreck.pos());
+                }
+                Ok(ObjectiveStatus::InProgress)
+            }
+            Self::Gathering { wreck_id, left } => {
+                let now_left = this_vessel
+                    .wreck_contacts()
+                    .iter()
+                    .find(|wreck| wreck.wreck_id() == *wreck_id)
+                    .map(|wreck| wreck.research_data())
+                    .unwrap_or(0);
+                if now_left == 0 {
+                    logger.log(
+                        Severity::Info,
+                        format!("Collected all research data from wreck {}", wreck_id),
+                    );
+                    *self = Self::Done;
+                    return Ok(ObjectiveStatus::Done);
+                }
+                if *left == Some(now_left) {
+                    logger.log(Severity::Info, "Research labs are full");
+                    *self = Self::Done;
+                    return Ok(ObjectiveStatus::Done);
+                }
+                this_vessel.gather_research_data(*wreck_id);
+                *left = Some(now_left);
+                Ok(ObjectiveStatus::InProgress)
+            }
+            Self::Done => Ok(ObjectiveStatus::Done),
+        }
+    }
+}
+
+impl DynSerialize for GatherResearchDataObjective {
+    fn type_id(&self) -> TypeId {
+        TYPE_ID.to_string()
+    }
+
+    fn serialize(&self) -> Result<Intermediate, Box<dyn Error>> {
+        to_intermediate(self).map_err(|e| e.into())
+    }
+}
+
+pub(crate) struct GatherResearchDataObjectiveDynSeed;
+
+impl DynDeserializeSeed<dyn DynObjective> for GatherResearchDataObjectiveDynSeed {
+    fn type_id(&self) -> TypeId {
+        TYPE_ID.to_string()
+    }
+
+    fn deserialize(
+        &self,
+        intermediate: Intermediate,
+        _: &DynDeserializeSeedVault<dyn DynObjective>,
+    ) -> Result<Box<dyn DynObjective>, Box<dyn Error>> {
+        let obj: GatherResearchDataObjective =
+            from_intermediate(&intermediate).map_err(|e| e.to_string())?;
+        Ok(Box::new(obj))
     }
 }
 
@@ -35,17 +162,161 @@ impl ObjectiveDecider for GatherResearchDataObjectiveDecider {
         boldness: Boldness,
         awareness: Awareness,
     ) -> Option<Box<dyn DynObjective>> {
-        None
+        if passions.contains(&Passion::Crafting) || passions.contains(&Passion::Adventuring) {
+            Some(Box::new(GatherResearchDataObjective::new()))
+        } else {
+            None
+        }
     }
 }
 
 #[derive(Debug)]
-pub(crate) enum GatherResearchDataError {}
+pub(crate) enum GatherResearchDataObjectiveError {
+    NoResearchLab,
+    NoResearchDataDetected,
+}
 
-impl Display for GatherResearchDataError {
+impl Display for GatherResearchDataObjectiveError {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        todo!()
+        match self {
+            GatherResearchDataObjectiveError::NoResearchLab => {
+                write!(f, "Vessel has no research labs")
+            }
+            GatherResearchDataObjectiveError::NoResearchDataDetected => {
+                write!(f, "No wrecks with research data detected")
+            }
+        }
     }
 }
 
-impl Error for GatherResearchDataError {}
+impl Error for GatherResearchDataObjectiveError {}
+
+#[cfg(test)]
+mod tests {
+    use super::GatherResearchDataObjective;
+    use crate::items::register_items;
+    use crate::modules::{MiningLaser, Radar, Reactor, ResearchLab, ShuttleFactory};
+    use dudes_in_space_api::environment::{Environment, Nebula};
+    use dudes_in_space_api::item::{Item, ItemRegistry};
+    use dudes_in_space_api::module::{DefaultModuleConsole, Module, ModuleId, ProcessTokenContext};
+    use dudes_in_space_api::person::{
+        Logger, Objective, ObjectiveDeciderVault, ObjectiveStatus, PersonId, PersonLogger, Severity,
+    };
+    use dudes_in_space_api::recipe::{InputRecipe, ModuleFactory};
+    use dudes_in_space_api::research::{Tech, TechTree};
+    use dudes_in_space_api::utils::simulation_rng::SimulationRng;
+    use dudes_in_space_api::vessel::{Vessel, VesselModuleInterface};
+
+    struct NoLogger;
+
+    impl Logger for NoLogger {
+        fn log(&mut self, _: &PersonId, _: Severity, _: String) {}
+    }
+
+    #[test]
+    fn research_unlocks_modules() {
+        let mut rng = SimulationRng::new(0);
+        let item_registry = register_items(ItemRegistry::new());
+        let tech_tree = TechTree::new().with(Tech::new(
+            "mining".to_string(),
+            "Mining".to_string(),
+            10,
+            vec![],
+            vec!["MiningLaser".to_string()],
+        ));
+        let mut shuttle =
+            ShuttleFactory {}.create(&InputRecipe::try_from(vec![]).unwrap(), &mut rng);
+        assert!(
+            shuttle.storages_mut()[0]
+                .try_add_item(Item::new("fuel".to_string(), 10), &item_registry)
+        );
+        let mut reactor = Reactor::new(&mut rng);
+        assert!(
+            reactor.storages_mut()[0]
+                .try_add_item(Item::new("fuel".to_string(), 100), &item_registry)
+        );
+        let vessel = Vessel::new(
+            PersonId::nil(),
+            (0., 0.).into(),
+            vec![
+                shuttle,
+                reactor,
+                Radar::new(&mut rng),
+                ResearchLab::new(&mut rng),
+            ],
+            &mut rng,
+        );
+        let derelict = Vessel::new(
+            PersonId::nil(),
+            (300., 0.).into(),
+            vec![
+                Radar::new(&mut rng),
+                MiningLaser::new(&mut rng),
+                Radar::new(&mut rng),
+                Radar::new(&mut rng),
+            ],
+            &mut rng,
+        );
+        let derelict_id = derelict.id();
+        let nebula = Nebula::new(
+            vec![
+                (-10., -10.).into(),
+                (10., -10.).into(),
+                (10., 10.).into(),
+                (-10., 10.).into(),
+            ],
+            1.,
+            1.,
+        )
+        .with_research_data(3);
+
+        let mut environment = Environment::new(rng, vec![vessel, derelict], vec![nebula]);
+        assert!(environment.wreck_vessel(derelict_id, &item_registry));
+        let wreck_data = environment.wrecks()[0].research_data();
+        assert!(wreck_data > 0);
+
+        let process_token_context = ProcessTokenContext::new();
+        let decider_vault = ObjectiveDeciderVault::new();
+        let proceed = |environment: &mut Environment| {
+            environment.proceed(
+                &item_registry,
+                &tech_tree,
+                &process_token_context,
+                &decider_vault,
+                &mut NoLogger,
+            )
+        };
+
+        proceed(&mut environment);
+        assert!(environment.vessels()[0].is_module_locked(&"MiningLaser".to_string()));
+        assert_eq!(environment.nebulae()[0].research_data(), 2);
+
+        let mut objective = GatherResearchDataObjective::new();
+        let mut status = ObjectiveStatus::InProgress;
+        for _ in 0..200 {
+            status = objective
+                .pursue(
+                    &mut DefaultModuleConsole::new(ModuleId::nil()),
+                    &environment.vessels()[0],
+                    &process_token_context,
+                    PersonLogger::new(&PersonId::nil(), &mut NoLogger),
+                )
+                .unwrap();
+            if status == ObjectiveStatus::Done {
+                break;
+            }
+            proceed(&mut environment);
+        }
+        assert_eq!(status, ObjectiveStatus::Done);
+        assert!(environment.wrecks().iter().all(|w| w.research_data() == 0));
+
+        // let the lab process everything
+        for _ in 0..wreck_data + 10 {
+            proceed(&mut environment);
+        }
+        let research = environment.research(PersonId::nil()).unwrap();
+        assert!(research.is_researched(&"mining".to_string()));
+        assert_eq!(research.data() + 10, wreck_data + 3);
+        assert!(!environment.vessels()[0].is_module_locked(&"MiningLaser".to_string()));
+    }
+}
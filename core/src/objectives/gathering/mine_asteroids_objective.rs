@@ -1,12 +1,65 @@
-use dudes_in_space_api::module::{ModuleConsole, ProcessTokenContext};
-use dudes_in_space_api::person::{Awareness, Boldness, DynObjective, Gender, Morale, Objective, ObjectiveDecider, ObjectiveStatus, Passion, PersonId, PersonLogger};
+use dudes_in_space_api::environment::AsteroidId;
+use dudes_in_space_api::item::{ItemCount, ItemId};
+use dudes_in_space_api::module::{ModuleCapability, ModuleConsole, ProcessTokenContext};
+use dudes_in_space_api::person::{
+    Awareness, Boldness, DynObjective, Gender, Morale, Objective, ObjectiveDecider,
+    ObjectiveStatus, Passion, PersonId, PersonLogger, Severity,
+};
+use dudes_in_space_api::utils::utils::Float;
 use dudes_in_space_api::vessel::VesselConsole;
+use dyn_serde::{DynDeserializeSeed, DynDeserializeSeedVault, DynSerialize, TypeId};
 use serde::{Deserialize, Serialize};
+use serde_intermediate::{Intermediate, from_intermediate, to_intermediate};
 use std::error::Error;
 use std::fmt::{Display, Formatter};
 
+static TYPE_ID: &str = "MineAsteroidsObjective";
+
+/// fly to the nearest detected asteroid and mine it until item storages of the vessel are full
 #[derive(Debug, Serialize, Deserialize)]
-pub(crate) struct MineAsteroidsObjective {}
+#[serde(tag = "mine_asteroids_objective_stage")]
+pub(crate) enum MineAsteroidsObjective {
+    SearchingForAsteroid,
+    FlyingToAsteroid {
+        asteroid_id: AsteroidId,
+    },
+    Mining {
+        asteroid_id: AsteroidId,
+        ore: ItemId,
+        /// ore in item storages when the last tick of mining was requested
+        stored: Option<ItemCount>,
+    },
+    Done,
+}
+
+impl MineAsteroidsObjective {
+    pub(crate) fn new() -> Self {
+        Self::SearchingForAsteroid
+    }
+
+    /// range of the shortest reaching mining laser. `None` if the vessel has no mining lasers
+    fn mining_range(this_vessel: &dyn VesselConsole) -> Option<Float> {
+        this_vessel
+            .modules_with_cap(ModuleCapability::MiningLaser)
+            .iter()
+            .filter_map(|module| module.mining_laser())
+            .map(|laser| laser.range)
+            .reduce(Float::min)
+    }
+
+    fn stored(this_vessel: &dyn VesselConsole, ore: &ItemId) -> ItemCount {
+        this_vessel
+            .modules_with_cap(ModuleCapability::ItemStorage)
+            .iter()
+            .flat_map(|module| {
+                module
+                    .storages()
+                    .iter()
+                    .map(|storage| storage.count(ore.clone()))
+            })
+            .sum()
+    }
+}
 
 impl Objective for MineAsteroidsObjective {
     type Error = MineAsteroidsObjectiveError;
@@ -16,9 +69,114 @@ impl Objective for MineAsteroidsObjective {
         this_module: &mut dyn ModuleConsole,
         this_vessel: &dyn VesselConsole,
         process_token_context: &ProcessTokenContext,
-        logger: PersonLogger,
+        mut logger: PersonLogger,
     ) -> Result<ObjectiveStatus, Self::Error> {
-        todo!()
+        match self {
+            Self::SearchingForAsteroid => {
+                if Self::mining_range(this_vessel).is_none() {
+                    return Err(MineAsteroidsObjectiveError::NoMiningLaser);
+                }
+                let pos = this_vessel.pos();
+                let asteroid = this_vessel
+                    .asteroid_contacts()
+                    .iter()
+                    .filter(|asteroid| asteroid.amount() > 0)
+                    .min_by(|a, b| (a.pos() - pos).len().total_cmp(&(b.pos() - pos).len()))
+                    .ok_or(MineAsteroidsObjectiveError::NoAsteroidsDetected)?;
+                logger.log(
+                    Severity::Info,
+                    format!(
+                        "Flying to asteroid {} to mine {}",
+                        asteroid.asteroid_id(),
+                        asteroid.ore()
+                    ),
+                );
+                this_vessel.set_destination(asteroid.pos());
+                *self = Self::FlyingToAsteroid {
+                    asteroid_id: asteroid.asteroid_id(),
+                };
+                Ok(ObjectiveStatus::InProgress)
+            }
+            Self::FlyingToAsteroid { asteroid_id } => {
+                let Some(asteroid) = this_vessel
+                    .asteroid_contacts()
+                    .iter()
+                    .find(|asteroid| asteroid.asteroid_id() == *asteroid_id)
+                else {
+                    *self = Self::SearchingForAsteroid;
+                    return Ok(ObjectiveStatus::InProgress);
+                };
+                let range = Self::mining_range(this_vessel)
+                    .ok_or(MineAsteroidsObjectiveError::NoMiningLaser)?;
+                if (asteroid.pos() - this_vessel.pos()).len() <= range {
+                    this_vessel.stop();
+                    *self = Self::Mining {
+                        asteroid_id: *asteroid_id,
+                        ore: asteroid.ore().clone(),
+                        stored: None,
+                    };
+                } else if this_vessel.nav_target().is_none() {
+                    this_vessel.set_destination(asteroid.pos());
+                }
+                Ok(ObjectiveStatus::InProgress)
+            }
+            Self::Mining {
+                asteroid_id,
+                ore,
+                stored,
+            } => {
+                let depleted = this_vessel
+                    .asteroid_contacts()
+                    .iter()
+                    .find(|asteroid| asteroid.asteroid_id() == *asteroid_id)
+                    .map(|asteroid| asteroid.amount() == 0)
+                    .unwrap_or(true);
+                if depleted {
+                    *self = Self::SearchingForAsteroid;
+                    return Ok(ObjectiveStatus::InProgress);
+                }
+
+                let now_stored = Self::stored(this_vessel, ore);
+                if stored.map(|stored| now_stored <= stored).unwrap_or(false) {
+                    logger.log(Severity::Info, format!("Mined {} {}", now_stored, ore));
+                    *self = Self::Done;
+                    return Ok(ObjectiveStatus::Done);
+                }
+
+                this_vessel.mine(*asteroid_id);
+                *stored = Some(now_stored);
+                Ok(ObjectiveStatus::InProgress)
+            }
+            Self::Done => Ok(ObjectiveStatus::Done),
+        }
+    }
+}
+
+impl DynSerialize for MineAsteroidsObjective {
+    fn type_id(&self) -> TypeId {
+        TYPE_ID.to_string()
+    }
+
+    fn serialize(&self) -> Result<Intermediate, Box<dyn Error>> {
+        to_intermediate(self).map_err(|e| e.into())
+    }
+}
+
+pub(crate) struct MineAsteroidsObjectiveDynSeed;
+
+impl DynDeserializeSeed<dyn DynObjective> for MineAsteroidsObjectiveDynSeed {
+    fn type_id(&self) -> TypeId {
+        TYPE_ID.to_string()
+    }
+
+    fn deserialize(
+        &self,
+        intermediate: Intermediate,
+        _: &DynDeserializeSeedVault<dyn DynObjective>,
+    ) -> Result<Box<dyn DynObjective>, Box<dyn Error>> {
+        let obj: MineAsteroidsObjective =
+            from_intermediate(&intermediate).map_err(|e| e.to_string())?;
+        Ok(Box::new(obj))
     }
 }
 
@@ -35,17 +193,113 @@ impl ObjectiveDecider for MineAsteroidsObjectiveDecider {
         boldness: Boldness,
         awareness: Awareness,
     ) -> Option<Box<dyn DynObjective>> {
-        None
+        if passions.contains(&Passion::Money) || passions.contains(&Passion::Flying) {
+            Some(Box::new(MineAsteroidsObjective::new()))
+        } else {
+            None
+        }
     }
 }
 
 #[derive(Debug)]
-pub(crate) enum MineAsteroidsObjectiveError {}
+pub(crate) enum MineAsteroidsObjectiveError {
+    NoMiningLaser,
+    NoAsteroidsDetected,
+}
 
 impl Display for MineAsteroidsObjectiveError {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        todo!()
+        match self {
+            MineAsteroidsObjectiveError::NoMiningLaser => {
+                write!(f, "Vessel has no mining lasers")
+            }
+            MineAsteroidsObjectiveError::NoAsteroidsDetected => {
+                write!(f, "No asteroids with ore detected")
+            }
+        }
     }
 }
 
 impl Error for MineAsteroidsObjectiveError {}
+
+#[cfg(test)]
+mod tests {
+    use super::MineAsteroidsObjective;
+    use crate::items::register_items;
+    use crate::modules::{MiningLaser, Radar, ShuttleFactory};
+    use dudes_in_space_api::environment::{Asteroid, Environment};
+    use dudes_in_space_api::item::{Item, ItemRegistry};
+    use dudes_in_space_api::module::{DefaultModuleConsole, ModuleId, ProcessTokenContext};
+    use dudes_in_space_api::person::{
+        Logger, Objective, ObjectiveDeciderVault, ObjectiveStatus, PersonId, PersonLogger, Severity,
+    };
+    use dudes_in_space_api::recipe::{InputRecipe, ModuleFactory};
+    use dudes_in_space_api::research::TechTree;
+    use dudes_in_space_api::utils::simulation_rng::SimulationRng;
+    use dudes_in_space_api::vessel::Vessel;
+
+    struct NoLogger;
+
+    impl Logger for NoLogger {
+        fn log(&mut self, _: &PersonId, _: Severity, _: String) {}
+    }
+
+    #[test]
+    fn mines_until_hold_is_full() {
+        let mut rng = SimulationRng::new(0);
+        let item_registry = register_items(ItemRegistry::new());
+        let mut shuttle =
+            ShuttleFactory {}.create(&InputRecipe::try_from(vec![]).unwrap(), &mut rng);
+        assert!(
+            shuttle.storages_mut()[0]
+                .try_add_item(Item::new("fuel".to_string(), 10), &item_registry)
+        );
+        let vessel = Vessel::new(
+            PersonId::nil(),
+            (0., 0.).into(),
+            vec![shuttle, Radar::new(&mut rng), MiningLaser::new(&mut rng)],
+            &mut rng,
+        );
+        let asteroid = Asteroid::new((300., 0.).into(), "ore".to_string(), 200, 0., &mut rng);
+        let mut environment =
+            Environment::new(rng, vec![vessel], vec![]).with_asteroids(vec![asteroid]);
+
+        let process_token_context = ProcessTokenContext::new();
+        let decider_vault = ObjectiveDeciderVault::new();
+        let mut proceed = |environment: &mut Environment| {
+            environment.proceed(
+                &item_registry,
+                &TechTree::new(),
+                &process_token_context,
+                &decider_vault,
+                &mut NoLogger,
+            )
+        };
+
+        // first radar scan
+        proceed(&mut environment);
+
+        let mut objective = MineAsteroidsObjective::new();
+        let mut status = ObjectiveStatus::InProgress;
+        for _ in 0..200 {
+            status = objective
+                .pursue(
+                    &mut DefaultModuleConsole::new(ModuleId::nil()),
+                    &environment.vessels()[0],
+                    &process_token_context,
+                    PersonLogger::new(&PersonId::nil(), &mut NoLogger),
+                )
+                .unwrap();
+            if status == ObjectiveStatus::Done {
+                break;
+            }
+            proceed(&mut environment);
+        }
+
+        assert_eq!(status, ObjectiveStatus::Done);
+        let vessel = &environment.vessels()[0];
+        let laser = vessel.modules().nth(2).unwrap();
+        assert_eq!(laser.storages()[0].count("ore".to_string()), 100);
+        assert_eq!(environment.asteroids()[0].amount(), 100);
+    }
+}
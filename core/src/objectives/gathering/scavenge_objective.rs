@@ -1,12 +1,49 @@
-use dudes_in_space_api::module::{ModuleConsole, ProcessTokenContext};
-use dudes_in_space_api::person::{Awareness, Boldness, DynObjective, Gender, Morale, Objective, ObjectiveDecider, ObjectiveStatus, Passion, PersonId, PersonLogger};
+use dudes_in_space_api::environment::{SALVAGE_RANGE, WreckId};
+use dudes_in_space_api::item::ItemCount;
+use dudes_in_space_api::module::{ModuleCapability, ModuleConsole, ProcessTokenContext};
+use dudes_in_space_api::person::{
+    Awareness, Boldness, DynObjective, Gender, Morale, Objective, ObjectiveDecider,
+    ObjectiveStatus, Passion, PersonId, PersonLogger, Severity,
+};
 use dudes_in_space_api::vessel::VesselConsole;
+use dyn_serde::{DynDeserializeSeed, DynDeserializeSeedVault, DynSerialize, TypeId};
 use serde::{Deserialize, Serialize};
+use serde_intermediate::{Intermediate, from_intermediate, to_intermediate};
 use std::error::Error;
 use std::fmt::{Display, Formatter};
 
+static TYPE_ID: &str = "ScavengeObjective";
+
+/// fly to the nearest detected wreck and salvage its modules and items until it is empty or there is no space left
 #[derive(Debug, Serialize, Deserialize)]
-pub(crate) struct ScavengeObjective {}
+#[serde(tag = "scavenge_objective_stage")]
+pub(crate) enum ScavengeObjective {
+    SearchingForWreck,
+    FlyingToWreck {
+        wreck_id: WreckId,
+    },
+    Salvaging {
+        wreck_id: WreckId,
+        /// modules and items left in the wreck when the last salvage was requested
+        left: Option<(usize, ItemCount)>,
+    },
+    Done,
+}
+
+impl ScavengeObjective {
+    pub(crate) fn new() -> Self {
+        Self::SearchingForWreck
+    }
+
+    fn has_cargo_space(this_vessel: &dyn VesselConsole) -> bool {
+        !this_vessel
+            .modules_with_cap(ModuleCapability::ModuleStorage)
+            .is_empty()
+            || !this_vessel
+                .modules_with_cap(ModuleCapability::ItemStorage)
+                .is_empty()
+    }
+}
 
 impl Objective for ScavengeObjective {
     type Error = ScavengeObjectiveError;
@@ -16,9 +53,105 @@ impl Objective for ScavengeObjective {
         this_module: &mut dyn ModuleConsole,
         this_vessel: &dyn VesselConsole,
         process_token_context: &ProcessTokenContext,
-        logger: PersonLogger,
+        mut logger: PersonLogger,
     ) -> Result<ObjectiveStatus, Self::Error> {
-        todo!()
+        match self {
+            Self::SearchingForWreck => {
+                if !Self::has_cargo_space(this_vessel) {
+                    return Err(ScavengeObjectiveError::NoCargoSpace);
+                }
+                let pos = this_vessel.pos();
+                let wreck = this_vessel
+                    .wreck_contacts()
+                    .iter()
+                    .filter(|wreck| wreck.module_count() > 0 || wreck.item_count() > 0)
+                    .min_by(|a, b| (a.pos() - pos).len().total_cmp(&(b.pos() - pos).len()))
+                    .ok_or(ScavengeObjectiveError::NoWrecksDetected)?;
+                logger.log(
+                    Severity::Info,
+                    format!("Flying to wreck {}", wreck.wreck_id()),
+                );
+                this_vessel.set_destination(wreck.pos());
+                *self = Self::FlyingToWreck {
+                    wreck_id: wreck.wreck_id(),
+                };
+                Ok(ObjectiveStatus::InProgress)
+            }
+            Self::FlyingToWreck { wreck_id } => {
+                let Some(wreck) = this_vessel
+                    .wreck_contacts()
+                    .iter()
+                    .find(|wreck| wreck.wreck_id() == *wreck_id)
+                else {
+                    // salvaged by someone else or out of radar range
+                    *self = Self::SearchingForWreck;
+                    return Ok(ObjectiveStatus::InProgress);
+                };
+                if (wreck.pos() - this_vessel.pos()).len() <= SALVAGE_RANGE {
+                    this_vessel.stop();
+                    *self = Self::Salvaging {
+                        wreck_id: *wreck_id,
+                        left: None,
+                    };
+                } else if this_vessel.nav_target().is_none() {
+                    this_vessel.set_destination(wreck.pos());
+                }
+                Ok(ObjectiveStatus::InProgress)
+            }
+            Self::Salvaging { wreck_id, left } => {
+                let now_left = this_vessel
+                    .wreck_contacts()
+                    .iter()
+                    .find(|wreck| wreck.wreck_id() == *wreck_id)
+                    .map(|wreck| (wreck.module_count(), wreck.item_count()))
+                    .unwrap_or((0, 0));
+                // a wreck with research data left stays after salvage
+                if now_left == (0, 0) {
+                    logger.log(Severity::Info, format!("Salvaged wreck {}", wreck_id));
+                    *self = Self::Done;
+                    return Ok(ObjectiveStatus::Done);
+                }
+                if *left == Some(now_left) {
+                    logger.log(
+                        Severity::Info,
+                        format!("No space left to salvage wreck {}", wreck_id),
+                    );
+                    *self = Self::Done;
+                    return Ok(ObjectiveStatus::Done);
+                }
+                this_vessel.salvage(*wreck_id);
+                *left = Some(now_left);
+                Ok(ObjectiveStatus::InProgress)
+            }
+            Self::Done => Ok(ObjectiveStatus::Done),
+        }
+    }
+}
+
+impl DynSerialize for ScavengeObjective {
+    fn type_id(&self) -> TypeId {
+        TYPE_ID.to_string()
+    }
+
+    fn serialize(&self) -> Result<Intermediate, Box<dyn Error>> {
+        to_intermediate(self).map_err(|e| e.into())
+    }
+}
+
+pub(crate) struct ScavengeObjectiveDynSeed;
+
+impl DynDeserializeSeed<dyn DynObjective> for ScavengeObjectiveDynSeed {
+    fn type_id(&self) -> TypeId {
+        TYPE_ID.to_string()
+    }
+
+    fn deserialize(
+        &self,
+        intermediate: Intermediate,
+        _: &DynDeserializeSeedVault<dyn DynObjective>,
+    ) -> Result<Box<dyn DynObjective>, Box<dyn Error>> {
+        let obj: ScavengeObjective = from_intermediate(&intermediate).map_err(|e| e.to_string())?;
+        Ok(Box::new(obj))
     }
 }
 
@@ -35,17 +168,209 @@ impl ObjectiveDecider for ScavengeObjectiveDecider {
         boldness: Boldness,
         awareness: Awareness,
     ) -> Option<Box<dyn DynObjective>> {
-        None
+        if passions.contains(&Passion::Adventuring) {
+            Some(Box::new(ScavengeObjective::new()))
+        } else {
+            None
+        }
     }
 }
 
 #[derive(Debug)]
-pub(crate) enum ScavengeObjectiveError {}
+pub(crate) enum ScavengeObjectiveError {
+    NoCargoSpace,
+    NoWrecksDetected,
+}
 
 impl Display for ScavengeObjectiveError {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        todo!()
+        match self {
+            ScavengeObjectiveError::NoCargoSpace => {
+                write!(f, "Vessel has neither module nor item storages")
+            }
+            ScavengeObjectiveError::NoWrecksDetected => write!(f, "No wrecks detected"),
+        }
     }
 }
 
 impl Error for ScavengeObjectiveError {}
+
+#[cfg(test)]
+mod tests {
+    use super::ScavengeObjective;
+    use crate::items::register_items;
+    use crate::modules::{Assembler, Dockyard, MiningLaser, Radar, Reactor, ShuttleFactory};
+    use dudes_in_space_api::environment::Environment;
+    use dudes_in_space_api::item::{Item, ItemCount, ItemRegistry};
+    use dudes_in_space_api::module::{DefaultModuleConsole, Module, ModuleId, ProcessTokenContext};
+    use dudes_in_space_api::person::{
+        Logger, Objective, ObjectiveDeciderVault, ObjectiveStatus, PersonId, PersonLogger, Severity,
+    };
+    use dudes_in_space_api::recipe::{InputRecipe, ModuleFactory};
+    use dudes_in_space_api::research::TechTree;
+    use dudes_in_space_api::utils::simulation_rng::SimulationRng;
+    use dudes_in_space_api::vessel::{Vessel, VesselConsole};
+
+    struct NoLogger;
+
+    impl Logger for NoLogger {
+        fn log(&mut self, _: &PersonId, _: Severity, _: String) {}
+    }
+
+    #[test]
+    fn salvages_wreck() {
+        let mut rng = SimulationRng::new(0);
+        let item_registry = register_items(ItemRegistry::new());
+        let mut shuttle =
+            ShuttleFactory {}.create(&InputRecipe::try_from(vec![]).unwrap(), &mut rng);
+        assert!(
+            shuttle.storages_mut()[0]
+                .try_add_item(Item::new("fuel".to_string(), 10), &item_registry)
+        );
+        let mut reactor = Reactor::new(&mut rng);
+        assert!(
+            reactor.storages_mut()[0]
+                .try_add_item(Item::new("fuel".to_string(), 100), &item_registry)
+        );
+        let vessel = Vessel::new(
+            PersonId::nil(),
+            (0., 0.).into(),
+            vec![
+                shuttle,
+                reactor,
+                Radar::new(&mut rng),
+                Box::new(Dockyard::new(&mut rng)),
+                MiningLaser::new(&mut rng),
+            ],
+            &mut rng,
+        );
+
+        let mut laser = MiningLaser::new(&mut rng);
+        assert!(
+            laser.storages_mut()[0].try_add_item(Item::new("ore".to_string(), 50), &item_registry)
+        );
+        let derelict = Vessel::new(
+            PersonId::nil(),
+            (300., 0.).into(),
+            vec![
+                Radar::new(&mut rng),
+                MiningLaser::new(&mut rng),
+                Radar::new(&mut rng),
+                laser,
+            ],
+            &mut rng,
+        );
+        let derelict_id = derelict.id();
+
+        let mut environment = Environment::new(rng, vec![vessel, derelict], vec![]);
+        assert!(environment.wreck_vessel(derelict_id, &item_registry));
+        assert_eq!(environment.vessels().len(), 1);
+        let wreck = &environment.wrecks()[0];
+        let wreck_modules = wreck.modules().count();
+        let wreck_ore = wreck.items().count("ore".to_string());
+        assert!(wreck_modules > 0);
+        assert!(wreck_ore > 0);
+
+        let process_token_context = ProcessTokenContext::new();
+        let decider_vault = ObjectiveDeciderVault::new();
+        let proceed = |environment: &mut Environment| {
+            environment.proceed(
+                &item_registry,
+                &TechTree::new(),
+                &process_token_context,
+                &decider_vault,
+                &mut NoLogger,
+            )
+        };
+
+        // first radar scan
+        proceed(&mut environment);
+
+        let mut objective = ScavengeObjective::new();
+        let mut status = ObjectiveStatus::InProgress;
+        for _ in 0..200 {
+            status = objective
+                .pursue(
+                    &mut DefaultModuleConsole::new(ModuleId::nil()),
+                    &environment.vessels()[0],
+                    &process_token_context,
+                    PersonLogger::new(&PersonId::nil(), &mut NoLogger),
+                )
+                .unwrap();
+            if status == ObjectiveStatus::Done {
+                break;
+            }
+            proceed(&mut environment);
+        }
+
+        assert_eq!(status, ObjectiveStatus::Done);
+        let wreck = &environment.wrecks()[0];
+        assert_eq!(wreck.modules().count(), 0);
+        assert!(wreck.items().is_empty());
+        let vessel = &environment.vessels()[0];
+        let dockyard = vessel.modules().nth(3).unwrap();
+        assert_eq!(dockyard.module_storages()[0].len(), wreck_modules);
+        let ore: ItemCount = vessel
+            .modules()
+            .flat_map(|module| {
+                module
+                    .storages()
+                    .iter()
+                    .map(|storage| storage.count("ore".to_string()))
+                    .collect::<Vec<_>>()
+            })
+            .sum();
+        assert_eq!(ore, wreck_ore);
+    }
+
+    #[test]
+    fn salvages_wreck_with_assembler_aboard() {
+        let mut rng = SimulationRng::new(0);
+        let item_registry = register_items(ItemRegistry::new());
+        let mut reactor = Reactor::new(&mut rng);
+        assert!(
+            reactor.storages_mut()[0]
+                .try_add_item(Item::new("fuel".to_string(), 100), &item_registry)
+        );
+        let vessel = Vessel::new(
+            PersonId::nil(),
+            (0., 0.).into(),
+            vec![
+                Assembler::new(vec![], &mut rng),
+                reactor,
+                Box::new(Dockyard::new(&mut rng)),
+            ],
+            &mut rng,
+        );
+        let derelict = Vessel::new(
+            PersonId::nil(),
+            (10., 0.).into(),
+            vec![
+                Radar::new(&mut rng),
+                Radar::new(&mut rng),
+                Radar::new(&mut rng),
+            ],
+            &mut rng,
+        );
+        let derelict_id = derelict.id();
+
+        let mut environment = Environment::new(rng, vec![vessel, derelict], vec![]);
+        assert!(environment.wreck_vessel(derelict_id, &item_registry));
+        let wreck_id = environment.wrecks()[0].id();
+        let wreck_modules = environment.wrecks()[0].modules().count();
+        assert!(wreck_modules > 0);
+
+        environment.vessels()[0].salvage(wreck_id);
+        environment.proceed(
+            &item_registry,
+            &TechTree::new(),
+            &ProcessTokenContext::new(),
+            &ObjectiveDeciderVault::new(),
+            &mut NoLogger,
+        );
+
+        assert_eq!(environment.wrecks()[0].modules().count(), 0);
+        let dockyard = environment.vessels()[0].modules().nth(2).unwrap();
+        assert_eq!(dockyard.module_storages()[0].len(), wreck_modules);
+    }
+}
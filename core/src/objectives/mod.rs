@@ -1,11 +1,21 @@
 mod crafting;
 mod gathering;
 mod trading;
+mod transport;
 
+use crate::objectives::crafting::{
+    CraftModulesObjectiveDynSeed, FabricateItemsObjectiveDecider, FabricateItemsObjectiveDynSeed,
+};
 use crate::objectives::gathering::{
-    GatherResearchDataObjectiveDecider, MineAsteroidsObjectiveDecider, ScavengeObjectiveDecider,
+    GatherResearchDataObjectiveDecider, GatherResearchDataObjectiveDynSeed,
+    MineAsteroidsObjectiveDecider, MineAsteroidsObjectiveDynSeed, ScavengeObjectiveDecider,
+    ScavengeObjectiveDynSeed,
+};
+use crate::objectives::trading::{
+    DeliverContractObjectiveDecider, DeliverContractObjectiveDynSeed, TradeObjectiveDecider,
+    TradeObjectiveDynSeed,
 };
-use crate::objectives::trading::TradeObjectiveDecider;
+use crate::objectives::transport::BoardVesselObjectiveDynSeed;
 use dudes_in_space_api::person::{DynObjective, ObjectiveDeciderVault};
 use dyn_serde::DynDeserializeSeedVault;
 
@@ -13,12 +23,22 @@ pub fn register_objectives(
     vault: DynDeserializeSeedVault<dyn DynObjective>,
 ) -> DynDeserializeSeedVault<dyn DynObjective> {
     vault
+        .with(MineAsteroidsObjectiveDynSeed)
+        .with(ScavengeObjectiveDynSeed)
+        .with(GatherResearchDataObjectiveDynSeed)
+        .with(TradeObjectiveDynSeed)
+        .with(DeliverContractObjectiveDynSeed)
+        .with(BoardVesselObjectiveDynSeed)
+        .with(FabricateItemsObjectiveDynSeed)
+        .with(CraftModulesObjectiveDynSeed)
 }
 
 pub fn register_objective_deciders(vault: ObjectiveDeciderVault) -> ObjectiveDeciderVault {
     vault
         .with(TradeObjectiveDecider)
+        .with(DeliverContractObjectiveDecider)
         .with(GatherResearchDataObjectiveDecider)
         .with(MineAsteroidsObjectiveDecider)
         .with(ScavengeObjectiveDecider)
+        .with(FabricateItemsObjectiveDecider)
 }
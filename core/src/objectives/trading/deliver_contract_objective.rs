@@ -0,0 +1,533 @@
+use crate::objectives::trading::{
+    OrderProgress, PendingTrade, PendingTradeError, cargo_count, find_terminal, travel_cost,
+};
+use dudes_in_space_api::item::{ContractId, Item, ItemCount, Money, OfferId, OrderId};
+use dudes_in_space_api::module::{ModuleCapability, ModuleConsole, ProcessTokenContext};
+use dudes_in_space_api::person::{
+    Awareness, Boldness, DynObjective, Gender, Morale, Objective, ObjectiveDecider,
+    ObjectiveStatus, Passion, PersonId, PersonLogger, Severity,
+};
+use dudes_in_space_api::utils::utils::Float;
+use dudes_in_space_api::vessel::{TerminalRef, VesselConsole};
+use dyn_serde::{DynDeserializeSeed, DynDeserializeSeedVault, DynSerialize, TypeId};
+use serde::{Deserialize, Serialize};
+use serde_intermediate::{Intermediate, from_intermediate, to_intermediate};
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+static TYPE_ID: &str = "DeliverContractObjective";
+
+/// buy offer of another terminal the missing contract items are bought at
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub(crate) struct Supplier {
+    terminal: TerminalRef,
+    offer_id: OfferId,
+    count: ItemCount,
+}
+
+/// accepted contract
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct Delivery {
+    terminal: TerminalRef,
+    contract_id: ContractId,
+    /// sell order paying the reward
+    order_id: OrderId,
+    item: Item,
+}
+
+/// accept the most profitable delivery contract posted by a detected terminal,
+/// buy the missing items at another terminal and deliver them before the deadline
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "deliver_contract_objective_stage")]
+pub(crate) enum DeliverContractObjective {
+    SearchForContract,
+    AcceptContract {
+        terminal: TerminalRef,
+        contract_id: ContractId,
+        supplier: Option<Supplier>,
+    },
+    MoveToVesselToBuy {
+        delivery: Delivery,
+        trade: PendingTrade,
+    },
+    MoveToVesselToDeliver {
+        delivery: Delivery,
+        trade: PendingTrade,
+    },
+    Done,
+}
+
+/// profitable contract found in terminal contacts
+struct ContractPlan {
+    terminal: TerminalRef,
+    contract_id: ContractId,
+    supplier: Option<Supplier>,
+    profit: Float,
+}
+
+impl DeliverContractObjective {
+    pub(crate) fn new() -> Self {
+        Self::SearchForContract
+    }
+
+    fn has_cargo_space(this_vessel: &dyn VesselConsole) -> bool {
+        !this_vessel
+            .modules_with_cap(ModuleCapability::ItemStorage)
+            .is_empty()
+    }
+
+    /// most profitable open contract for a single kind of item the owner can afford
+    /// the collateral and the missing items for
+    fn plan_contract(this_vessel: &dyn VesselConsole) -> Option<ContractPlan> {
+        let pos = this_vessel.pos();
+        let balance = this_vessel.owner_balance();
+        let terminals = this_vessel.terminal_contacts();
+        terminals
+            .iter()
+            .flat_map(|terminal| {
+                terminal
+                    .contracts()
+                    .iter()
+                    .map(move |contract| (terminal, contract))
+            })
+            .filter(|(_, contract)| contract.contractor().is_none() && contract.items().len() == 1)
+            .filter_map(|(terminal, contract)| {
+                let item = &contract.items()[0];
+                let needed = item
+                    .count()
+                    .saturating_sub(cargo_count(this_vessel, item.id()));
+                let (supplier, cost, route) = if needed == 0 {
+                    (None, 0, (terminal.pos() - pos).len())
+                } else {
+                    if this_vessel.cargo_space(item.id()) < needed {
+                        return None;
+                    }
+                    let (seller, offer) = terminals
+                        .iter()
+                        .filter(|seller| TerminalRef::from(*seller) != TerminalRef::from(terminal))
+                        .flat_map(|seller| {
+                            seller.buy_offers().iter().map(move |offer| (seller, offer))
+                        })
+                        .filter(|(_, offer)| {
+                            offer.item == *item.id() && offer.count_range.contains(&needed)
+                        })
+                        .min_by_key(|(_, offer)| offer.price_per_unit)?;
+                    let supplier = Supplier {
+                        terminal: seller.into(),
+                        offer_id: offer.id,
+                        count: needed,
+                    };
+                    let cost = offer.price_per_unit.checked_mul(needed as Money)?;
+                    let route = (seller.pos() - pos).len() + (terminal.pos() - seller.pos()).len();
+                    (Some(supplier), cost, route)
+                };
+                if cost.checked_add(contract.penalty())? > balance {
+                    return None;
+                }
+                Some(ContractPlan {
+                    terminal: terminal.into(),
+                    contract_id: contract.id(),
+                    supplier,
+                    profit: contract.reward() as Float - cost as Float - travel_cost(route),
+                })
+            })
+            .filter(|plan| plan.profit > 0.)
+            .max_by(|a, b| a.profit.total_cmp(&b.profit))
+    }
+
+    /// false if the destination terminal is detected and does not list the contract anymore
+    fn is_contract_open(this_vessel: &dyn VesselConsole, delivery: &Delivery) -> bool {
+        find_terminal(this_vessel, &delivery.terminal)
+            .map(|terminal| {
+                terminal
+                    .contracts()
+                    .iter()
+                    .any(|contract| contract.id() == delivery.contract_id)
+            })
+            .unwrap_or(true)
+    }
+
+    fn start_delivery(this_vessel: &dyn VesselConsole, delivery: &Delivery) -> PendingTrade {
+        PendingTrade::start(
+            this_vessel,
+            delivery.terminal,
+            delivery.item.id().clone(),
+            delivery.item.count(),
+            Some(delivery.order_id),
+        )
+    }
+}
+
+impl Objective for DeliverContractObjective {
+    type Error = DeliverContractObjectiveError;
+
+    fn pursue(
+        &mut self,
+        this_module: &mut dyn ModuleConsole,
+        this_vessel: &dyn VesselConsole,
+        process_token_context: &ProcessTokenContext,
+        mut logger: PersonLogger,
+    ) -> Result<ObjectiveStatus, Self::Error> {
+        match self {
+            Self::SearchForContract => {
+                if !Self::has_cargo_space(this_vessel) {
+                    return Err(DeliverContractObjectiveError::NoCargoSpace);
+                }
+                let plan = Self::plan_contract(this_vessel)
+                    .ok_or(DeliverContractObjectiveError::NoProfitableContract)?;
+                logger.log(
+                    Severity::Info,
+                    format!(
+                        "Accepting contract {} for an estimated profit of {:.0}",
+                        plan.contract_id, plan.profit
+                    ),
+                );
+                this_vessel.accept_contract(plan.terminal, plan.contract_id);
+                *self = Self::AcceptContract {
+                    terminal: plan.terminal,
+                    contract_id: plan.contract_id,
+                    supplier: plan.supplier,
+                };
+                Ok(ObjectiveStatus::InProgress)
+            }
+            Self::AcceptContract {
+                terminal,
+                contract_id,
+                supplier,
+            } => {
+                let contract = find_terminal(this_vessel, terminal)
+                    .and_then(|contact| {
+                        contact
+                            .contracts()
+                            .iter()
+                            .find(|contract| contract.id() == *contract_id)
+                    })
+                    .filter(|contract| contract.contractor() == Some(this_vessel.id()))
+                    .ok_or(DeliverContractObjectiveError::ContractRejected {
+                        contract_id: *contract_id,
+                    })?;
+                let delivery = Delivery {
+                    terminal: *terminal,
+                    contract_id: *contract_id,
+                    order_id: contract.order_id().unwrap(),
+                    item: contract.items()[0].clone(),
+                };
+                match supplier {
+                    Some(supplier) => {
+                        logger.log(
+                            Severity::Info,
+                            format!(
+                                "Buying {} {} at {} for contract {}",
+                                supplier.count,
+                                delivery.item.id(),
+                                supplier.terminal.vessel_id,
+                                contract_id
+                            ),
+                        );
+                        let trade = PendingTrade::start(
+                            this_vessel,
+                            supplier.terminal,
+                            delivery.item.id().clone(),
+                            supplier.count,
+                            None,
+                        );
+                        this_vessel.place_buy_order(
+                            supplier.terminal,
+                            supplier.offer_id,
+                            supplier.count,
+                        );
+                        *self = Self::MoveToVesselToBuy { delivery, trade };
+                    }
+                    None => {
+                        let trade = Self::start_delivery(this_vessel, &delivery);
+                        *self = Self::MoveToVesselToDeliver { delivery, trade };
+                    }
+                }
+                Ok(ObjectiveStatus::InProgress)
+            }
+            Self::MoveToVesselToBuy { delivery, trade } => {
+                if !Self::is_contract_open(this_vessel, delivery) {
+                    if let Some(order_id) = trade.order_id() {
+                        this_vessel.cancel_order(trade.terminal(), order_id);
+                    }
+                    this_vessel.stop();
+                    return Err(DeliverContractObjectiveError::ContractExpired {
+                        contract_id: delivery.contract_id,
+                    });
+                }
+                match trade.track(this_vessel, true)? {
+                    OrderProgress::Pending => {}
+                    OrderProgress::Settled => {
+                        let trade = Self::start_delivery(this_vessel, delivery);
+                        *self = Self::MoveToVesselToDeliver {
+                            delivery: delivery.clone(),
+                            trade,
+                        };
+                    }
+                }
+                Ok(ObjectiveStatus::InProgress)
+            }
+            Self::MoveToVesselToDeliver { delivery, trade } => {
+                match trade.track(this_vessel, false) {
+                    Ok(OrderProgress::Pending) => Ok(ObjectiveStatus::InProgress),
+                    Ok(OrderProgress::Settled) => {
+                        logger.log(
+                            Severity::Info,
+                            format!("Contract {} fulfilled", delivery.contract_id),
+                        );
+                        *self = Self::Done;
+                        Ok(ObjectiveStatus::Done)
+                    }
+                    // the order paying the reward is removed with the contract
+                    Err(PendingTradeError::OrderRejected { .. }) => {
+                        this_vessel.stop();
+                        Err(DeliverContractObjectiveError::ContractExpired {
+                            contract_id: delivery.contract_id,
+                        })
+                    }
+                    Err(e) => Err(e.into()),
+                }
+            }
+            Self::Done => Ok(ObjectiveStatus::Done),
+        }
+    }
+}
+
+impl DynSerialize for DeliverContractObjective {
+    fn type_id(&self) -> TypeId {
+        TYPE_ID.to_string()
+    }
+
+    fn serialize(&self) -> Result<Intermediate, Box<dyn Error>> {
+        to_intermediate(self).map_err(|e| e.into())
+    }
+}
+
+pub(crate) struct DeliverContractObjectiveDynSeed;
+
+impl DynDeserializeSeed<dyn DynObjective> for DeliverContractObjectiveDynSeed {
+    fn type_id(&self) -> TypeId {
+        TYPE_ID.to_string()
+    }
+
+    fn deserialize(
+        &self,
+        intermediate: Intermediate,
+        _: &DynDeserializeSeedVault<dyn DynObjective>,
+    ) -> Result<Box<dyn DynObjective>, Box<dyn Error>> {
+        let obj: DeliverContractObjective =
+            from_intermediate(&intermediate).map_err(|e| e.to_string())?;
+        Ok(Box::new(obj))
+    }
+}
+
+pub(crate) struct DeliverContractObjectiveDecider;
+
+impl ObjectiveDecider for DeliverContractObjectiveDecider {
+    fn consider(
+        &self,
+        person_id: PersonId,
+        age: u8,
+        gender: Gender,
+        passions: &[Passion],
+        morale: Morale,
+        boldness: Boldness,
+        awareness: Awareness,
+    ) -> Option<Box<dyn DynObjective>> {
+        if passions.contains(&Passion::Trade) {
+            Some(Box::new(DeliverContractObjective::new()))
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Debug)]
+pub(crate) enum DeliverContractObjectiveError {
+    NoCargoSpace,
+    NoProfitableContract,
+    ContractRejected { contract_id: ContractId },
+    ContractExpired { contract_id: ContractId },
+    Order(PendingTradeError),
+}
+
+impl From<PendingTradeError> for DeliverContractObjectiveError {
+    fn from(e: PendingTradeError) -> Self {
+        Self::Order(e)
+    }
+}
+
+impl Display for DeliverContractObjectiveError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DeliverContractObjectiveError::NoCargoSpace => {
+                write!(f, "Vessel has no item storages")
+            }
+            DeliverContractObjectiveError::NoProfitableContract => {
+                write!(f, "No profitable contract posted by detected terminals")
+            }
+            DeliverContractObjectiveError::ContractRejected { contract_id } => {
+                write!(f, "Contract {} rejected", contract_id)
+            }
+            DeliverContractObjectiveError::ContractExpired { contract_id } => {
+                write!(f, "Contract {} expired before delivery", contract_id)
+            }
+            DeliverContractObjectiveError::Order(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl Error for DeliverContractObjectiveError {}
+
+#[cfg(test)]
+mod tests {
+    use super::DeliverContractObjective;
+    use crate::items::register_items;
+    use crate::modules::{MiningLaser, Radar, Reactor, ShuttleFactory, TradingTerminal};
+    use dudes_in_space_api::environment::Environment;
+    use dudes_in_space_api::finance::Wallet;
+    use dudes_in_space_api::item::{ContractId, Item, ItemRegistry};
+    use dudes_in_space_api::module::{DefaultModuleConsole, Module, ModuleId, ProcessTokenContext};
+    use dudes_in_space_api::person::{
+        Logger, Objective, ObjectiveDeciderVault, ObjectiveStatus, PersonId, PersonLogger, Severity,
+    };
+    use dudes_in_space_api::recipe::{InputRecipe, ModuleFactory};
+    use dudes_in_space_api::research::TechTree;
+    use dudes_in_space_api::utils::simulation_rng::SimulationRng;
+    use dudes_in_space_api::vessel::Vessel;
+    use std::time::Duration;
+
+    struct NoLogger;
+
+    impl Logger for NoLogger {
+        fn log(&mut self, _: &PersonId, _: Severity, _: String) {}
+    }
+
+    fn reactor(item_registry: &ItemRegistry, rng: &mut SimulationRng) -> Box<Reactor> {
+        let mut reactor = Reactor::new(rng);
+        assert!(
+            reactor.storages_mut()[0]
+                .try_add_item(Item::new("fuel".to_string(), 100), item_registry)
+        );
+        reactor
+    }
+
+    #[test]
+    fn buys_missing_items_and_delivers() {
+        let mut rng = SimulationRng::new(0);
+        let item_registry = register_items(ItemRegistry::new());
+        let mut shuttle =
+            ShuttleFactory {}.create(&InputRecipe::try_from(vec![]).unwrap(), &mut rng);
+        assert!(
+            shuttle.storages_mut()[0]
+                .try_add_item(Item::new("fuel".to_string(), 10), &item_registry)
+        );
+        let mut cargo = MiningLaser::new(&mut rng);
+        assert!(
+            cargo.storages_mut()[0].try_add_item(Item::new("steel".to_string(), 5), &item_registry)
+        );
+        let trader = PersonId::new_v4();
+        let vessel = Vessel::new(
+            trader,
+            (0., 0.).into(),
+            vec![
+                shuttle,
+                reactor(&item_registry, &mut rng),
+                Radar::new(&mut rng),
+                cargo,
+            ],
+            &mut rng,
+        );
+
+        let mut supplier_terminal = TradingTerminal::new(&mut rng);
+        assert!(
+            supplier_terminal.storages_mut()[0]
+                .try_add_item(Item::new("steel".to_string(), 50), &item_registry)
+        );
+        supplier_terminal.admin_console_mut().place_buy_offer(
+            "steel".to_string(),
+            (1..100).into(),
+            5,
+        );
+        let supplier = Vessel::new(
+            PersonId::nil(),
+            (200., 0.).into(),
+            vec![supplier_terminal, reactor(&item_registry, &mut rng)],
+            &mut rng,
+        );
+        let customer = Vessel::new(
+            PersonId::nil(),
+            (400., 0.).into(),
+            vec![
+                TradingTerminal::new(&mut rng),
+                reactor(&item_registry, &mut rng),
+            ],
+            &mut rng,
+        );
+
+        let mut environment = Environment::new(rng, vec![vessel, supplier, customer], vec![]);
+        environment
+            .bank_mut()
+            .deposit(trader, 200, Default::default(), "savings".to_string())
+            .unwrap();
+
+        let process_token_context = ProcessTokenContext::new();
+        let decider_vault = ObjectiveDeciderVault::new();
+        let proceed = |environment: &mut Environment| {
+            environment.proceed(
+                &item_registry,
+                &TechTree::new(),
+                &process_token_context,
+                &decider_vault,
+                &mut NoLogger,
+            )
+        };
+
+        // terminals learn their vessels
+        proceed(&mut environment);
+        let deadline = environment.clock().now() + Duration::from_secs(1000);
+        let contract_id = environment.vessels()[2]
+            .modules_mut()
+            .next()
+            .unwrap()
+            .contract_board_console_mut()
+            .unwrap()
+            .post_contract(
+                ContractId::new_v4(),
+                vec![Item::new("steel".to_string(), 20)],
+                300,
+                50,
+                deadline,
+                &mut Wallet::new(300),
+            )
+            .unwrap()
+            .id();
+        proceed(&mut environment);
+
+        let mut objective = DeliverContractObjective::new();
+        let mut status = ObjectiveStatus::InProgress;
+        for _ in 0..300 {
+            status = objective
+                .pursue(
+                    &mut DefaultModuleConsole::new(ModuleId::nil()),
+                    &environment.vessels()[0],
+                    &process_token_context,
+                    PersonLogger::new(&PersonId::nil(), &mut NoLogger),
+                )
+                .unwrap();
+            if status == ObjectiveStatus::Done {
+                break;
+            }
+            proceed(&mut environment);
+        }
+
+        assert_eq!(status, ObjectiveStatus::Done);
+        // 15 steel bought for 75, collateral of 50 paid and returned with the reward of 300
+        assert_eq!(environment.bank().balance(trader), 425);
+        let customer = &environment.vessels()[2];
+        let mut terminal = customer.modules_mut().next().unwrap();
+        assert_eq!(terminal.storages()[0].count("steel".to_string()), 20);
+        let board = terminal.contract_board_console_mut().unwrap();
+        assert!(board.contracts().iter().all(|c| c.id() != contract_id));
+    }
+}
@@ -1,6 +1,12 @@
 mod buy_goods_objective;
 pub use buy_goods_objective::*;
 
+mod deliver_contract_objective;
+pub use deliver_contract_objective::*;
+
+mod pending_trade;
+pub(crate) use pending_trade::*;
+
 mod sell_goods_objective;
 pub use sell_goods_objective::*;
 
@@ -0,0 +1,183 @@
+use dudes_in_space_api::environment::DOCKING_RANGE;
+use dudes_in_space_api::item::{ItemCount, ItemId, OrderId};
+use dudes_in_space_api::module::ModuleCapability;
+use dudes_in_space_api::utils::utils::Float;
+use dudes_in_space_api::vessel::{TerminalContact, TerminalRef, VesselConsole, VesselId};
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+/// estimated fuel and time cost of flying one meter
+static TRAVEL_COST_PER_METER: Float = 0.05;
+
+/// ticks to wait for settlement of an order after arriving at the terminal
+static SETTLEMENT_TIMEOUT: u32 = 10;
+
+pub(crate) fn travel_cost(distance: Float) -> Float {
+    distance * TRAVEL_COST_PER_METER
+}
+
+/// units of the item in item storages of the vessel
+pub(crate) fn cargo_count(this_vessel: &dyn VesselConsole, item: &ItemId) -> ItemCount {
+    this_vessel
+        .modules_with_cap(ModuleCapability::ItemStorage)
+        .iter()
+        .flat_map(|module| {
+            module
+                .storages()
+                .iter()
+                .map(|storage| storage.count(item.clone()))
+                .collect::<Vec<_>>()
+        })
+        .sum()
+}
+
+pub(crate) fn find_terminal<'a>(
+    this_vessel: &'a dyn VesselConsole,
+    terminal: &TerminalRef,
+) -> Option<&'a TerminalContact> {
+    this_vessel
+        .terminal_contacts()
+        .iter()
+        .find(|contact| TerminalRef::from(*contact) == *terminal)
+}
+
+/// outcome of an order after the last tick
+pub(crate) enum OrderProgress {
+    Pending,
+    Settled,
+}
+
+/// order placed at a terminal and the progress of its settlement
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct PendingTrade {
+    terminal: TerminalRef,
+    item: ItemId,
+    count: ItemCount,
+    /// units of the item in cargo when the order was placed
+    cargo_before: ItemCount,
+    /// orders of this vessel the terminal had before the order was placed
+    known_orders: Vec<OrderId>,
+    /// `None` until the terminal acknowledges the order
+    order_id: Option<OrderId>,
+    /// ticks spent in docking range waiting for settlement
+    waited: u32,
+}
+
+impl PendingTrade {
+    /// starts flying to the terminal. `order_id` is known for orders not placed by this vessel,
+    /// otherwise the order is recognized among pending orders of the terminal on the next tick
+    pub(crate) fn start(
+        this_vessel: &dyn VesselConsole,
+        terminal: TerminalRef,
+        item: ItemId,
+        count: ItemCount,
+        order_id: Option<OrderId>,
+    ) -> Self {
+        let known_orders = find_terminal(this_vessel, &terminal)
+            .map(|contact| contact.pending_orders().to_vec())
+            .unwrap_or_default();
+        this_vessel.follow_vessel(terminal.vessel_id);
+        Self {
+            terminal,
+            cargo_before: cargo_count(this_vessel, &item),
+            item,
+            count,
+            known_orders,
+            order_id,
+            waited: 0,
+        }
+    }
+
+    pub(crate) fn terminal(&self) -> TerminalRef {
+        self.terminal
+    }
+
+    pub(crate) fn item(&self) -> &ItemId {
+        &self.item
+    }
+
+    pub(crate) fn order_id(&self) -> Option<OrderId> {
+        self.order_id
+    }
+
+    /// tracks the order until its items arrived in or left the cargo
+    pub(crate) fn track(
+        &mut self,
+        this_vessel: &dyn VesselConsole,
+        buying: bool,
+    ) -> Result<OrderProgress, PendingTradeError> {
+        let cargo = cargo_count(this_vessel, &self.item);
+        let settled = if buying {
+            cargo >= self.cargo_before + self.count
+        } else {
+            cargo + self.count <= self.cargo_before
+        };
+        if settled {
+            this_vessel.stop();
+            return Ok(OrderProgress::Settled);
+        }
+
+        let vessel_id = self.terminal.vessel_id;
+        let Some(terminal) = find_terminal(this_vessel, &self.terminal) else {
+            return Err(PendingTradeError::TerminalLost { vessel_id });
+        };
+        match self.order_id {
+            None => {
+                self.order_id = terminal
+                    .pending_orders()
+                    .iter()
+                    .find(|id| !self.known_orders.contains(id))
+                    .copied();
+                if self.order_id.is_none() {
+                    return Err(PendingTradeError::OrderRejected { vessel_id });
+                }
+            }
+            Some(order_id) => {
+                if !terminal.pending_orders().contains(&order_id) {
+                    // cancelled by the terminal
+                    return Err(PendingTradeError::OrderRejected { vessel_id });
+                }
+            }
+        }
+
+        if (terminal.pos() - this_vessel.pos()).len() <= DOCKING_RANGE {
+            self.waited += 1;
+            if self.waited > SETTLEMENT_TIMEOUT {
+                if let Some(order_id) = self.order_id {
+                    this_vessel.cancel_order(self.terminal, order_id);
+                }
+                this_vessel.stop();
+                return Err(PendingTradeError::SettlementTimeout { vessel_id });
+            }
+        } else if this_vessel.nav_target().is_none() {
+            this_vessel.follow_vessel(vessel_id);
+        }
+        Ok(OrderProgress::Pending)
+    }
+}
+
+#[derive(Debug)]
+pub(crate) enum PendingTradeError {
+    OrderRejected { vessel_id: VesselId },
+    TerminalLost { vessel_id: VesselId },
+    SettlementTimeout { vessel_id: VesselId },
+}
+
+impl Display for PendingTradeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PendingTradeError::OrderRejected { vessel_id } => {
+                write!(f, "Order rejected by terminal at {}", vessel_id)
+            }
+            PendingTradeError::TerminalLost { vessel_id } => {
+                write!(f, "Terminal at {} is out of radar range", vessel_id)
+            }
+            PendingTradeError::SettlementTimeout { vessel_id } => {
+                write!(f, "Order at {} was not settled in time", vessel_id)
+            }
+        }
+    }
+}
+
+impl Error for PendingTradeError {}
@@ -1,24 +1,142 @@
-use dudes_in_space_api::module::{ModuleConsole, ProcessTokenContext};
-use dudes_in_space_api::person::{Awareness, Boldness, DynObjective, Gender, Morale, Objective, ObjectiveDecider, ObjectiveStatus, Passion, PersonId, PersonLogger};
-use dudes_in_space_api::vessel::VesselConsole;
+use crate::objectives::trading::{
+    OrderProgress, PendingTrade, PendingTradeError, cargo_count, travel_cost,
+};
+use dudes_in_space_api::item::{ItemCount, ItemId, Money, OfferId};
+use dudes_in_space_api::module::{ModuleCapability, ModuleConsole, ProcessTokenContext};
+use dudes_in_space_api::person::{
+    Awareness, Boldness, DynObjective, Gender, Morale, Objective, ObjectiveDecider,
+    ObjectiveStatus, Passion, PersonId, PersonLogger, Severity,
+};
+use dudes_in_space_api::utils::utils::Float;
+use dudes_in_space_api::vessel::{TerminalRef, VesselConsole};
+use dyn_serde::{DynDeserializeSeed, DynDeserializeSeedVault, DynSerialize, TypeId};
 use serde::{Deserialize, Serialize};
+use serde_intermediate::{Intermediate, from_intermediate, to_intermediate};
 use std::error::Error;
 use std::fmt::{Display, Formatter};
-use serde_intermediate::Intermediate;
-use dyn_serde::{DynSerialize, TypeId};
 
+static TYPE_ID: &str = "TradeObjective";
+
+/// buy goods at the terminal selling them cheapest, fly them to the terminal paying most
+/// and sell them there. Orders are placed remotely and settled in docking range of the terminals
 #[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "trade_objective_stage")]
 pub(crate) enum TradeObjective {
     SearchForBuyOffers,
-    MoveToVesselToBuy,
-    SearchForSellOffers,
-    MoveToVesselToSell,
+    MoveToVesselToBuy {
+        trade: PendingTrade,
+        /// balance of the owner before buying
+        start_balance: Money,
+    },
+    SearchForSellOffers {
+        item: ItemId,
+        start_balance: Money,
+    },
+    MoveToVesselToSell {
+        trade: PendingTrade,
+        start_balance: Money,
+    },
+    Done,
+}
+
+/// profitable pair of offers found in terminal contacts
+struct TradePlan {
+    buy_at: TerminalRef,
+    buy_offer: OfferId,
+    item: ItemId,
+    count: ItemCount,
+    profit: Float,
 }
 
 impl TradeObjective {
-    pub fn new() -> Self {
+    pub(crate) fn new() -> Self {
         Self::SearchForBuyOffers
     }
+
+    fn has_cargo_space(this_vessel: &dyn VesselConsole) -> bool {
+        !this_vessel
+            .modules_with_cap(ModuleCapability::ItemStorage)
+            .is_empty()
+    }
+
+    /// most profitable buy offer which can be sold at another detected terminal
+    fn plan_trade(this_vessel: &dyn VesselConsole) -> Option<TradePlan> {
+        let pos = this_vessel.pos();
+        let balance = this_vessel.owner_balance();
+        let terminals = this_vessel.terminal_contacts();
+        terminals
+            .iter()
+            .flat_map(|seller| {
+                seller
+                    .buy_offers()
+                    .iter()
+                    .map(move |buy_offer| (seller, buy_offer))
+            })
+            .flat_map(|(seller, buy_offer)| {
+                terminals
+                    .iter()
+                    .filter(move |buyer| TerminalRef::from(*buyer) != TerminalRef::from(seller))
+                    .flat_map(|buyer| buyer.sell_offers().iter().map(move |offer| (buyer, offer)))
+                    .filter(move |(_, sell_offer)| {
+                        sell_offer.item == buy_offer.item
+                            && sell_offer.price_per_unit > buy_offer.price_per_unit
+                    })
+                    .map(move |(buyer, sell_offer)| (seller, buy_offer, buyer, sell_offer))
+            })
+            .filter_map(|(seller, buy_offer, buyer, sell_offer)| {
+                let affordable = (balance / buy_offer.price_per_unit.max(1))
+                    .try_into()
+                    .unwrap_or(ItemCount::MAX);
+                let count = this_vessel
+                    .cargo_space(&buy_offer.item)
+                    .min(affordable)
+                    .min(buy_offer.count_range.end.saturating_sub(1))
+                    .min(sell_offer.count_range.end.saturating_sub(1));
+                if count == 0
+                    || !buy_offer.count_range.contains(&count)
+                    || !sell_offer.count_range.contains(&count)
+                {
+                    return None;
+                }
+                let margin =
+                    (sell_offer.price_per_unit - buy_offer.price_per_unit) * count as Money;
+                let distance = (seller.pos() - pos).len() + (buyer.pos() - seller.pos()).len();
+                Some(TradePlan {
+                    buy_at: seller.into(),
+                    buy_offer: buy_offer.id,
+                    item: buy_offer.item.clone(),
+                    count,
+                    profit: margin as Float - travel_cost(distance),
+                })
+            })
+            .filter(|plan| plan.profit > 0.)
+            .max_by(|a, b| a.profit.total_cmp(&b.profit))
+    }
+
+    /// detected terminal paying most for the cargo after travel costs
+    fn best_buyer(
+        this_vessel: &dyn VesselConsole,
+        item: &ItemId,
+        held: ItemCount,
+    ) -> Option<(TerminalRef, OfferId, ItemCount)> {
+        let pos = this_vessel.pos();
+        this_vessel
+            .terminal_contacts()
+            .iter()
+            .flat_map(|buyer| buyer.sell_offers().iter().map(move |offer| (buyer, offer)))
+            .filter(|(_, offer)| offer.item == *item)
+            .filter_map(|(buyer, offer)| {
+                let count = held.min(offer.count_range.end.saturating_sub(1));
+                if !offer.count_range.contains(&count) {
+                    return None;
+                }
+                let revenue = (offer.price_per_unit * count as Money) as Float
+                    - travel_cost((buyer.pos() - pos).len());
+                Some((buyer.into(), offer.id, count, revenue))
+            })
+            .max_by(|a, b| a.3.total_cmp(&b.3))
+            .map(|(terminal, offer_id, count, _)| (terminal, offer_id, count))
+    }
 }
 
 impl Objective for TradeObjective {
@@ -29,24 +147,115 @@ impl Objective for TradeObjective {
         this_module: &mut dyn ModuleConsole,
         this_vessel: &dyn VesselConsole,
         process_token_context: &ProcessTokenContext,
-        logger: PersonLogger,
+        mut logger: PersonLogger,
     ) -> Result<ObjectiveStatus, Self::Error> {
         match self {
-            TradeObjective::SearchForBuyOffers => todo!(),
-            TradeObjective::MoveToVesselToBuy => todo!(),
-            TradeObjective::SearchForSellOffers => todo!(),
-            TradeObjective::MoveToVesselToSell => todo!(),
+            Self::SearchForBuyOffers => {
+                if !Self::has_cargo_space(this_vessel) {
+                    return Err(TradeObjectiveError::NoCargoSpace);
+                }
+                let plan =
+                    Self::plan_trade(this_vessel).ok_or(TradeObjectiveError::NoProfitableTrade)?;
+                logger.log(
+                    Severity::Info,
+                    format!(
+                        "Buying {} {} at {} for an estimated profit of {:.0}",
+                        plan.count, plan.item, plan.buy_at.vessel_id, plan.profit
+                    ),
+                );
+                let start_balance = this_vessel.owner_balance();
+                let trade =
+                    PendingTrade::start(this_vessel, plan.buy_at, plan.item, plan.count, None);
+                this_vessel.place_buy_order(plan.buy_at, plan.buy_offer, plan.count);
+                *self = Self::MoveToVesselToBuy {
+                    trade,
+                    start_balance,
+                };
+                Ok(ObjectiveStatus::InProgress)
+            }
+            Self::MoveToVesselToBuy {
+                trade,
+                start_balance,
+            } => match trade.track(this_vessel, true)? {
+                OrderProgress::Pending => Ok(ObjectiveStatus::InProgress),
+                OrderProgress::Settled => {
+                    *self = Self::SearchForSellOffers {
+                        item: trade.item().clone(),
+                        start_balance: *start_balance,
+                    };
+                    Ok(ObjectiveStatus::InProgress)
+                }
+            },
+            Self::SearchForSellOffers {
+                item,
+                start_balance,
+            } => {
+                let held = cargo_count(this_vessel, item);
+                let (terminal, offer_id, count) = Self::best_buyer(this_vessel, item, held)
+                    .ok_or_else(|| TradeObjectiveError::NoBuyerFound { item: item.clone() })?;
+                logger.log(
+                    Severity::Info,
+                    format!("Selling {} {} at {}", count, item, terminal.vessel_id),
+                );
+                let trade = PendingTrade::start(this_vessel, terminal, item.clone(), count, None);
+                this_vessel.place_sell_order(terminal, offer_id, count);
+                *self = Self::MoveToVesselToSell {
+                    trade,
+                    start_balance: *start_balance,
+                };
+                Ok(ObjectiveStatus::InProgress)
+            }
+            Self::MoveToVesselToSell {
+                trade,
+                start_balance,
+            } => match trade.track(this_vessel, false)? {
+                OrderProgress::Pending => Ok(ObjectiveStatus::InProgress),
+                OrderProgress::Settled => {
+                    let balance = this_vessel.owner_balance();
+                    if balance >= *start_balance {
+                        logger.log(
+                            Severity::Info,
+                            format!("Trade done with profit of {}", balance - *start_balance),
+                        );
+                    } else {
+                        logger.log(
+                            Severity::Warning,
+                            format!("Trade done with loss of {}", *start_balance - balance),
+                        );
+                    }
+                    *self = Self::Done;
+                    Ok(ObjectiveStatus::Done)
+                }
+            },
+            Self::Done => Ok(ObjectiveStatus::Done),
         }
     }
 }
 
 impl DynSerialize for TradeObjective {
     fn type_id(&self) -> TypeId {
-        todo!()
+        TYPE_ID.to_string()
     }
 
     fn serialize(&self) -> Result<Intermediate, Box<dyn Error>> {
-        todo!()
+        to_intermediate(self).map_err(|e| e.into())
+    }
+}
+
+pub(crate) struct TradeObjectiveDynSeed;
+
+impl DynDeserializeSeed<dyn DynObjective> for TradeObjectiveDynSeed {
+    fn type_id(&self) -> TypeId {
+        TYPE_ID.to_string()
+    }
+
+    fn deserialize(
+        &self,
+        intermediate: Intermediate,
+        _: &DynDeserializeSeedVault<dyn DynObjective>,
+    ) -> Result<Box<dyn DynObjective>, Box<dyn Error>> {
+        let obj: TradeObjective = from_intermediate(&intermediate).map_err(|e| e.to_string())?;
+        Ok(Box::new(obj))
     }
 }
 
@@ -72,12 +281,172 @@ impl ObjectiveDecider for TradeObjectiveDecider {
 }
 
 #[derive(Debug)]
-pub(crate) enum TradeObjectiveError {}
+pub(crate) enum TradeObjectiveError {
+    NoCargoSpace,
+    NoProfitableTrade,
+    NoBuyerFound { item: ItemId },
+    Order(PendingTradeError),
+}
+
+impl From<PendingTradeError> for TradeObjectiveError {
+    fn from(e: PendingTradeError) -> Self {
+        Self::Order(e)
+    }
+}
 
 impl Display for TradeObjectiveError {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        todo!()
+        match self {
+            TradeObjectiveError::NoCargoSpace => write!(f, "Vessel has no item storages"),
+            TradeObjectiveError::NoProfitableTrade => {
+                write!(f, "No profitable trade between detected terminals")
+            }
+            TradeObjectiveError::NoBuyerFound { item } => {
+                write!(f, "No detected terminal buys {}", item)
+            }
+            TradeObjectiveError::Order(e) => write!(f, "{}", e),
+        }
     }
 }
 
 impl Error for TradeObjectiveError {}
+
+#[cfg(test)]
+mod tests {
+    use super::TradeObjective;
+    use crate::items::register_items;
+    use crate::modules::{MiningLaser, Radar, Reactor, ShuttleFactory, TradingTerminal};
+    use dudes_in_space_api::environment::Environment;
+    use dudes_in_space_api::item::{Item, ItemRegistry, Money};
+    use dudes_in_space_api::module::{DefaultModuleConsole, Module, ModuleId, ProcessTokenContext};
+    use dudes_in_space_api::person::{
+        Logger, Objective, ObjectiveDeciderVault, ObjectiveStatus, PersonId, PersonLogger, Severity,
+    };
+    use dudes_in_space_api::recipe::{InputRecipe, ModuleFactory};
+    use dudes_in_space_api::research::TechTree;
+    use dudes_in_space_api::utils::simulation_rng::SimulationRng;
+    use dudes_in_space_api::vessel::Vessel;
+
+    struct NoLogger;
+
+    impl Logger for NoLogger {
+        fn log(&mut self, _: &PersonId, _: Severity, _: String) {}
+    }
+
+    fn reactor(item_registry: &ItemRegistry, rng: &mut SimulationRng) -> Box<Reactor> {
+        let mut reactor = Reactor::new(rng);
+        assert!(
+            reactor.storages_mut()[0]
+                .try_add_item(Item::new("fuel".to_string(), 100), item_registry)
+        );
+        reactor
+    }
+
+    fn station(
+        x: f64,
+        ore_stock: u32,
+        ore_price: Money,
+        item_registry: &ItemRegistry,
+        rng: &mut SimulationRng,
+    ) -> Vessel {
+        let mut terminal = TradingTerminal::new(rng);
+        assert!(
+            terminal.storages_mut()[0]
+                .try_add_item(Item::new("ore".to_string(), ore_stock), item_registry)
+        );
+        let admin = terminal.admin_console_mut();
+        if ore_stock > 0 {
+            admin.place_buy_offer("ore".to_string(), (1..100).into(), ore_price);
+        } else {
+            admin.place_sell_offer("ore".to_string(), (1..100).into(), ore_price);
+        }
+        Vessel::new(
+            PersonId::nil(),
+            (x, 0.).into(),
+            vec![terminal, reactor(item_registry, rng)],
+            rng,
+        )
+    }
+
+    #[test]
+    fn buys_low_and_sells_high() {
+        let mut rng = SimulationRng::new(0);
+        let item_registry = register_items(ItemRegistry::new());
+        let mut shuttle =
+            ShuttleFactory {}.create(&InputRecipe::try_from(vec![]).unwrap(), &mut rng);
+        assert!(
+            shuttle.storages_mut()[0]
+                .try_add_item(Item::new("fuel".to_string(), 10), &item_registry)
+        );
+        let trader = PersonId::new_v4();
+        let vessel = Vessel::new(
+            trader,
+            (0., 0.).into(),
+            vec![
+                shuttle,
+                reactor(&item_registry, &mut rng),
+                Radar::new(&mut rng),
+                MiningLaser::new(&mut rng),
+            ],
+            &mut rng,
+        );
+        let cheap = station(200., 80, 2, &item_registry, &mut rng);
+        let dear = station(400., 0, 10, &item_registry, &mut rng);
+
+        let mut environment = Environment::new(rng, vec![vessel, cheap, dear], vec![]);
+        environment
+            .bank_mut()
+            .deposit(trader, 100, Default::default(), "savings".to_string())
+            .unwrap();
+        // the till of both stations
+        environment
+            .bank_mut()
+            .deposit(
+                PersonId::nil(),
+                1000,
+                Default::default(),
+                "capital".to_string(),
+            )
+            .unwrap();
+
+        let process_token_context = ProcessTokenContext::new();
+        let decider_vault = ObjectiveDeciderVault::new();
+        let proceed = |environment: &mut Environment| {
+            environment.proceed(
+                &item_registry,
+                &TechTree::new(),
+                &process_token_context,
+                &decider_vault,
+                &mut NoLogger,
+            )
+        };
+
+        // terminals learn their vessels, the trader scans them
+        proceed(&mut environment);
+        proceed(&mut environment);
+
+        let mut objective = TradeObjective::new();
+        let mut status = ObjectiveStatus::InProgress;
+        for _ in 0..300 {
+            status = objective
+                .pursue(
+                    &mut DefaultModuleConsole::new(ModuleId::nil()),
+                    &environment.vessels()[0],
+                    &process_token_context,
+                    PersonLogger::new(&PersonId::nil(), &mut NoLogger),
+                )
+                .unwrap();
+            if status == ObjectiveStatus::Done {
+                break;
+            }
+            proceed(&mut environment);
+        }
+
+        assert_eq!(status, ObjectiveStatus::Done);
+        // 50 units affordable at 2, sold at 10
+        assert_eq!(environment.bank().balance(trader), 500);
+        let dear = &environment.vessels()[2];
+        let terminal = dear.modules().next().unwrap();
+        assert_eq!(terminal.storages()[0].count("ore".to_string()), 50);
+    }
+}
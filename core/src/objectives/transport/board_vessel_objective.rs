@@ -0,0 +1,664 @@
+use dudes_in_space_api::environment::DOCKING_RANGE;
+use dudes_in_space_api::module::{ModuleConsole, ProcessTokenContext};
+use dudes_in_space_api::person::{
+    DynObjective, Objective, ObjectiveStatus, PersonId, PersonLogger, Severity,
+};
+use dudes_in_space_api::vessel::{VesselConsole, VesselId, VesselRequestError};
+use dyn_serde::{DynDeserializeSeed, DynDeserializeSeedVault, DynSerialize, TypeId};
+use serde::{Deserialize, Serialize};
+use serde_intermediate::{Intermediate, from_intermediate, to_intermediate};
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+static TYPE_ID: &str = "BoardVesselObjective";
+
+/// ticks to wait for a free docking clamp of the vessel
+static DOCKING_TIMEOUT: u32 = 3;
+
+/// fly the vessel the person is in to another vessel, dock to it and go aboard.
+/// Persons on board of a vessel docked to the destination go aboard right away
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "board_vessel_objective_stage")]
+pub(crate) enum BoardVesselObjective {
+    LeavingCarrier {
+        this_person: PersonId,
+        vessel_id: VesselId,
+    },
+    FlyingToVessel {
+        this_person: PersonId,
+        vessel_id: VesselId,
+    },
+    Docking {
+        this_person: PersonId,
+        vessel_id: VesselId,
+        waited: u32,
+    },
+    Boarding {
+        vessel_id: VesselId,
+    },
+    Done,
+}
+
+impl BoardVesselObjective {
+    pub(crate) fn new(this_person: PersonId, vessel_id: VesselId) -> Self {
+        Self::LeavingCarrier {
+            this_person,
+            vessel_id,
+        }
+    }
+}
+
+impl Objective for BoardVesselObjective {
+    type Error = BoardVesselObjectiveError;
+
+    fn pursue(
+        &mut self,
+        this_module: &mut dyn ModuleConsole,
+        this_vessel: &dyn VesselConsole,
+        process_token_context: &ProcessTokenContext,
+        mut logger: PersonLogger,
+    ) -> Result<ObjectiveStatus, Self::Error> {
+        match self {
+            Self::LeavingCarrier {
+                this_person,
+                vessel_id,
+            } => {
+                if this_vessel.id() == *vessel_id {
+                    *self = Self::Done;
+                    return Ok(ObjectiveStatus::Done);
+                }
+                match this_vessel.carrier() {
+                    Some(carrier) if carrier == *vessel_id => {
+                        this_vessel.move_to_vessel(*this_person, *vessel_id);
+                        *self = Self::Boarding {
+                            vessel_id: *vessel_id,
+                        };
+                    }
+                    Some(_) => this_vessel.undock_from_carrier(),
+                    None => {
+                        logger.log(Severity::Info, format!("Flying to vessel {}", vessel_id));
+                        this_vessel.follow_vessel(*vessel_id);
+                        *self = Self::FlyingToVessel {
+                            this_person: *this_person,
+                            vessel_id: *vessel_id,
+                        };
+                    }
+                }
+                Ok(ObjectiveStatus::InProgress)
+            }
+            Self::FlyingToVessel {
+                this_person,
+                vessel_id,
+            } => {
+                let Some(contact) = this_vessel
+                    .contacts()
+                    .iter()
+                    .find(|contact| contact.vessel_id() == *vessel_id)
+                else {
+                    return Err(BoardVesselObjectiveError::VesselLost {
+                        vessel_id: *vessel_id,
+                    });
+                };
+                if (contact.pos() - this_vessel.pos()).len() <= DOCKING_RANGE {
+                    this_vessel.stop();
+                    this_vessel.dock_to(*vessel_id);
+                    *self = Self::Docking {
+                        this_person: *this_person,
+                        vessel_id: *vessel_id,
+                        waited: 0,
+                    };
+                } else if this_vessel.nav_target().is_none() {
+                    this_vessel.follow_vessel(*vessel_id);
+                }
+                Ok(ObjectiveStatus::InProgress)
+            }
+            Self::Docking {
+                this_person,
+                vessel_id,
+                waited,
+            } => {
+                if this_vessel.carrier() == Some(*vessel_id) {
+                    this_vessel.move_to_vessel(*this_person, *vessel_id);
+                    *self = Self::Boarding {
+                        vessel_id: *vessel_id,
+                    };
+                    return Ok(ObjectiveStatus::InProgress);
+                }
+                *waited += 1;
+                if *waited > DOCKING_TIMEOUT {
+                    return Err(BoardVesselObjectiveError::NoFreeDockingClamp {
+                        vessel_id: *vessel_id,
+                    });
+                }
+                Ok(ObjectiveStatus::InProgress)
+            }
+            Self::Boarding { vessel_id } => {
+                if this_vessel.id() != *vessel_id {
+                    return Err(BoardVesselObjectiveError::NoRoomAboard {
+                        vessel_id: *vessel_id,
+                    });
+                }
+                logger.log(Severity::Info, format!("Boarded vessel {}", vessel_id));
+                *self = Self::Done;
+                Ok(ObjectiveStatus::Done)
+            }
+            Self::Done => Ok(ObjectiveStatus::Done),
+        }
+    }
+
+    fn on_request_error(&mut self, error: &VesselRequestError) -> Result<(), Self::Error> {
+        Err(BoardVesselObjectiveError::Request(error.clone()))
+    }
+}
+
+impl DynSerialize for BoardVesselObjective {
+    fn type_id(&self) -> TypeId {
+        TYPE_ID.to_string()
+    }
+
+    fn serialize(&self) -> Result<Intermediate, Box<dyn Error>> {
+        to_intermediate(self).map_err(|e| e.into())
+    }
+}
+
+pub(crate) struct BoardVesselObjectiveDynSeed;
+
+impl DynDeserializeSeed<dyn DynObjective> for BoardVesselObjectiveDynSeed {
+    fn type_id(&self) -> TypeId {
+        TYPE_ID.to_string()
+    }
+
+    fn deserialize(
+        &self,
+        intermediate: Intermediate,
+        _: &DynDeserializeSeedVault<dyn DynObjective>,
+    ) -> Result<Box<dyn DynObjective>, Box<dyn Error>> {
+        let obj: BoardVesselObjective =
+            from_intermediate(&intermediate).map_err(|e| e.to_string())?;
+        Ok(Box::new(obj))
+    }
+}
+
+#[derive(Debug)]
+pub(crate) enum BoardVesselObjectiveError {
+    VesselLost { vessel_id: VesselId },
+    NoFreeDockingClamp { vessel_id: VesselId },
+    NoRoomAboard { vessel_id: VesselId },
+    Request(VesselRequestError),
+}
+
+impl Display for BoardVesselObjectiveError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BoardVesselObjectiveError::VesselLost { vessel_id } => {
+                write!(f, "Vessel {} is out of radar range", vessel_id)
+            }
+            BoardVesselObjectiveError::NoFreeDockingClamp { vessel_id } => {
+                write!(f, "Vessel {} has no free docking clamp", vessel_id)
+            }
+            BoardVesselObjectiveError::NoRoomAboard { vessel_id } => {
+                write!(f, "No room aboard vessel {}", vessel_id)
+            }
+            BoardVesselObjectiveError::Request(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl Error for BoardVesselObjectiveError {}
+
+#[cfg(test)]
+mod tests {
+    use super::BoardVesselObjective;
+    use crate::items::register_items;
+    use crate::modules::{
+        Dockyard, PersonnelArea, Radar, Reactor, ShuttleFactory, register_module_factories,
+        register_modules,
+    };
+    use crate::objectives::register_objectives;
+    use dudes_in_space_api::environment::{Environment, EnvironmentSeed};
+    use dudes_in_space_api::item::{Item, ItemRegistry};
+    use dudes_in_space_api::module::{Module, ModuleConsole, ModuleId, ProcessTokenContext};
+    use dudes_in_space_api::person::{
+        Awareness, Boldness, DynObjective, Gender, Logger, Morale, Objective, ObjectiveDecider,
+        ObjectiveDeciderVault, ObjectiveStatus, Passion, Person, PersonId, PersonLogger, Severity,
+    };
+    use dudes_in_space_api::recipe::{InputRecipe, ModuleFactory};
+    use dudes_in_space_api::research::TechTree;
+    use dudes_in_space_api::utils::simulation_rng::SimulationRng;
+    use dudes_in_space_api::vessel::{
+        PersonDestination, Vessel, VesselConsole, VesselId, VesselRequestError,
+    };
+    use dyn_serde::{DynSerialize, TypeId};
+    use serde::Serialize;
+    use serde::de::DeserializeSeed;
+    use serde_intermediate::{Intermediate, to_intermediate};
+    use std::collections::BTreeMap;
+    use std::error::Error;
+    use std::rc::Rc;
+
+    struct NoLogger;
+
+    impl Logger for NoLogger {
+        fn log(&mut self, _: &PersonId, _: Severity, _: String) {}
+    }
+
+    /// keeps warnings and errors
+    #[derive(Default)]
+    struct ProblemLogger {
+        problems: Vec<(PersonId, String)>,
+    }
+
+    impl Logger for ProblemLogger {
+        fn log(&mut self, person: &PersonId, severity: Severity, message: String) {
+            if !matches!(severity, Severity::Info) {
+                self.problems.push((*person, message));
+            }
+        }
+    }
+
+    struct BoardVesselDecider(VesselId);
+
+    impl ObjectiveDecider for BoardVesselDecider {
+        fn consider(
+            &self,
+            person_id: PersonId,
+            _: u8,
+            _: Gender,
+            _: &[Passion],
+            _: Morale,
+            _: Boldness,
+            _: Awareness,
+        ) -> Option<Box<dyn DynObjective>> {
+            Some(Box::new(BoardVesselObjective::new(person_id, self.0)))
+        }
+    }
+
+    /// ferry with two persons aboard and a station with the given modules 200 meters away
+    fn ferry_and_station(
+        station_modules: Vec<Box<dyn Module>>,
+        item_registry: &ItemRegistry,
+    ) -> (Environment, [PersonId; 2], VesselId) {
+        let mut rng = SimulationRng::new(0);
+        let pilot = Person::random(&mut rng);
+        let passenger = Person::random(&mut rng);
+        let crew = [pilot.id(), passenger.id()];
+
+        let mut shuttle =
+            ShuttleFactory {}.create(&InputRecipe::try_from(vec![]).unwrap(), &mut rng);
+        assert!(
+            shuttle.storages_mut()[0]
+                .try_add_item(Item::new("fuel".to_string(), 20), item_registry)
+        );
+        assert!(shuttle.insert_person(pilot).is_ok());
+        assert!(shuttle.insert_person(passenger).is_ok());
+        let ferry = Vessel::new(
+            PersonId::nil(),
+            (0., 0.).into(),
+            vec![shuttle, Radar::new(&mut rng)],
+            &mut rng,
+        );
+        let station = Vessel::new(
+            PersonId::nil(),
+            (200., 0.).into(),
+            station_modules,
+            &mut rng,
+        );
+        let station_id = station.id();
+
+        (
+            Environment::new(rng, vec![ferry, station], vec![]),
+            crew,
+            station_id,
+        )
+    }
+
+    #[test]
+    fn ferries_crew_to_vessel() {
+        let mut rng = SimulationRng::new(0);
+        let item_registry = register_items(ItemRegistry::new());
+        let (mut environment, crew, station_id) = ferry_and_station(
+            vec![
+                Box::new(Dockyard::new(&mut rng)),
+                PersonnelArea::new(vec![], &mut rng),
+            ],
+            &item_registry,
+        );
+        let process_token_context = ProcessTokenContext::new();
+        let decider_vault = ObjectiveDeciderVault::new().with(BoardVesselDecider(station_id));
+        for _ in 0..100 {
+            environment.proceed(
+                &item_registry,
+                &TechTree::new(),
+                &process_token_context,
+                &decider_vault,
+                &mut NoLogger,
+            );
+        }
+
+        // the ferry is docked to the station and its crew went aboard
+        assert_eq!(environment.vessels().len(), 1);
+        let station = &environment.vessels()[0];
+        for person in crew {
+            assert!(
+                station
+                    .modules()
+                    .any(|module| module.contains_person(person))
+            );
+        }
+        let dockyard = station.modules().next().unwrap();
+        let ferry = dockyard.docking_clamps()[0].vessel_docked().unwrap();
+        assert_eq!(ferry.carrier(), Some(station_id));
+        assert!(
+            ferry
+                .modules()
+                .all(|module| crew.iter().all(|person| !module.contains_person(*person)))
+        );
+    }
+
+    #[test]
+    fn reports_full_vessel_to_objective() {
+        let mut rng = SimulationRng::new(0);
+        let item_registry = register_items(ItemRegistry::new());
+        // the dockyard operator seat is the only room aboard
+        let (mut environment, crew, station_id) =
+            ferry_and_station(vec![Box::new(Dockyard::new(&mut rng))], &item_registry);
+        let process_token_context = ProcessTokenContext::new();
+        let decider_vault = ObjectiveDeciderVault::new().with(BoardVesselDecider(station_id));
+        let mut logger = ProblemLogger::default();
+        for _ in 0..100 {
+            environment.proceed(
+                &item_registry,
+                &TechTree::new(),
+                &process_token_context,
+                &decider_vault,
+                &mut logger,
+            );
+        }
+
+        let station = &environment.vessels()[0];
+        let dockyard = station.modules().next().unwrap();
+        let ferry = dockyard.docking_clamps()[0].vessel_docked().unwrap();
+        let aboard: Vec<_> = crew
+            .into_iter()
+            .filter(|person| dockyard.contains_person(*person))
+            .collect();
+        assert_eq!(aboard.len(), 1);
+        let left_behind = crew
+            .into_iter()
+            .find(|person| !aboard.contains(person))
+            .unwrap();
+        // returned to the ferry instead of getting lost
+        assert!(
+            ferry
+                .modules()
+                .any(|module| module.contains_person(left_behind))
+        );
+
+        let full = VesselRequestError::DestinationFull {
+            destination: PersonDestination::Vessel(station_id),
+        };
+        assert!(
+            logger
+                .problems
+                .iter()
+                .all(|(person, _)| *person == left_behind)
+        );
+        assert!(logger.problems.contains(&(left_behind, full.to_string())));
+        // the objective failed on the next tick
+        assert!(
+            logger
+                .problems
+                .iter()
+                .any(|(_, message)| message.ends_with(&format!("failed: {}", full)))
+        );
+    }
+
+    #[test]
+    fn keeps_request_errors_in_save() {
+        let mut rng = SimulationRng::new(0);
+        let item_registry = register_items(ItemRegistry::new());
+        let (mut environment, _, station_id) =
+            ferry_and_station(vec![Box::new(Dockyard::new(&mut rng))], &item_registry);
+        let process_token_context = Rc::new(ProcessTokenContext::new());
+        let decider_vault = ObjectiveDeciderVault::new().with(BoardVesselDecider(station_id));
+        let full = VesselRequestError::DestinationFull {
+            destination: PersonDestination::Vessel(station_id),
+        };
+        let mut logger = ProblemLogger::default();
+        // until the second person finds no room aboard
+        while !logger
+            .problems
+            .iter()
+            .any(|(_, message)| *message == full.to_string())
+        {
+            environment.proceed(
+                &item_registry,
+                &TechTree::new(),
+                &process_token_context,
+                &decider_vault,
+                &mut logger,
+            );
+        }
+
+        let objective_vault = register_objectives(Default::default()).into_rc();
+        let module_vault = register_modules(
+            Default::default(),
+            register_module_factories(Default::default()).into_rc(),
+            objective_vault.clone(),
+            process_token_context.clone(),
+        );
+        let json = serde_json::to_string(&environment).unwrap();
+        let mut reloaded = EnvironmentSeed::new(&module_vault, &objective_vault)
+            .deserialize(&mut serde_json::Deserializer::from_str(&json))
+            .unwrap();
+        assert_eq!(serde_json::to_string(&reloaded).unwrap(), json);
+
+        // the objective learns about the error after loading
+        let mut logger = ProblemLogger::default();
+        reloaded.proceed(
+            &item_registry,
+            &TechTree::new(),
+            &process_token_context,
+            &decider_vault,
+            &mut logger,
+        );
+        assert!(
+            logger
+                .problems
+                .iter()
+                .any(|(_, message)| message.ends_with(&format!("failed: {}", full)))
+        );
+    }
+
+    /// requests to move once, then stays where it is
+    #[derive(Debug, Serialize)]
+    struct MoveOnce {
+        this_person: PersonId,
+        destination: Option<PersonDestination>,
+    }
+
+    impl Objective for MoveOnce {
+        type Error = std::fmt::Error;
+
+        fn pursue(
+            &mut self,
+            _: &mut dyn ModuleConsole,
+            this_vessel: &dyn VesselConsole,
+            _: &ProcessTokenContext,
+            _: PersonLogger,
+        ) -> Result<ObjectiveStatus, Self::Error> {
+            match self.destination.take() {
+                Some(PersonDestination::Module(id)) => {
+                    this_vessel.move_to_module(self.this_person, id)
+                }
+                Some(PersonDestination::Vessel(id)) => {
+                    this_vessel.move_to_vessel(self.this_person, id)
+                }
+                None => {}
+            }
+            Ok(ObjectiveStatus::InProgress)
+        }
+    }
+
+    impl DynSerialize for MoveOnce {
+        fn type_id(&self) -> TypeId {
+            "MoveOnce".to_string()
+        }
+
+        fn serialize(&self) -> Result<Intermediate, Box<dyn Error>> {
+            to_intermediate(self).map_err(|e| e.into())
+        }
+    }
+
+    struct MoveOnceDecider(BTreeMap<PersonId, PersonDestination>);
+
+    impl ObjectiveDecider for MoveOnceDecider {
+        fn consider(
+            &self,
+            person_id: PersonId,
+            _: u8,
+            _: Gender,
+            _: &[Passion],
+            _: Morale,
+            _: Boldness,
+            _: Awareness,
+        ) -> Option<Box<dyn DynObjective>> {
+            Some(Box::new(MoveOnce {
+                this_person: person_id,
+                destination: self.0.get(&person_id).copied(),
+            }))
+        }
+    }
+
+    fn dockyard(operator: Person, rng: &mut SimulationRng) -> Box<Dockyard> {
+        let mut dockyard = Box::new(Dockyard::new(rng));
+        assert!(dockyard.insert_person(operator).is_ok());
+        dockyard
+    }
+
+    fn reactor(item_registry: &ItemRegistry, rng: &mut SimulationRng) -> Box<Reactor> {
+        let mut reactor = Reactor::new(rng);
+        assert!(
+            reactor.storages_mut()[0]
+                .try_add_item(Item::new("fuel".to_string(), 100), item_registry)
+        );
+        reactor
+    }
+
+    /// carrier with two manned dockyards. A personnel area vessel is docked to the first one and
+    /// a vessel with a manned dockyard to the second one. In the same tick the carrier operator
+    /// `runner` moves to the docked dockyard, a person from the personnel area takes the seat of
+    /// `runner` and the operator of the docked dockyard `stray` tries to take the seat of the
+    /// first carrier operator. Returns the environment after that tick, the first carrier
+    /// operator, `stray` and its dockyard
+    fn fill_seat_of_stray(
+        carrier_modules: Vec<Box<dyn Module>>,
+        item_registry: &ItemRegistry,
+    ) -> (Environment, PersonId, PersonId, ModuleId) {
+        let mut rng = SimulationRng::new(0);
+        let operator = Person::random(&mut rng);
+        let runner = Person::random(&mut rng);
+        let filler = Person::random(&mut rng);
+        let stray = Person::random(&mut rng);
+        let operator_id = operator.id();
+        let (runner_id, filler_id, stray_id) = (runner.id(), filler.id(), stray.id());
+
+        let area_vessel = Vessel::new(
+            PersonId::nil(),
+            (0., 0.).into(),
+            vec![PersonnelArea::new(vec![filler], &mut rng)],
+            &mut rng,
+        );
+        let stray_dockyard = dockyard(stray, &mut rng);
+        let stray_dockyard_id = stray_dockyard.id();
+        let docked = Vessel::new(
+            PersonId::nil(),
+            (0., 0.).into(),
+            vec![stray_dockyard, reactor(item_registry, &mut rng)],
+            &mut rng,
+        );
+        let first_dockyard = dockyard(operator, &mut rng);
+        let first_dockyard_id = first_dockyard.id();
+        let second_dockyard = dockyard(runner, &mut rng);
+        let second_dockyard_id = second_dockyard.id();
+        let mut modules: Vec<Box<dyn Module>> = vec![
+            first_dockyard,
+            second_dockyard,
+            reactor(item_registry, &mut rng),
+        ];
+        modules.extend(carrier_modules);
+        let carrier = Vessel::new(PersonId::nil(), (0., 0.).into(), modules, &mut rng);
+        carrier.dock(first_dockyard_id, area_vessel.id());
+        carrier.dock(second_dockyard_id, docked.id());
+
+        let decider_vault = ObjectiveDeciderVault::new().with(MoveOnceDecider(BTreeMap::from([
+            (runner_id, PersonDestination::Vessel(docked.id())),
+            (filler_id, PersonDestination::Module(second_dockyard_id)),
+            (stray_id, PersonDestination::Module(first_dockyard_id)),
+        ])));
+        let mut environment = Environment::new(rng, vec![carrier, area_vessel, docked], vec![]);
+        let process_token_context = ProcessTokenContext::new();
+        // docks the vessels and gives everyone an objective, then everyone moves
+        for _ in 0..2 {
+            environment.proceed(
+                &item_registry,
+                &TechTree::new(),
+                &process_token_context,
+                &decider_vault,
+                &mut NoLogger,
+            );
+        }
+        (environment, operator_id, stray_id, stray_dockyard_id)
+    }
+
+    #[test]
+    fn returns_person_to_free_seat_on_carrier() {
+        let mut rng = SimulationRng::new(0);
+        let item_registry = register_items(ItemRegistry::new());
+        let (environment, _, stray, stray_dockyard_id) =
+            fill_seat_of_stray(vec![PersonnelArea::new(vec![], &mut rng)], &item_registry);
+
+        assert!(environment.stranded_persons().is_empty());
+        let carrier = &environment.vessels()[0];
+        let stray_module = carrier
+            .modules()
+            .find(|module| module.contains_person(stray))
+            .unwrap()
+            .id();
+        // the seat of the stray was taken and the personnel area is the only free room
+        assert_ne!(stray_module, stray_dockyard_id);
+        assert_eq!(stray_module, carrier.modules().nth(3).unwrap().id());
+    }
+
+    #[test]
+    fn keeps_stranded_person_until_seat_is_free() {
+        let item_registry = register_items(ItemRegistry::new());
+        let (mut environment, operator, stray, _) = fill_seat_of_stray(vec![], &item_registry);
+
+        let stranded: Vec<_> = environment
+            .stranded_persons()
+            .iter()
+            .map(|stranded| stranded.person().id())
+            .collect();
+        assert_eq!(stranded, vec![stray]);
+        // the first carrier operator leaves the ship
+        let carrier = &environment.vessels()[0];
+        let first_dockyard = carrier
+            .modules_mut()
+            .next()
+            .unwrap()
+            .extract_person(operator);
+        assert!(first_dockyard.is_some());
+
+        environment.proceed(
+            &item_registry,
+            &TechTree::new(),
+            &ProcessTokenContext::new(),
+            &ObjectiveDeciderVault::new(),
+            &mut NoLogger,
+        );
+        assert!(environment.stranded_persons().is_empty());
+        let carrier = &environment.vessels()[0];
+        assert!(carrier.modules().next().unwrap().contains_person(stray));
+    }
+}
@@ -0,0 +1,2 @@
+mod board_vessel_objective;
+pub use board_vessel_objective::*;
@@ -0,0 +1,8 @@
+use dudes_in_space_api::research::TechTree;
+
+static TECHS: &str = include_str!("techs.ron");
+
+pub fn register_techs(mut tree: TechTree) -> TechTree {
+    tree.extend_from_ron(TECHS).unwrap();
+    tree
+}
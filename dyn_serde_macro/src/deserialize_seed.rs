@@ -27,6 +27,8 @@ struct DeserializeSeedXXXAttributes {
 #[darling(attributes(deserialize_seed_xxx), forward_attrs(allow, doc, cfg))]
 struct DeserializeSeedXXXFieldAttributes {
     seed: Option<Expr>,
+    /// missing field is filled with this expression, e.g. a value held by the seed
+    default: Option<Expr>,
 }
 
 #[derive(deluxe::ExtractAttributes, Debug)]
@@ -48,6 +50,9 @@ struct SerdeFieldAttributes {
     skip: bool,
     #[darling(default)]
     skip_deserializing: bool,
+    /// missing field is filled with `Default::default()`
+    #[darling(default)]
+    default: bool,
     with: Option<String>,
 }
 
@@ -149,7 +154,8 @@ fn deserialize_seed_struct(
         let serde_options = SerdeFieldAttributes::from_field(field).expect("Wrong serde attributes");
         let skip = serde_options.skip || serde_options.skip_deserializing;
 
-        let value_arm = match DeserializeSeedXXXFieldAttributes::from_field(field).expect("Wrong attributes").seed {
+        let seed_options = DeserializeSeedXXXFieldAttributes::from_field(field).expect("Wrong attributes");
+        let value_arm = match seed_options.seed {
             Some(seed) => quote! {
                 Field::#variant_ident => {
                     if #locale_variable_ident.is_some() {
@@ -195,8 +201,21 @@ fn deserialize_seed_struct(
             }
         };
 
-        let check_missing = quote! {
-            let #locale_variable_ident: #field_type = #locale_variable_ident.ok_or_else(|| serde::de::Error::missing_field(#field_name))?;
+        let check_missing = if let Some(default) = seed_options.default {
+            quote! {
+                let #locale_variable_ident: #field_type = match #locale_variable_ident {
+                    Some(value) => value,
+                    None => #default,
+                };
+            }
+        } else if serde_options.default {
+            quote! {
+                let #locale_variable_ident: #field_type = #locale_variable_ident.unwrap_or_default();
+            }
+        } else {
+            quote! {
+                let #locale_variable_ident: #field_type = #locale_variable_ident.ok_or_else(|| serde::de::Error::missing_field(#field_name))?;
+            }
         };
 
         FieldRecipe { field_ident, variant_ident, locale_variable_ident, field_name, var_decl, key_arm, value_arm, check_missing, skip, }
@@ -356,7 +375,8 @@ fn deserialize_seed_struct_visitor(
         let serde_options = SerdeFieldAttributes::from_field(field).expect("Wrong serde attributes");
         let skip = serde_options.skip || serde_options.skip_deserializing;
 
-        let seed: Option<Expr> = DeserializeSeedXXXFieldAttributes::from_field(field).expect("Wrong attributes").seed;
+        let seed_options = DeserializeSeedXXXFieldAttributes::from_field(field).expect("Wrong attributes");
+        let seed: Option<Expr> = seed_options.seed;
         let extra_seed = extra_field_seeds.iter().find_map(|(ident, seed)| if ident == &field_ident { Some(seed) } else { None });
 
         println!("xxx_field: {} -> {:?} | {:?}", field_ident, seed, extra_seed);
@@ -408,8 +428,21 @@ fn deserialize_seed_struct_visitor(
             }
         };
 
-        let check_missing = quote! {
-            let #locale_variable_ident: #field_type = #locale_variable_ident.ok_or_else(|| serde::de::Error::missing_field(#field_name))?;
+        let check_missing = if let Some(default) = seed_options.default {
+            quote! {
+                let #locale_variable_ident: #field_type = match #locale_variable_ident {
+                    Some(value) => value,
+                    None => #default,
+                };
+            }
+        } else if serde_options.default {
+            quote! {
+                let #locale_variable_ident: #field_type = #locale_variable_ident.unwrap_or_default();
+            }
+        } else {
+            quote! {
+                let #locale_variable_ident: #field_type = #locale_variable_ident.ok_or_else(|| serde::de::Error::missing_field(#field_name))?;
+            }
         };
 
         FieldRecipe { field_ident, variant_ident, locale_variable_ident, field_name, var_decl, key_arm, value_arm, check_missing, skip, }
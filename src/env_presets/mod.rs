@@ -1 +1,12 @@
 pub(crate) mod preset0;
+
+use dudes_in_space_api::environment::Environment;
+
+pub(crate) static NAMES: &[&str] = &["preset0"];
+
+pub(crate) fn by_name(name: &str, seed: u64) -> Option<Environment> {
+    match name {
+        "preset0" => Some(preset0::new(seed)),
+        _ => None,
+    }
+}
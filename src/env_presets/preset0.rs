@@ -1,11 +1,13 @@
 use dudes_in_space_api::environment::Environment;
 use dudes_in_space_api::person::Person;
-use rand::Rng;
+use dudes_in_space_api::utils::simulation_rng::SimulationRng;
 
-pub(crate) fn new<R: Rng>(rng: &mut R) -> Environment {
-    let person0 = Person::random(rng);
-    let person1 = Person::random(rng);
-    let person2 = Person::random(rng);
+pub(crate) fn new(seed: u64) -> Environment {
+    let mut rng = SimulationRng::new(seed);
+
+    let person0 = Person::random(&mut rng);
+    let person1 = Person::random(&mut rng);
+    let person2 = Person::random(&mut rng);
 
     // let spawn_station_personnel_area = PersonnelArea::new(vec![person0, person1, person2]);
 
@@ -16,5 +18,5 @@ pub(crate) fn new<R: Rng>(rng: &mut R) -> Environment {
 
     // Environment::new(vec![spawn_station])
 
-    Environment::new(vec![], vec![])
+    Environment::new(rng, vec![], vec![])
 }
@@ -1,22 +1,32 @@
+use clap::{Args, Parser, Subcommand};
 use dudes_in_space_api::environment::{Environment, EnvironmentSeed};
+use dudes_in_space_api::item::ItemRegistry;
+use dudes_in_space_api::research::TechTree;
 use dudes_in_space_api::module::{Module, ProcessTokenContext};
-use dudes_in_space_api::person::{DynObjective, Logger, PersonId, Severity};
+use dudes_in_space_api::person::{DynObjective, Logger, ObjectiveDeciderVault, PersonId, Severity};
+use dudes_in_space_api::utils::utils::pretty_duration;
+use dudes_in_space_api::vessel::Vessel;
 use dyn_serde::DynDeserializeSeedVault;
-use rand::rng;
+use rand::random;
 use serde::Serialize;
 use serde::de::DeserializeSeed;
 use std::env::home_dir;
+use std::error::Error;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
 use std::rc::Rc;
+use std::time::Duration;
 
 mod env_presets;
 
 fn env_from_json(
     registry: &DynDeserializeSeedVault<dyn Module>,
+    objective_registry: &DynDeserializeSeedVault<dyn DynObjective>,
     bytes: &[u8],
 ) -> Result<Environment, serde_json::Error> {
     let read = serde_json::de::SliceRead::new(bytes);
     let mut de = serde_json::Deserializer::new(read);
-    let value = EnvironmentSeed::new(registry).deserialize(&mut de)?;
+    let value = EnvironmentSeed::new(registry, objective_registry).deserialize(&mut de)?;
     de.end()?;
     Ok(value)
 }
@@ -24,7 +34,7 @@ fn env_from_json(
 fn env_to_json(env: &Environment) -> Result<Vec<u8>, serde_json::Error> {
     let mut writer = Vec::with_capacity(128);
     let mut ser = serde_json::Serializer::pretty(&mut writer);
-    env.serialize(&mut ser).unwrap();
+    env.serialize(&mut ser)?;
     Ok(writer)
 }
 
@@ -33,65 +43,306 @@ struct StdOutLogger;
 impl Logger for StdOutLogger {
     fn log(&mut self, person: &PersonId, severity: Severity, message: String) {
         match severity {
-            Severity::Error =>         eprintln!("{}: {}", person, message),
+            Severity::Error => eprintln!("{}: {}", person, message),
             Severity::Warning => eprintln!("{}: {}", person, message),
             Severity::Info => println!("{}: {}", person, message),
         }
     }
 }
 
-fn main() {
-    let save_path = home_dir().unwrap().join(".dudes_in_space/save.json");
+#[derive(Parser)]
+#[command(version, about = "Dudes in space simulation driver")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
 
-    let process_token_context = Rc::new(ProcessTokenContext::new());
+#[derive(Subcommand)]
+enum Command {
+    /// Proceed the environment from the save (or from a fresh preset if the save does not exist).
+    /// Generation flags are rejected if the save exists
+    Run {
+        /// Number of times the environment is proceeded
+        #[arg(long, default_value_t = 1)]
+        ticks: u64,
+        #[command(flatten)]
+        generation: GenerationArgs,
+        #[command(flatten)]
+        save: SaveArgs,
+    },
+    /// Create a new save from a preset
+    New {
+        #[command(flatten)]
+        generation: GenerationArgs,
+        #[command(flatten)]
+        save: SaveArgs,
+        /// Overwrite the save if it already exists
+        #[arg(long)]
+        force: bool,
+    },
+    /// Print a summary of the save
+    Inspect {
+        #[command(flatten)]
+        save: SaveArgs,
+    },
+    /// Check that the save can be loaded and written back without changes
+    Validate {
+        #[command(flatten)]
+        save: SaveArgs,
+    },
+}
 
-    let objectives_seed_vault = dudes_in_space_core::register_objectives(Default::default());
-    let objectives_decider_vault =
-        dudes_in_space_core::register_objective_deciders(Default::default());
+#[derive(Args)]
+struct SaveArgs {
+    /// Path to the save file. Defaults to `~/.dudes_in_space/save.json`
+    #[arg(long)]
+    save: Option<PathBuf>,
+}
 
-    let module_factory_seed_vault =
-        dudes_in_space_core::register_module_factories(Default::default()).into_rc();
+impl SaveArgs {
+    fn path(&self) -> PathBuf {
+        self.save
+            .clone()
+            .unwrap_or_else(|| home_dir().unwrap().join(".dudes_in_space/save.json"))
+    }
+}
 
-    let module_seed_vault = dudes_in_space_core::register_modules(
-        Default::default(),
-        module_factory_seed_vault,
-        objectives_seed_vault.into_rc(),
-        process_token_context.clone(),
-    )
-    .into_rc();
+#[derive(Args)]
+struct GenerationArgs {
+    /// Seed of the new environment. It is stored in the save, so runs from the same save are
+    /// reproducible. Random if not specified
+    #[arg(long)]
+    seed: Option<u64>,
+    /// Preset used to generate a new environment. `preset0` if not specified
+    #[arg(long)]
+    preset: Option<String>,
+    /// Simulation time passing during one tick of a new environment, in seconds. 1 if not specified
+    #[arg(long)]
+    tick_duration: Option<f64>,
+}
 
-    let mut environment = if save_path.exists() {
-        env_from_json(
-            &module_seed_vault,
-            &std::fs::read(save_path.as_path()).unwrap(),
+impl GenerationArgs {
+    /// flags which were given on the command line
+    fn given(&self) -> Vec<&'static str> {
+        [
+            ("--seed", self.seed.is_some()),
+            ("--preset", self.preset.is_some()),
+            ("--tick-duration", self.tick_duration.is_some()),
+        ]
+        .into_iter()
+        .filter_map(|(flag, given)| given.then_some(flag))
+        .collect()
+    }
+
+    fn generate(&self) -> Result<Environment, Box<dyn Error>> {
+        let seed = self.seed.unwrap_or_else(random);
+        let preset = self.preset.as_deref().unwrap_or("preset0");
+        let tick_duration = self.tick_duration.unwrap_or(1.);
+        let tick_duration = Duration::try_from_secs_f64(tick_duration)
+            .map_err(|e| format!("Invalid tick duration `{}`: {}", tick_duration, e))?;
+        let mut environment = env_presets::by_name(preset, seed).ok_or_else(|| {
+            format!(
+                "Unknown preset `{}`. Available presets: {}",
+                preset,
+                env_presets::NAMES.join(", ")
+            )
+        })?;
+        environment.set_tick_duration(tick_duration);
+        Ok(environment)
+    }
+}
+
+struct Vaults {
+    process_token_context: Rc<ProcessTokenContext>,
+    objective_decider_vault: ObjectiveDeciderVault,
+    item_registry: ItemRegistry,
+    tech_tree: TechTree,
+    module_seed_vault: Rc<DynDeserializeSeedVault<dyn Module>>,
+    objective_seed_vault: Rc<DynDeserializeSeedVault<dyn DynObjective>>,
+}
+
+impl Vaults {
+    fn new() -> Self {
+        let process_token_context = Rc::new(ProcessTokenContext::new());
+
+        let objective_seed_vault =
+            dudes_in_space_core::register_objectives(Default::default()).into_rc();
+        let objective_decider_vault =
+            dudes_in_space_core::register_objective_deciders(Default::default());
+
+        let module_factory_seed_vault =
+            dudes_in_space_core::register_module_factories(Default::default()).into_rc();
+
+        let module_seed_vault = dudes_in_space_core::register_modules(
+            Default::default(),
+            module_factory_seed_vault,
+            objective_seed_vault.clone(),
+            process_token_context.clone(),
         )
-        .unwrap()
+        .into_rc();
+
+        let item_registry = dudes_in_space_core::register_items(Default::default());
+        let tech_tree = dudes_in_space_core::register_techs(Default::default());
+
+        Self {
+            process_token_context,
+            objective_decider_vault,
+            item_registry,
+            tech_tree,
+            module_seed_vault,
+            objective_seed_vault,
+        }
+    }
+
+    fn load(&self, path: &Path) -> Result<Environment, Box<dyn Error>> {
+        let bytes = std::fs::read(path)
+            .map_err(|e| format!("Can not read save `{}`: {}", path.display(), e))?;
+        let environment = env_from_json(&self.module_seed_vault, &self.objective_seed_vault, &bytes)
+            .map_err(|e| format!("Can not parse save `{}`: {}", path.display(), e))?;
+        environment
+            .validate_items(&self.item_registry)
+            .map_err(|e| format!("Invalid save `{}`: {}", path.display(), e))?;
+        Ok(environment)
+    }
+}
+
+fn store(path: &Path, environment: &Environment) -> Result<(), Box<dyn Error>> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, env_to_json(environment)?)?;
+    Ok(())
+}
+
+fn run(ticks: u64, generation: &GenerationArgs, save: &SaveArgs) -> Result<(), Box<dyn Error>> {
+    let save_path = save.path();
+    let vaults = Vaults::new();
+
+    let mut environment = if save_path.exists() {
+        let given = generation.given();
+        if !given.is_empty() {
+            return Err(format!(
+                "Save `{}` already exists, so {} would be ignored. Use `new --force` to replace it",
+                save_path.display(),
+                given.join(", ")
+            )
+            .into());
+        }
+        vaults.load(&save_path)?
     } else {
-        env_presets::preset0::new(&mut rng())
+        generation.generate()?
     };
 
-    // struct MyAssVisitor;
-    // impl ModuleVisitorMut for MyAssVisitor {
-    //     type Result = ();
-    //     fn visit_assembler(&self, assembler: &mut Assembler) -> Option<Self::Result> {
-    //         // assembler.add_recipe(AssemblyRecipe::new(
-    //         //     vec![Item {
-    //         //         id: "steel".to_string(),
-    //         //         count: 10,
-    //         //     }]
-    //         //     .into(),
-    //         //     todo!(),
-    //         // ));
-    //         Some(())
-    //     }
-    // }
-
-    // environment.vessel_by_id_mut(0).unwrap().visit_modules_mut(&MyAssVisitor);
-
-    environment.proceed(&process_token_context, &objectives_decider_vault, &mut StdOutLogger);
-
-    // println!("{:#?}", environment);
-
-    std::fs::create_dir_all(save_path.parent().unwrap()).unwrap();
-    std::fs::write(save_path, env_to_json(&environment).unwrap()).unwrap();
+    for _ in 0..ticks {
+        environment.proceed(
+            &vaults.item_registry,
+            &vaults.tech_tree,
+            &vaults.process_token_context,
+            &vaults.objective_decider_vault,
+            &mut StdOutLogger,
+        );
+    }
+
+    store(&save_path, &environment)
+}
+
+fn new(generation: &GenerationArgs, save: &SaveArgs, force: bool) -> Result<(), Box<dyn Error>> {
+    let save_path = save.path();
+    if save_path.exists() && !force {
+        return Err(format!(
+            "Save `{}` already exists. Use `--force` to overwrite it",
+            save_path.display()
+        )
+        .into());
+    }
+    store(&save_path, &generation.generate()?)
+}
+
+fn print_vessel(vessel: &Vessel, depth: usize) {
+    let indent = "  ".repeat(depth);
+    println!(
+        "{}vessel {} (owner: {}, pos: ({}, {}), {})",
+        indent,
+        vessel.id(),
+        vessel.owner(),
+        vessel.pos().x(),
+        vessel.pos().y(),
+        if vessel.powered() { "powered" } else { "dark" }
+    );
+    for module in vessel.modules() {
+        println!(
+            "{}  {} {}: {:?}",
+            indent,
+            module.type_id(),
+            module.id(),
+            module.capabilities()
+        );
+        for clamp in module.docking_clamps() {
+            if let Some(docked) = clamp.vessel_docked() {
+                println!("{}    docked ({:?}):", indent, clamp.policy());
+                print_vessel(docked, depth + 3);
+            }
+        }
+    }
+}
+
+fn inspect(save: &SaveArgs) -> Result<(), Box<dyn Error>> {
+    let environment = Vaults::new().load(&save.path())?;
+
+    println!(
+        "time: {} (tick: {})",
+        pretty_duration(environment.clock().elapsed()),
+        pretty_duration(environment.clock().tick_duration())
+    );
+    println!("vessels: {}", environment.vessels().len());
+    for vessel in environment.vessels() {
+        print_vessel(vessel, 1);
+    }
+    println!("nebulae: {}", environment.nebulae().len());
+    println!("asteroids: {}", environment.asteroids().len());
+    println!("wrecks: {}", environment.wrecks().len());
+    println!("accounts: {}", environment.bank().wallets().count());
+    Ok(())
+}
+
+fn validate(save: &SaveArgs) -> Result<(), Box<dyn Error>> {
+    let save_path = save.path();
+    let environment = Vaults::new().load(&save_path)?;
+    let bytes = env_to_json(&environment)?;
+    // process tokens are registered in the context on load, so the reload needs a fresh one
+    let vaults = Vaults::new();
+    let reloaded = env_from_json(&vaults.module_seed_vault, &vaults.objective_seed_vault, &bytes)
+        .map_err(|e| format!("Save can not be parsed after writing it back: {}", e))?;
+    if env_to_json(&reloaded)? != bytes {
+        return Err("Save changes after being loaded and written back".into());
+    }
+    println!("`{}` is valid", save_path.display());
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+
+    let result = match &cli.command {
+        Command::Run {
+            ticks,
+            generation,
+            save,
+        } => run(*ticks, generation, save),
+        Command::New {
+            generation,
+            save,
+            force,
+        } => new(generation, save, *force),
+        Command::Inspect { save } => inspect(save),
+        Command::Validate { save } => validate(save),
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            ExitCode::FAILURE
+        }
+    }
 }